@@ -0,0 +1,21 @@
+//! Times rendering the track TUI with every pane's log buffer full. Frames only lay out the
+//! cached visible lines, so this should stay flat as buffers grow.
+//! Run with `cargo bench --features bench --bench render_panes`.
+
+use bashers::commands::kube::track::time_full_buffer_frames;
+
+const PANES: usize = 4;
+const WIDTH: u16 = 240;
+const HEIGHT: u16 = 60;
+const FRAMES: u32 = 30;
+const ROUNDS: usize = 5;
+
+fn main() {
+    let best = (0..ROUNDS)
+        .map(|_| time_full_buffer_frames(PANES, WIDTH, HEIGHT, FRAMES))
+        .min()
+        .unwrap_or_default();
+
+    println!("render {PANES} full panes at {WIDTH}x{HEIGHT} (best of {ROUNDS}):");
+    println!("  per frame: {best:?}");
+}
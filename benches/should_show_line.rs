@@ -0,0 +1,76 @@
+//! Compares `should_show_line` against the previous `to_uppercase`-per-line implementation.
+//! Run with `cargo bench --bench should_show_line`.
+
+use bashers::commands::kube::track::{should_show_line, TraceState};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const LINES: usize = 200_000;
+const ROUNDS: usize = 5;
+
+fn uppercase_should_show_line(line: &str, in_traceback: &mut bool) -> bool {
+    if line.contains("Traceback (most recent call last)") {
+        *in_traceback = true;
+        return true;
+    }
+    if *in_traceback {
+        if line.starts_with(' ') || line.starts_with('\t') {
+            return true;
+        }
+        *in_traceback = false;
+        if !line.is_empty() {
+            return true;
+        }
+    }
+    let upper = line.to_uppercase();
+    upper.contains("WARNING")
+        || upper.contains("ERROR")
+        || upper.contains("CRITICAL")
+        || upper.contains("FATAL")
+}
+
+fn sample_lines() -> Vec<String> {
+    (0..LINES)
+        .map(|i| match i % 50 {
+            0 => format!(
+                "2026-01-01T00:00:{:02}Z ERROR request {i} failed: upstream timeout",
+                i % 60
+            ),
+            1 => format!("2026-01-01T00:00:{:02}Z warning: retrying job {i}", i % 60),
+            _ => format!(
+                "2026-01-01T00:00:{:02}Z INFO GET /api/v1/items/{i} 200 12ms user-agent=curl/8.0",
+                i % 60
+            ),
+        })
+        .collect()
+}
+
+fn time<S: Default>(lines: &[String], filter: fn(&str, &mut S) -> bool) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut shown = 0;
+    for _ in 0..ROUNDS {
+        let mut state = S::default();
+        let start = Instant::now();
+        shown = lines
+            .iter()
+            .filter(|line| filter(black_box(line), &mut state))
+            .count();
+        best = best.min(start.elapsed());
+    }
+    (best, shown)
+}
+
+fn main() {
+    let lines = sample_lines();
+    let (baseline, baseline_shown) = time(&lines, uppercase_should_show_line);
+    let (current, current_shown) = time::<TraceState>(&lines, should_show_line);
+    assert_eq!(baseline_shown, current_shown, "implementations disagree");
+
+    println!("should_show_line over {LINES} lines (best of {ROUNDS}):");
+    println!("  to_uppercase baseline: {baseline:?}");
+    println!("  keyword matcher:       {current:?}");
+    println!(
+        "  speedup:               {:.1}x",
+        baseline.as_secs_f64() / current.as_secs_f64()
+    );
+}
@@ -1,9 +1,25 @@
 use clap::{Parser, Subcommand};
 
+use crate::commands::docker::build::Progress;
+use crate::commands::kube::kmg::KmgFormat;
+use crate::commands::kube::track::{NavMode, PodColumn, ThemeName, TraceStyle};
+use crate::commands::show::SortKey;
+use crate::utils::output::OutputFormat;
+use crate::utils::project::ProjectType;
+
 #[derive(Parser)]
 #[command(name = "bashers")]
 #[command(about = "Bash command helpers", long_about = None)]
 pub struct BashersApp {
+    /// Disable colored output (also honors NO_COLOR; FORCE_COLOR forces color on)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+    /// Output format; json gives structured results for show, update, kube ctx/ns/pods/kmg and track
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+    /// Log external commands to stderr (-v; update also shows tool output); -vv adds timings
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -20,9 +36,12 @@ pub enum Commands {
         /// Run command in non-interactive mode - will auto select the closest matching library
         #[arg(short = 'y')]
         auto_select: bool,
-        /// Show output from the underlying dependency manager (cargo/uv/poetry)
-        #[arg(short = 'v', long)]
-        verbose: bool,
+        /// Drop fuzzy matches scoring below this (skim score; higher is a closer match)
+        #[arg(long, value_name = "SCORE")]
+        min_score: Option<i64>,
+        /// Project type to use when the project root has several (e.g. Cargo.toml and pyproject.toml)
+        #[arg(long, value_enum)]
+        project: Option<ProjectType>,
     },
     /// Install project dependencies
     Setup {
@@ -35,11 +54,35 @@ pub enum Commands {
         /// Print commands without executing
         #[arg(long)]
         dry_run: bool,
+        /// Describe the steps setup would run, without running them
+        #[arg(long, conflicts_with = "dry_run")]
+        list: bool,
+        /// Project type to use when the project root has several (e.g. Cargo.toml and pyproject.toml)
+        #[arg(long, value_enum)]
+        project: Option<ProjectType>,
     },
     /// List installed packages
     Show {
         /// Filter patterns
         patterns: Vec<String>,
+        /// Print dependencies as JSON
+        #[arg(long)]
+        json: bool,
+        /// Limit the dependency tree depth (cargo only; default: full tree)
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
+        /// Print an aligned table sorted by name or version instead of raw tool output
+        #[arg(long, value_enum)]
+        sort: Option<SortKey>,
+        /// Look up the latest release of each dependency and flag the outdated ones
+        #[arg(long)]
+        outdated: bool,
+        /// Only keep packages whose name fuzzy-matches a pattern with at least this score
+        #[arg(long, value_name = "SCORE", requires = "patterns")]
+        min_score: Option<i64>,
+        /// Project type to use when the project root has several (e.g. Cargo.toml and pyproject.toml)
+        #[arg(long, value_enum)]
+        project: Option<ProjectType>,
     },
     /// Git helper commands
     Git {
@@ -56,16 +99,68 @@ pub enum Commands {
         #[command(subcommand)]
         command: DockerCommands,
     },
+    /// Follow local log files in the multi-pane TUI, like tail -f
+    Tail {
+        /// File paths or glob patterns (quote globs so new files are picked up too)
+        #[arg(required = true)]
+        paths: Vec<String>,
+        /// Only show WARNING/ERROR/CRITICAL log lines and tracebacks
+        #[arg(long)]
+        err_only: bool,
+    },
     /// Print version
     Version,
+    /// Print a shell completion script (e.g. bashers completions zsh > _bashers)
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Show which config files are loaded, or scaffold one with --init
+    Config {
+        /// Write a commented sample to ~/.config/bashers/config.toml
+        #[arg(long)]
+        init: bool,
+    },
     /// Run a command repeatedly and highlight output changes (use -- to separate options from command)
     Watch {
-        /// Seconds between runs
-        #[arg(short = 'n', long, default_value = "2")]
-        interval: u64,
+        /// Seconds between runs (default 2)
+        #[arg(short = 'n', long)]
+        interval: Option<u64>,
         /// Disable diff highlighting; show raw output only
         #[arg(long)]
         no_diff: bool,
+        /// Stop after this many runs
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        max_runs: Option<u64>,
+        /// Exit on the first run whose output differs from the previous one
+        /// (non-zero if --max-runs is reached first)
+        #[arg(long)]
+        until_change: bool,
+        /// Stop with an error as soon as the command exits non-zero
+        #[arg(long)]
+        exit_on_error: bool,
+        /// Ring the terminal bell when the output changes
+        #[arg(long)]
+        bell: bool,
+        /// Shell command to run when the output changes (e.g. notify-send)
+        #[arg(long, value_name = "CMD")]
+        notify_cmd: Option<String>,
+        /// Highlight whole changed lines instead of individual characters
+        #[arg(long, conflicts_with = "no_diff")]
+        line_diff: bool,
+        /// Also show lines removed since the last run (red, `-` prefix)
+        #[arg(long, conflicts_with = "no_diff")]
+        show_removed: bool,
+        /// Capture and diff only the command's stdout
+        #[arg(long, conflicts_with = "stderr_only")]
+        stdout_only: bool,
+        /// Capture and diff only the command's stderr
+        #[arg(long)]
+        stderr_only: bool,
+        /// Run the command through `sh -c` (pipes, globs, variables); off by default
+        #[arg(long)]
+        shell: bool,
         /// Command and arguments to run (e.g. watch -n 1 -- ls -la)
         #[arg(required = true, num_args = 1.., value_terminator = "--")]
         command: Vec<String>,
@@ -87,6 +182,12 @@ pub enum GitCommands {
         /// Sync current branch only (pull + fetch, no checkout)
         #[arg(long)]
         current: bool,
+        /// Pull with --rebase instead of merging
+        #[arg(long)]
+        rebase: bool,
+        /// After fetching, fast-forward every other local branch that tracks an upstream
+        #[arg(long)]
+        all_branches: bool,
         /// Print commands without executing
         #[arg(long)]
         dry_run: bool,
@@ -109,15 +210,75 @@ pub enum DockerCommands {
         /// Build context path (default: directory of the Dockerfile)
         #[arg(short = 'c', long, value_name = "PATH")]
         context: Option<std::path::PathBuf>,
+        /// Build-time variable passed to docker (repeatable)
+        #[arg(long = "build-arg", value_name = "KEY=VALUE")]
+        build_args: Vec<String>,
+        /// Target platform (e.g. linux/amd64)
+        #[arg(long)]
+        platform: Option<String>,
+        /// Build with `docker buildx build --push` and push the image (requires --tag)
+        #[arg(long, requires = "tag")]
+        push: bool,
+        /// Tag the image as <tag>:<short git sha>, adding -dirty for uncommitted changes
+        #[arg(long, requires = "tag")]
+        tag_from_git: bool,
+        /// With --tag-from-git, also tag the image as <tag>:latest
+        #[arg(long, requires = "tag_from_git")]
+        latest: bool,
+        /// Build output style passed to docker (auto, plain or tty)
+        #[arg(long, value_enum)]
+        progress: Option<Progress>,
+        /// Hide build output and print only the image ID
+        #[arg(short, long)]
+        quiet: bool,
+        /// Print the docker build command without running it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Follow logs from running containers matching patterns in a multi-pane TUI
+    Logs {
+        /// Container name patterns (regex)
+        #[arg(required = true)]
+        patterns: Vec<String>,
+        /// Only show WARNING/ERROR/CRITICAL log lines and tracebacks
+        #[arg(long)]
+        err_only: bool,
     },
 }
 
+// Parsed once per run, so boxing `Track`'s many flags would only add noise.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum KubeCommands {
+    /// List kube contexts (current marked with *), or switch with a name or the picker
+    Ctx {
+        /// Context to switch to; omit to pick interactively (or list when not a terminal)
+        name: Option<String>,
+    },
+    /// Show the current context's default namespace, or switch with a name or the picker
+    Ns {
+        /// Namespace to make the default; omit to pick interactively (or list when not a terminal)
+        name: Option<String>,
+    },
     /// Describe pod(s) and show Image lines (pod name regex-matched)
     Kmg {
         /// Pod name patterns (regex); pod matches if any pattern matches
         patterns: Vec<String>,
+        /// How to print matched pods (the global --output json also selects json)
+        #[arg(long, value_enum, default_value_t = KmgFormat::Table)]
+        format: KmgFormat,
+    },
+    /// Print matching pods as `namespace name` lines (or JSON with --output json)
+    Pods {
+        /// Pod name patterns (regex); pod matches if any pattern matches
+        #[arg(required = true)]
+        patterns: Vec<String>,
+        /// Kubeconfig context to use (defaults to the current context)
+        #[arg(long)]
+        context: Option<String>,
+        /// Only look for pods in this namespace (defaults to all namespaces)
+        #[arg(short, long)]
+        namespace: Option<String>,
     },
     /// Follow logs from pods matching patterns (persists through restarts)
     Track {
@@ -126,14 +287,94 @@ pub enum KubeCommands {
         /// Only show WARNING/ERROR/CRITICAL log lines and tracebacks
         #[arg(long)]
         err_only: bool,
+        /// Stack trace format to keep intact with --err-only (auto recognizes all of them)
+        #[arg(long, value_enum, default_value_t = TraceStyle::Auto, requires = "err_only")]
+        trace_style: TraceStyle,
+        /// With --err-only, show the whole multi-line block that starts at lines matching this regex
+        #[arg(long, value_name = "REGEX", requires = "err_only")]
+        block_start: Option<String>,
+        /// Regex for lines that continue a --block-start block (default: indented lines)
+        #[arg(long, value_name = "REGEX", requires = "block_start")]
+        block_continue: Option<String>,
         /// Use simple output mode with context-switch headers instead of TUI
         #[arg(long)]
         simple: bool,
+        /// Kubeconfig context to use (defaults to the current context)
+        #[arg(long)]
+        context: Option<String>,
+        /// Only look for pods in this namespace (defaults to all namespaces)
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Open the TUI on this tab (1-based; clamped to the available tabs)
+        #[arg(long, value_name = "N")]
+        initial_tab: Option<usize>,
+        /// Select the pane for this pod on startup (namespace/name or pod name)
+        #[arg(long, value_name = "POD")]
+        select: Option<String>,
+        /// Pane/tab navigation at the ends of the list: wrap around or clamp
+        /// (default: tabs clamp, panes wrap)
+        #[arg(long, value_enum)]
+        nav: Option<NavMode>,
+        /// Jump to panes that log errors while you are not navigating (toggle with `e`)
+        #[arg(long)]
+        follow_errors: bool,
+        /// Pretty-print JSON log lines with highlighted keys and values (toggle with `J`)
+        #[arg(long)]
+        json_logs: bool,
+        /// Show log lines verbatim with escape sequences visible instead of rendering colors
+        #[arg(long)]
+        raw: bool,
+        /// Minimum pane height in rows; decides how many panes fit on a tab (default 12)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(3..))]
+        min_pane_height: Option<u16>,
+        /// Keep every pane on one tab, shrinking panes and scrolling to the selection
+        #[arg(long, conflicts_with = "min_pane_height")]
+        single_tab: bool,
+        /// Extra pod fields to fetch alongside namespace and name (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', value_name = "COLUMNS")]
+        columns: Vec<PodColumn>,
+        /// Only track pods scheduled on this node
+        #[arg(long, value_name = "NAME")]
+        node: Option<String>,
+        /// Seconds to wait for kubectl when listing pods (default: 10s request, 15s overall)
+        #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+        /// After an authentication hint, wait a moment and retry the pod lookup once
+        #[arg(long)]
+        retry: bool,
+        /// Only show logs since each pod's containers last started (re-checked on reconnect)
+        #[arg(long)]
+        since_restart: bool,
+        /// Seconds to reuse a pod listing when adding patterns in the TUI (default: 3; 0 disables)
+        #[arg(long, value_name = "SECS")]
+        pod_cache_ttl: Option<u64>,
+        /// Seconds between checks for new matching pods in the TUI (default: 5)
+        #[arg(long = "poll", value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+        poll_interval: Option<u64>,
+        /// Pane color palette (default: the config file's `[kube] palette`, else `default`)
+        #[arg(long, value_enum)]
+        theme: Option<ThemeName>,
     },
 }
 
 #[derive(Subcommand)]
 pub enum SelfCommands {
-    /// Update bashers to the latest version
-    Update,
+    /// Update bashers to the latest version (or a pinned one)
+    Update {
+        /// Install this release instead of the latest (e.g. 0.8.5)
+        #[arg(long, value_name = "VERSION")]
+        version: Option<String>,
+        /// Include pre-releases when looking up the latest version
+        #[arg(long, conflicts_with = "version")]
+        pre: bool,
+        /// Reinstall the current version or downgrade to an older one
+        #[arg(long)]
+        force: bool,
+        /// Only report whether a newer version is available; install nothing
+        #[arg(long, conflicts_with_all = ["force", "version"])]
+        check: bool,
+        /// With --check, exit with status 1 when an update is available
+        #[arg(long, requires = "check")]
+        exit_code: bool,
+    },
 }
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+use crate::cli::{BashersApp, TOPLEVEL_ALIAS_PARENTS};
+
+/// The CLI definition plus the top-level aliases (`bashers sync` for `bashers git sync`),
+/// so completions offer the same shortcuts `run` accepts.
+fn completion_command() -> clap::Command {
+    let root = BashersApp::command();
+    let aliases: Vec<clap::Command> = root
+        .get_subcommands()
+        .filter(|parent| TOPLEVEL_ALIAS_PARENTS.contains(&parent.get_name()))
+        .flat_map(|parent| parent.get_subcommands().cloned())
+        .filter(|alias| root.find_subcommand(alias.get_name()).is_none())
+        .collect();
+    root.subcommands(aliases)
+}
+
+pub fn write(shell: Shell, out: &mut dyn Write) {
+    let mut cmd = completion_command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, out);
+}
+
+pub fn run(shell: Shell) -> Result<()> {
+    // Generate into a buffer first: clap_complete panics on write errors such as a closed pipe.
+    let mut script = Vec::new();
+    write(shell, &mut script);
+    std::io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_completion_command_includes_aliases() {
+        let cmd = completion_command();
+        for name in ["sync", "build", "logs", "kmg", "track", "git", "watch"] {
+            assert!(cmd.find_subcommand(name).is_some(), "missing {}", name);
+        }
+    }
+
+    #[test]
+    fn test_generate_for_each_shell() {
+        for shell in Shell::value_variants() {
+            let mut out = Vec::new();
+            write(*shell, &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("bashers"), "{:?}", shell);
+            assert!(script.contains("track"), "{:?}", shell);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+
+use crate::utils::config;
+
+const SAMPLE_CONFIG: &str = r##"# bashers configuration. Every setting is optional; command-line flags always win.
+# A repo-local .bashers.toml overrides the values in this file.
+
+[kube]
+# Kubeconfig context for `kube track` (defaults to kubectl's current context)
+# context = "staging"
+# Namespace for `kube track` (defaults to all namespaces)
+# namespace = "default"
+# Pane colors for `kube track`, replacing the --theme palette
+# palette = ["#e69f00", "#56b4e9", "#009e73", "#f0e442"]
+
+[watch]
+# Seconds between runs for `watch`
+# interval = 2
+
+[docker]
+# Target platform for `docker build`
+# platform = "linux/amd64"
+"##;
+
+/// `bashers config`: lists the config files, or writes a commented sample with `--init`.
+pub fn run(init: bool) -> Result<()> {
+    if init {
+        let path = config::global_config_file()
+            .context("Cannot locate the config directory; set HOME or XDG_CONFIG_HOME")?;
+        if path.exists() {
+            anyhow::bail!("{} already exists", path.display());
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(&path, SAMPLE_CONFIG)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("Wrote {}", path.display());
+        return Ok(());
+    }
+
+    for path in config::config_paths() {
+        let state = if !path.is_file() {
+            "not found".to_string()
+        } else {
+            match config::read(&path) {
+                Ok(_) => "loaded".to_string(),
+                Err(e) => format!("invalid: {:#}", e),
+            }
+        };
+        println!("{} ({state})", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_config_parses_to_defaults() {
+        let parsed: config::Config = toml::from_str(SAMPLE_CONFIG).unwrap();
+        assert_eq!(parsed, config::Config::default());
+    }
+}
@@ -1,13 +1,49 @@
+use crate::utils::git::{has_local_changes, short_head};
+use crate::utils::shell;
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
-pub fn run(
-    dockerfile: Option<&Path>,
-    tag: Option<&str>,
-    no_cache: bool,
-    context: Option<&Path>,
-) -> Result<()> {
-    let path = dockerfile.map(PathBuf::from).unwrap_or_else(|| {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Progress {
+    Auto,
+    Plain,
+    Tty,
+}
+
+impl Progress {
+    fn as_arg(self) -> &'static str {
+        match self {
+            Progress::Auto => "auto",
+            Progress::Plain => "plain",
+            Progress::Tty => "tty",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BuildOptions {
+    pub dockerfile: Option<PathBuf>,
+    pub tag: Option<String>,
+    pub no_cache: bool,
+    pub context: Option<PathBuf>,
+    pub build_args: Vec<String>,
+    pub platform: Option<String>,
+    pub push: bool,
+    pub tag_from_git: bool,
+    pub latest: bool,
+    pub progress: Option<Progress>,
+    pub quiet: bool,
+    pub dry_run: bool,
+}
+
+pub fn run(options: &BuildOptions) -> Result<()> {
+    for build_arg in &options.build_args {
+        validate_build_arg(build_arg)?;
+    }
+    if options.push && options.tag.is_none() {
+        anyhow::bail!("--push requires --tag");
+    }
+    let path = options.dockerfile.clone().unwrap_or_else(|| {
         std::env::current_dir()
             .map(|cwd| cwd.join("Dockerfile"))
             .unwrap_or_else(|_| PathBuf::from("Dockerfile"))
@@ -15,29 +51,143 @@ pub fn run(
     let dockerfile_abs = path
         .canonicalize()
         .with_context(|| format!("Dockerfile path not found: {}", path.display()))?;
-    eprintln!("Building: {}", dockerfile_abs.display());
-    let context_path: PathBuf = context.map(|p| p.to_path_buf()).unwrap_or_else(|| {
+    let context_path: PathBuf = options.context.clone().unwrap_or_else(|| {
         dockerfile_abs
             .parent()
             .map(|p| p.to_path_buf())
             .unwrap_or_else(|| PathBuf::from("."))
     });
-    let mut cmd = std::process::Command::new("docker");
-    cmd.arg("build").arg("-f").arg(&dockerfile_abs);
-    if let Some(t) = tag {
-        cmd.arg("-t").arg(t);
+    let tags = resolve_tags(options)?;
+    let args = docker_build_args(&dockerfile_abs, &context_path, &tags, options);
+    if options.dry_run {
+        let quoted: Vec<String> = args.iter().map(|a| shell::quote(a)).collect();
+        println!("docker {}", quoted.join(" "));
+        return Ok(());
     }
-    if no_cache {
-        cmd.arg("--no-cache");
+    if options.push && !buildx_available() {
+        anyhow::bail!(
+            "--push needs docker buildx, which was not found. Install the buildx plugin: https://docs.docker.com/go/buildx/"
+        );
+    }
+    if options.quiet {
+        return run_quiet(&args);
     }
-    cmd.arg(&context_path);
-    let status = cmd.status().context("Failed to run docker build")?;
+    eprintln!("Building: {}", dockerfile_abs.display());
+    let status = std::process::Command::new("docker")
+        .args(&args)
+        .status()
+        .context("Failed to run docker build")?;
     if !status.success() {
         anyhow::bail!("docker build exited with {}", status);
     }
     Ok(())
 }
 
+fn run_quiet(args: &[String]) -> Result<()> {
+    let output = std::process::Command::new("docker")
+        .args(args)
+        .output()
+        .context("Failed to run docker build")?;
+    if !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        anyhow::bail!("docker build exited with {}", output.status);
+    }
+    if let Some(id) = last_non_empty_line(&String::from_utf8_lossy(&output.stdout)) {
+        println!("{}", id);
+    }
+    Ok(())
+}
+
+fn last_non_empty_line(text: &str) -> Option<&str> {
+    text.lines().map(str::trim).rfind(|l| !l.is_empty())
+}
+
+fn buildx_available() -> bool {
+    std::process::Command::new("docker")
+        .args(["buildx", "version"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+fn resolve_tags(options: &BuildOptions) -> Result<Vec<String>> {
+    let Some(tag) = &options.tag else {
+        return Ok(Vec::new());
+    };
+    if !options.tag_from_git {
+        return Ok(vec![tag.clone()]);
+    }
+    if tag
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains(':'))
+    {
+        anyhow::bail!(
+            "--tag-from-git expects --tag without a version (e.g. repo/name), got '{}'",
+            tag
+        );
+    }
+    let sha = short_head().context("--tag-from-git must be run inside a git repository")?;
+    let dirty = has_local_changes()?;
+    Ok(git_tags(tag, &sha, dirty, options.latest))
+}
+
+fn git_tags(repo: &str, sha: &str, dirty: bool, latest: bool) -> Vec<String> {
+    let suffix = if dirty { "-dirty" } else { "" };
+    let mut tags = vec![format!("{}:{}{}", repo, sha, suffix)];
+    if latest {
+        tags.push(format!("{}:latest", repo));
+    }
+    tags
+}
+
+fn validate_build_arg(build_arg: &str) -> Result<()> {
+    match build_arg.split_once('=') {
+        Some((key, _)) if !key.is_empty() => Ok(()),
+        _ => anyhow::bail!("Invalid --build-arg '{}': expected KEY=VALUE", build_arg),
+    }
+}
+
+fn docker_build_args(
+    dockerfile: &Path,
+    context: &Path,
+    tags: &[String],
+    options: &BuildOptions,
+) -> Vec<String> {
+    let mut args = if options.push {
+        vec![
+            "buildx".to_string(),
+            "build".to_string(),
+            "--push".to_string(),
+        ]
+    } else {
+        vec!["build".to_string()]
+    };
+    args.extend(["-f".to_string(), dockerfile.display().to_string()]);
+    for tag in tags {
+        args.extend(["-t".to_string(), tag.clone()]);
+    }
+    if options.no_cache {
+        args.push("--no-cache".to_string());
+    }
+    if let Some(progress) = options.progress {
+        args.extend(["--progress".to_string(), progress.as_arg().to_string()]);
+    }
+    if options.quiet {
+        args.push("--quiet".to_string());
+    }
+    if let Some(platform) = &options.platform {
+        args.extend(["--platform".to_string(), platform.clone()]);
+    }
+    for build_arg in &options.build_args {
+        args.extend(["--build-arg".to_string(), build_arg.clone()]);
+    }
+    args.push(context.display().to_string());
+    args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,12 +195,152 @@ mod tests {
 
     #[test]
     fn test_run_nonexistent_dockerfile_errors() {
-        let result = run(
-            Some(Path::new("/nonexistent/dockerfile")),
-            None,
-            false,
-            None,
-        );
+        let result = run(&BuildOptions {
+            dockerfile: Some(PathBuf::from("/nonexistent/dockerfile")),
+            ..Default::default()
+        });
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_build_arg() {
+        assert!(validate_build_arg("VERSION=1.2").is_ok());
+        assert!(validate_build_arg("EMPTY=").is_ok());
+        assert!(validate_build_arg("NOEQUALS").is_err());
+        assert!(validate_build_arg("=value").is_err());
+    }
+
+    #[test]
+    fn test_docker_build_args() {
+        let options = BuildOptions {
+            tag: Some("app:latest".to_string()),
+            no_cache: true,
+            build_args: vec!["A=1".to_string(), "B=two words".to_string()],
+            platform: Some("linux/amd64".to_string()),
+            ..Default::default()
+        };
+        let tags = resolve_tags(&options).unwrap();
+        let args = docker_build_args(
+            Path::new("/src/Dockerfile"),
+            Path::new("/src"),
+            &tags,
+            &options,
+        );
+        assert_eq!(
+            args,
+            vec![
+                "build",
+                "-f",
+                "/src/Dockerfile",
+                "-t",
+                "app:latest",
+                "--no-cache",
+                "--platform",
+                "linux/amd64",
+                "--build-arg",
+                "A=1",
+                "--build-arg",
+                "B=two words",
+                "/src",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_docker_build_args_push_uses_buildx() {
+        let options = BuildOptions {
+            tag: Some("registry/app:1".to_string()),
+            push: true,
+            ..Default::default()
+        };
+        let tags = resolve_tags(&options).unwrap();
+        let args = docker_build_args(
+            Path::new("/src/Dockerfile"),
+            Path::new("/src"),
+            &tags,
+            &options,
+        );
+        assert_eq!(
+            args,
+            vec![
+                "buildx",
+                "build",
+                "--push",
+                "-f",
+                "/src/Dockerfile",
+                "-t",
+                "registry/app:1",
+                "/src",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_push_without_tag_errors() {
+        let result = run(&BuildOptions {
+            push: true,
+            dry_run: true,
+            ..Default::default()
+        });
+        assert!(result.unwrap_err().to_string().contains("--tag"));
+    }
+
+    #[test]
+    fn test_docker_build_args_progress_and_quiet() {
+        let options = BuildOptions {
+            progress: Some(Progress::Plain),
+            quiet: true,
+            ..Default::default()
+        };
+        let tags = resolve_tags(&options).unwrap();
+        let args = docker_build_args(
+            Path::new("/src/Dockerfile"),
+            Path::new("/src"),
+            &tags,
+            &options,
+        );
+        assert_eq!(
+            args,
+            vec![
+                "build",
+                "-f",
+                "/src/Dockerfile",
+                "--progress",
+                "plain",
+                "--quiet",
+                "/src",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_non_empty_line() {
+        assert_eq!(
+            last_non_empty_line("step 1\nsha256:abc123\n\n"),
+            Some("sha256:abc123")
+        );
+        assert_eq!(last_non_empty_line("  \n"), None);
+    }
+
+    #[test]
+    fn test_git_tags() {
+        assert_eq!(
+            git_tags("repo/app", "abc1234", false, false),
+            vec!["repo/app:abc1234"]
+        );
+        assert_eq!(
+            git_tags("repo/app", "abc1234", true, true),
+            vec!["repo/app:abc1234-dirty", "repo/app:latest"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_tags_rejects_versioned_tag() {
+        let options = BuildOptions {
+            tag: Some("registry:5000/app:1.0".to_string()),
+            tag_from_git: true,
+            ..Default::default()
+        };
+        assert!(resolve_tags(&options).is_err());
+    }
 }
@@ -0,0 +1,41 @@
+use crate::commands::kube::track::tui::{self, docker};
+use crate::commands::kube::track::{pod_pattern_regex, print_warning, LogFilter, TuiOptions};
+use crate::utils::{colors, spinner};
+use anyhow::Result;
+use regex::Regex;
+
+pub fn run(patterns: &[String], err_only: bool) -> Result<()> {
+    let regexes = patterns
+        .iter()
+        .map(|p| pod_pattern_regex(p))
+        .collect::<Result<Vec<Regex>>>()?;
+
+    let mut sp = spinner::create_spinner("Finding containers...");
+    let containers = match docker::find_matching_containers(&regexes) {
+        Ok(c) => c,
+        Err(e) => {
+            spinner::stop_spinner(sp.as_mut());
+            return Err(e);
+        }
+    };
+    spinner::finish_with_message(sp.as_mut(), "Found containers");
+
+    let use_color = colors::use_color(atty::Stream::Stderr);
+    for (i, pattern) in patterns.iter().enumerate() {
+        if !containers.iter().any(|c| c.pattern_idx == i) {
+            print_warning(
+                &format!("No running containers matching pattern: \"{pattern}\""),
+                use_color,
+            );
+        }
+    }
+    if containers.is_empty() {
+        return Ok(());
+    }
+
+    let filter = LogFilter {
+        err_only,
+        ..LogFilter::default()
+    };
+    tui::run_docker(containers, regexes, filter, TuiOptions::default())
+}
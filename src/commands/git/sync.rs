@@ -1,12 +1,16 @@
-use crate::utils::colors::Colors;
+use crate::utils::colors::{self, Colors};
+use crate::utils::git::{get_current_branch, get_default_branch, has_local_changes};
+use crate::utils::log::CommandExt;
 use crate::utils::spinner;
 use anyhow::{Context, Result};
 use spinoff::Color as SpinoffColor;
 use std::io::{self, Write};
 use std::process::{self, Command};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use termcolor::{Color, ColorSpec, StandardStream, WriteColor};
 
 const SEPARATOR: &str = "────────────────────────────────────────";
+const STASH_PUSH_ARGS: [&str; 4] = ["stash", "push", "-m", "bashers sync"];
+const STASH_POP_ARGS: [&str; 2] = ["stash", "pop"];
 
 fn print_separator(colors: &mut Colors) -> io::Result<()> {
     colors.reset()?;
@@ -15,11 +19,7 @@ fn print_separator(colors: &mut Colors) -> io::Result<()> {
 }
 
 fn fail_cmd(cmd: &str) -> ! {
-    let mut stderr = StandardStream::stderr(if atty::is(atty::Stream::Stderr) {
-        ColorChoice::Auto
-    } else {
-        ColorChoice::Never
-    });
+    let mut stderr = StandardStream::stderr(colors::color_choice(atty::Stream::Stderr));
     let _ = stderr.set_color(ColorSpec::new().set_fg(Some(Color::Red)));
     let _ = writeln!(&mut stderr, "✗ Command: `{}` failed.", cmd);
     let _ = stderr.reset();
@@ -27,7 +27,7 @@ fn fail_cmd(cmd: &str) -> ! {
     process::exit(1);
 }
 
-pub fn run(current: bool, dry_run: bool) -> Result<()> {
+pub fn run(current: bool, rebase: bool, all_branches: bool, dry_run: bool) -> Result<()> {
     let branch = if current {
         get_current_branch()
             .context("Could not determine current branch. Are you in a git repository?")?
@@ -36,53 +36,44 @@ pub fn run(current: bool, dry_run: bool) -> Result<()> {
             .context("Could not determine default branch. Are you in a git repository?")?
     };
 
+    // Local changes are stashed before leaving the branch they belong to, and restored on
+    // that branch once the pull is done.
+    let original = if current {
+        branch.clone()
+    } else {
+        get_current_branch()
+            .context("Could not determine current branch. Are you in a git repository?")?
+    };
+    let dirty = has_local_changes()?;
+    let return_to = (dirty && original != branch).then_some(original.as_str());
+
     let mut colors = Colors::new();
+    let pull_args = pull_args(&branch, rebase);
 
-    if !current {
-        if dry_run {
-            println!("git checkout {}", branch);
-        } else {
-            let branch_clone = branch.clone();
-            let spinner_msg = format!("Checking out [{}]", branch);
-            let success_msg = format!("Checked out [{}]", branch);
-            let output = spinner::run_with_completion(
-                dry_run,
-                &spinner_msg,
-                &success_msg,
-                Some(SpinoffColor::Red),
-                || {
-                    Command::new("git")
-                        .args(["checkout", &branch_clone])
-                        .output()
-                },
-                |o| o.status.success(),
-            );
-            match output {
-                Ok(ref out) => {
-                    if !out.status.success() {
-                        spinner::print_failure_message(&format!("Checking out [{}]", branch));
-                    }
-                    print_pull_output(&mut colors, &out.stdout, &out.stderr)?;
-                    if !out.status.success() {
-                        fail_cmd(&format!("git checkout {}", branch));
-                    }
-                }
-                Err(_) => fail_cmd(&format!("git checkout {}", branch)),
-            }
+    if dry_run {
+        let (before_pull, pull) = dry_run_steps(&branch, &pull_args, current, dirty, return_to);
+        before_pull.iter().for_each(|step| println!("{}", step));
+        print_separator(&mut colors)?;
+        pull.iter().for_each(|step| println!("{}", step));
+    } else {
+        if dirty {
+            stash_changes(&mut colors)?;
+        }
+        if !current {
+            checkout(&mut colors, &branch, dirty)?;
+        }
+        print_separator(&mut colors)?;
+        run_pull_step(&mut colors, &branch, &pull_args, dirty)?;
+        if let Some(original) = return_to {
+            checkout(&mut colors, original, dirty)?;
+        }
+        if dirty {
+            restore_stash(&mut colors)?;
         }
     }
 
     print_separator(&mut colors)?;
 
-    let _did_stash = if dry_run {
-        println!("git pull origin {}", branch);
-        false
-    } else {
-        run_pull_step(&mut colors, &branch, dry_run)?
-    };
-
-    print_separator(&mut colors)?;
-
     if dry_run {
         println!("git fetch --all");
     } else {
@@ -91,7 +82,7 @@ pub fn run(current: bool, dry_run: bool) -> Result<()> {
             "Fetching all",
             "Fetched all",
             Some(SpinoffColor::Green),
-            || Command::new("git").args(["fetch", "--all"]).output(),
+            || Command::new("git").args(["fetch", "--all"]).logged_output(),
             |o| o.status.success(),
         );
         match output {
@@ -108,27 +99,221 @@ pub fn run(current: bool, dry_run: bool) -> Result<()> {
         }
     }
 
+    if all_branches {
+        print_separator(&mut colors)?;
+        sync_tracking_branches(&mut colors, &branch, dry_run)?;
+    }
+
     spinner::print_success_message("Done.");
 
     Ok(())
 }
 
-fn run_pull_step(colors: &mut Colors, branch: &str, dry_run: bool) -> Result<bool> {
-    let branch_clone = branch.to_string();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BranchUpdate {
+    UpToDate,
+    FastForwarded,
+    Diverged,
+}
+
+/// Fast-forwards every local branch with an upstream to that upstream, except `synced_branch`,
+/// which the pull step already updated. Diverged branches are reported and left alone.
+fn sync_tracking_branches(colors: &mut Colors, synced_branch: &str, dry_run: bool) -> Result<()> {
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--format=%(refname:short) %(upstream:short)",
+            "refs/heads",
+        ])
+        .logged_output()
+        .context("Failed to run git for-each-ref")?;
+    if !output.status.success() {
+        fail_cmd("git for-each-ref refs/heads");
+    }
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let branches: Vec<(&str, &str)> = parse_tracking_branches(&listing)
+        .into_iter()
+        .filter(|(branch, _)| *branch != synced_branch)
+        .collect();
+
+    if branches.is_empty() {
+        colors.println("No other tracking branches to update.")?;
+        colors.flush()?;
+        return Ok(());
+    }
+
+    for (branch, upstream) in branches {
+        if dry_run {
+            println!("git merge-base --is-ancestor {} {}", branch, upstream);
+            println!("git update-ref refs/heads/{} {}", branch, upstream);
+            continue;
+        }
+        match fast_forward_branch(branch, upstream)? {
+            BranchUpdate::FastForwarded => {
+                colors.green()?;
+                colors.print("✓ ")?;
+                colors.reset()?;
+                colors.println(&format!("{} fast-forwarded to {}", branch, upstream))?;
+            }
+            BranchUpdate::UpToDate => {
+                colors.println(&format!("  {} is up to date", branch))?;
+            }
+            BranchUpdate::Diverged => {
+                colors.yellow()?;
+                colors.print("⚠ ")?;
+                colors.reset()?;
+                colors.println(&format!("{} skipped: diverged from {}", branch, upstream))?;
+            }
+        }
+    }
+    colors.flush()?;
+    Ok(())
+}
+
+fn parse_tracking_branches(output: &str) -> Vec<(&str, &str)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let branch = parts.next()?;
+            let upstream = parts.next()?;
+            Some((branch, upstream))
+        })
+        .collect()
+}
+
+fn rev_parse(rev: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", rev])
+        .logged_output()
+        .context("Failed to run git rev-parse")?;
+    if !output.status.success() {
+        anyhow::bail!("Could not resolve {}", rev);
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+fn fast_forward_branch(branch: &str, upstream: &str) -> Result<BranchUpdate> {
+    let local = rev_parse(&format!("refs/heads/{}", branch))?;
+    let remote = rev_parse(upstream)?;
+    if local == remote {
+        return Ok(BranchUpdate::UpToDate);
+    }
+
+    let is_ancestor = Command::new("git")
+        .args(["merge-base", "--is-ancestor", &local, &remote])
+        .logged_status()
+        .context("Failed to run git merge-base")?
+        .success();
+    if !is_ancestor {
+        return Ok(BranchUpdate::Diverged);
+    }
+
+    let ref_name = format!("refs/heads/{}", branch);
+    // Passing the old value makes the update fail if the branch moved in the meantime.
+    let status = Command::new("git")
+        .args(["update-ref", &ref_name, &remote, &local])
+        .logged_status()
+        .context("Failed to run git update-ref")?;
+    if !status.success() {
+        fail_cmd(&format!("git update-ref {} {} {}", ref_name, remote, local));
+    }
+    Ok(BranchUpdate::FastForwarded)
+}
+
+/// The git commands a dry run prints, split at the separator before the pull.
+fn dry_run_steps(
+    branch: &str,
+    pull_args: &[&str],
+    current: bool,
+    dirty: bool,
+    return_to: Option<&str>,
+) -> (Vec<String>, Vec<String>) {
+    let mut before_pull = Vec::new();
+    if dirty {
+        before_pull.push(format!("git {}", STASH_PUSH_ARGS.join(" ")));
+    }
+    if !current {
+        before_pull.push(format!("git checkout {}", branch));
+    }
+    let mut pull = vec![format!("git {}", pull_args.join(" "))];
+    if let Some(original) = return_to {
+        pull.push(format!("git checkout {}", original));
+    }
+    if dirty {
+        pull.push(format!("git {}", STASH_POP_ARGS.join(" ")));
+    }
+    (before_pull, pull)
+}
+
+/// Checks out `branch`, exiting on failure. `stashed` adds a hint that local changes are
+/// still in the stash.
+fn checkout(colors: &mut Colors, branch: &str, stashed: bool) -> Result<()> {
+    let spinner_msg = format!("Checking out [{}]", branch);
+    let success_msg = format!("Checked out [{}]", branch);
+    let output = spinner::run_with_completion(
+        false,
+        &spinner_msg,
+        &success_msg,
+        Some(SpinoffColor::Red),
+        || {
+            Command::new("git")
+                .args(["checkout", branch])
+                .logged_output()
+        },
+        |o| o.status.success(),
+    );
+    match output {
+        Ok(ref out) => {
+            if !out.status.success() {
+                spinner::print_failure_message(&spinner_msg);
+            }
+            print_pull_output(colors, &out.stdout, &out.stderr)?;
+            if !out.status.success() {
+                if stashed {
+                    warn_still_stashed();
+                }
+                fail_cmd(&format!("git checkout {}", branch));
+            }
+        }
+        Err(_) => fail_cmd(&format!("git checkout {}", branch)),
+    }
+    Ok(())
+}
+
+fn warn_still_stashed() {
+    spinner::print_warning_message(
+        "Your local changes are still stashed; run `git stash pop` once this is fixed.",
+    );
+}
+
+fn pull_args(branch: &str, rebase: bool) -> Vec<&str> {
+    let mut args = vec!["pull"];
+    if rebase {
+        args.push("--rebase");
+    }
+    args.extend(["origin", branch]);
+    args
+}
+
+/// Pulls `branch`, exiting on failure. `stashed` adds a hint that local changes are still
+/// in the stash.
+fn run_pull_step(
+    colors: &mut Colors,
+    branch: &str,
+    pull_args: &[&str],
+    stashed: bool,
+) -> Result<()> {
     let pull_spinner_msg = format!("Pulling origin [{}]", branch);
     let pull_success_msg = format!("Pulled origin [{}]", branch);
-    let pull_cmd = format!("git pull origin {}", branch);
+    let pull_cmd = format!("git {}", pull_args.join(" "));
 
     let output = spinner::run_with_completion(
-        dry_run,
+        false,
         &pull_spinner_msg,
         &pull_success_msg,
         Some(SpinoffColor::Green),
-        || {
-            Command::new("git")
-                .args(["pull", "origin", &branch_clone])
-                .output()
-        },
+        || Command::new("git").args(pull_args).logged_output(),
         |o| o.status.success(),
     );
     let output = match output {
@@ -140,9 +325,101 @@ fn run_pull_step(colors: &mut Colors, branch: &str, dry_run: bool) -> Result<boo
     }
     print_pull_output(colors, &output.stdout, &output.stderr)?;
     if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if is_conflict_output(&stdout) || is_conflict_output(&stderr) {
+            let rebase = pull_args.contains(&"--rebase");
+            print_conflicts(colors, &conflicted_files()?, rebase)?;
+        }
+        if stashed {
+            warn_still_stashed();
+        }
         fail_cmd(&pull_cmd);
     }
-    Ok(false)
+    Ok(())
+}
+
+fn is_conflict_output(output: &str) -> bool {
+    output.contains("CONFLICT") || output.contains("Automatic merge failed")
+}
+
+fn conflicted_files() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .logged_output()
+        .context("Failed to list conflicted files")?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+fn conflict_hint(rebase: bool) -> &'static str {
+    if rebase {
+        "Resolve the conflicts and run `git rebase --continue`, or `git rebase --abort` to undo."
+    } else {
+        "Resolve the conflicts and commit, or run `git merge --abort` to undo."
+    }
+}
+
+fn print_conflicts(colors: &mut Colors, files: &[String], rebase: bool) -> io::Result<()> {
+    colors.yellow()?;
+    colors.println("\nMerge conflicts detected:")?;
+    for file in files {
+        colors.println(&format!("  {}", file))?;
+    }
+    colors.println(conflict_hint(rebase))?;
+    colors.reset()?;
+    colors.flush()
+}
+
+fn stash_changes(colors: &mut Colors) -> Result<()> {
+    let stash_cmd = format!("git {}", STASH_PUSH_ARGS.join(" "));
+    let output = spinner::run_with_completion(
+        false,
+        "Stashing local changes",
+        "Stashed local changes",
+        Some(SpinoffColor::Yellow),
+        || Command::new("git").args(STASH_PUSH_ARGS).logged_output(),
+        |o| o.status.success(),
+    );
+    let output = match output {
+        Ok(o) => o,
+        Err(_) => fail_cmd(&stash_cmd),
+    };
+    print_pull_output(colors, &output.stdout, &output.stderr)?;
+    if !output.status.success() {
+        spinner::print_failure_message("Stashing local changes");
+        fail_cmd(&stash_cmd);
+    }
+    Ok(())
+}
+
+fn restore_stash(colors: &mut Colors) -> Result<()> {
+    let pop_cmd = format!("git {}", STASH_POP_ARGS.join(" "));
+    let output = spinner::run_with_completion(
+        false,
+        "Restoring local changes",
+        "Restored local changes",
+        Some(SpinoffColor::Yellow),
+        || Command::new("git").args(STASH_POP_ARGS).logged_output(),
+        |o| o.status.success(),
+    );
+    let output = match output {
+        Ok(o) => o,
+        Err(_) => fail_cmd(&pop_cmd),
+    };
+    print_pull_output(colors, &output.stdout, &output.stderr)?;
+    if !output.status.success() {
+        spinner::print_failure_message("Restoring local changes");
+        spinner::print_warning_message(
+            "Stashed changes conflict with the pulled commits. Resolve the conflicts, then run `git stash drop`.",
+        );
+        fail_cmd(&pop_cmd);
+    }
+    Ok(())
 }
 
 fn is_fast_forward_summary_line(line: &str) -> bool {
@@ -189,232 +466,69 @@ fn print_pull_output(colors: &mut Colors, stdout: &[u8], stderr: &[u8]) -> io::R
     colors.flush()
 }
 
-fn get_current_branch() -> Result<String> {
-    let output = Command::new("git")
-        .args(["branch", "--show-current"])
-        .output()
-        .context("Failed to run git branch --show-current")?;
-
-    if output.status.success() {
-        let branch = String::from_utf8(output.stdout)?.trim().to_string();
-        if branch.is_empty() {
-            anyhow::bail!("Not on a branch (detached HEAD)");
-        }
-        return Ok(branch);
-    }
-    anyhow::bail!("Could not determine current branch")
-}
-
-fn get_default_branch() -> Result<String> {
-    let output = Command::new("git")
-        .args(["ls-remote", "--symref", "origin", "HEAD"])
-        .output()
-        .context("Failed to run git ls-remote")?;
-
-    if output.status.success() {
-        let stdout = String::from_utf8(output.stdout)?;
-        for line in stdout.lines() {
-            if line.starts_with("ref:") && line.contains("HEAD") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let ref_path = parts[1];
-                    if let Some(branch) = ref_path.strip_prefix("refs/heads/") {
-                        return Ok(branch.to_string());
-                    }
-                }
-            }
-        }
-    }
-
-    let output = Command::new("git")
-        .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
-        .output()
-        .context("Failed to run git symbolic-ref")?;
-
-    if output.status.success() {
-        let stdout = String::from_utf8(output.stdout)?;
-        if let Some(branch) = stdout.trim().strip_prefix("refs/remotes/origin/") {
-            return Ok(branch.to_string());
-        }
-    }
-
-    let output = Command::new("git")
-        .args(["remote", "show", "origin"])
-        .output()
-        .context("Failed to run git remote show")?;
-
-    if output.status.success() {
-        let stdout = String::from_utf8(output.stdout)?;
-        for line in stdout.lines() {
-            if line.trim().starts_with("HEAD branch:") {
-                if let Some(branch) = line.trim().strip_prefix("HEAD branch:") {
-                    return Ok(branch.trim().to_string());
-                }
-            }
-        }
-    }
-
-    anyhow::bail!("Could not determine default branch")
-}
-
-#[cfg(test)]
-fn parse_branch_output(output: &str) -> Result<String> {
-    let branch = output
-        .trim()
-        .strip_prefix("refs/remotes/origin/")
-        .ok_or_else(|| anyhow::anyhow!("Invalid git output"))?
-        .to_string();
-    Ok(branch)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_get_default_branch_parsing() {
-        let valid_output = "refs/remotes/origin/main";
-        let branch = valid_output
-            .trim()
-            .strip_prefix("refs/remotes/origin/")
-            .unwrap()
-            .to_string();
-        assert_eq!(branch, "main");
-    }
-
-    #[test]
-    fn test_get_default_branch_parsing_master() {
-        let valid_output = "refs/remotes/origin/master";
-        let branch = valid_output
-            .trim()
-            .strip_prefix("refs/remotes/origin/")
-            .unwrap()
-            .to_string();
-        assert_eq!(branch, "master");
-    }
-
-    #[test]
-    fn test_get_default_branch_parsing_invalid() {
-        let invalid_output = "invalid output";
-        let result = invalid_output.trim().strip_prefix("refs/remotes/origin/");
-        assert!(result.is_none());
-    }
-
-    #[test]
-    fn test_get_default_branch_parsing_with_whitespace() {
-        let output = "  refs/remotes/origin/main  ";
-        let branch = output
-            .trim()
-            .strip_prefix("refs/remotes/origin/")
-            .unwrap()
-            .to_string();
-        assert_eq!(branch, "main");
-    }
-
-    #[test]
-    fn test_get_default_branch_parsing_different_branch() {
-        let valid_output = "refs/remotes/origin/develop";
-        let branch = valid_output
-            .trim()
-            .strip_prefix("refs/remotes/origin/")
-            .unwrap()
-            .to_string();
-        assert_eq!(branch, "develop");
-    }
-
-    #[test]
-    fn test_parse_branch_output_helper() {
-        let result = parse_branch_output("refs/remotes/origin/main");
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "main");
+    fn test_pull_args() {
+        assert_eq!(pull_args("main", false), vec!["pull", "origin", "main"]);
+        assert_eq!(
+            pull_args("main", true),
+            vec!["pull", "--rebase", "origin", "main"]
+        );
     }
 
     #[test]
-    fn test_parse_branch_output_helper_invalid() {
-        let result = parse_branch_output("invalid");
-        assert!(result.is_err());
-    }
+    fn test_dry_run_steps_stash_before_leaving_branch() {
+        let pull = pull_args("main", false);
+        let (before, after) = dry_run_steps("main", &pull, false, true, Some("feature"));
+        assert_eq!(
+            before,
+            vec!["git stash push -m bashers sync", "git checkout main"]
+        );
+        assert_eq!(
+            after,
+            vec![
+                "git pull origin main",
+                "git checkout feature",
+                "git stash pop"
+            ]
+        );
 
-    #[test]
-    fn test_parse_ls_remote_output() {
-        let output = "ref: refs/heads/main\tHEAD\n1234567890abcdef\tHEAD\n";
-        for line in output.lines() {
-            if line.starts_with("ref:") && line.contains("HEAD") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let ref_path = parts[1];
-                    if let Some(branch) = ref_path.strip_prefix("refs/heads/") {
-                        assert_eq!(branch, "main");
-                        return;
-                    }
-                }
-            }
-        }
-        panic!("Failed to parse ls-remote output");
-    }
+        let (before, after) = dry_run_steps("main", &pull, false, false, None);
+        assert_eq!(before, vec!["git checkout main"]);
+        assert_eq!(after, vec!["git pull origin main"]);
 
-    #[test]
-    fn test_parse_remote_show_output() {
-        let output = "  HEAD branch: main\n  Remote branches:\n    main tracked";
-        for line in output.lines() {
-            if line.trim().starts_with("HEAD branch:") {
-                if let Some(branch) = line.trim().strip_prefix("HEAD branch:") {
-                    assert_eq!(branch.trim(), "main");
-                    return;
-                }
-            }
-        }
-        panic!("Failed to parse remote show output");
-    }
-
-    #[test]
-    fn test_parse_ls_remote_different_format() {
-        let output = "ref: refs/heads/master\tHEAD";
-        for line in output.lines() {
-            if line.starts_with("ref:") && line.contains("HEAD") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let ref_path = parts[1];
-                    if let Some(branch) = ref_path.strip_prefix("refs/heads/") {
-                        assert_eq!(branch, "master");
-                        return;
-                    }
-                }
-            }
-        }
-        panic!("Failed to parse ls-remote output");
+        let pull = pull_args("feature", true);
+        let (before, after) = dry_run_steps("feature", &pull, true, true, None);
+        assert_eq!(before, vec!["git stash push -m bashers sync"]);
+        assert_eq!(
+            after,
+            vec!["git pull --rebase origin feature", "git stash pop"]
+        );
     }
 
     #[test]
-    fn test_parse_ls_remote_with_tabs() {
-        let output = "ref:\trefs/heads/main\tHEAD";
-        for line in output.lines() {
-            if line.starts_with("ref:") && line.contains("HEAD") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let ref_path = parts[1];
-                    if let Some(branch) = ref_path.strip_prefix("refs/heads/") {
-                        assert_eq!(branch, "main");
-                        return;
-                    }
-                }
-            }
-        }
-        panic!("Failed to parse ls-remote output");
+    fn test_parse_tracking_branches() {
+        let output = "main origin/main\nfeature origin/feature\nlocal-only \n\n";
+        assert_eq!(
+            parse_tracking_branches(output),
+            vec![("main", "origin/main"), ("feature", "origin/feature")]
+        );
     }
 
     #[test]
-    fn test_parse_remote_show_with_whitespace() {
-        let output = "    HEAD branch:    main    ";
-        for line in output.lines() {
-            if line.trim().starts_with("HEAD branch:") {
-                if let Some(branch) = line.trim().strip_prefix("HEAD branch:") {
-                    assert_eq!(branch.trim(), "main");
-                    return;
-                }
-            }
-        }
-        panic!("Failed to parse remote show output");
+    fn test_is_conflict_output() {
+        assert!(is_conflict_output(
+            "CONFLICT (content): Merge conflict in src/main.rs"
+        ));
+        assert!(is_conflict_output(
+            "Automatic merge failed; fix conflicts and then commit the result."
+        ));
+        assert!(!is_conflict_output("Already up to date."));
+        assert!(conflict_hint(true).contains("git rebase --abort"));
+        assert!(conflict_hint(false).contains("git merge --abort"));
     }
 
     #[test]
@@ -431,35 +545,6 @@ mod tests {
         assert!(!is_fast_forward_summary_line(""));
     }
 
-    #[test]
-    fn test_branch_parsing_edge_cases() {
-        let cases = vec![
-            ("refs/remotes/origin/main", Some("main")),
-            ("refs/remotes/origin/master", Some("master")),
-            ("refs/remotes/origin/feature-branch", Some("feature-branch")),
-            ("invalid", None),
-            ("refs/remotes/origin/", None),
-        ];
-
-        for (input, expected) in cases {
-            let result = input.trim().strip_prefix("refs/remotes/origin/");
-
-            match expected {
-                Some(branch) => {
-                    assert!(result.is_some());
-                    assert_eq!(result.unwrap(), branch);
-                }
-                None => {
-                    if input == "refs/remotes/origin/" {
-                        assert_eq!(result, Some(""));
-                    } else {
-                        assert!(result.is_none());
-                    }
-                }
-            }
-        }
-    }
-
     #[test]
     fn test_print_pull_output_empty_no_panic() {
         let mut colors = Colors::new();
@@ -1,9 +1,143 @@
-use crate::cli::BashersApp;
+use crate::cli::{BashersApp, TOPLEVEL_ALIAS_PARENTS};
+use crate::utils::colors::Colors;
 use anyhow::Result;
-use clap::CommandFactory;
+use clap::{Command, CommandFactory};
+use std::fmt;
+
+/// Top-level commands by heading, in display order. Anything unlisted ends up under "Other".
+const GROUPS: &[(&str, &[&str])] = &[
+    ("Packages", &["update", "show", "setup"]),
+    ("Git", &["git"]),
+    ("Kube", &["kube"]),
+    ("Containers", &["docker"]),
+    ("Logs", &["tail"]),
+    (
+        "Utility",
+        &["watch", "config", "completions", "version", "self"],
+    ),
+];
+
+/// One line of the overview.
+enum HelpLine {
+    Heading(String),
+    Command {
+        name: String,
+        about: String,
+        /// The subcommand name when it can also be called without its parent.
+        alias: Option<String>,
+    },
+    Blank,
+}
+
+impl fmt::Display for HelpLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HelpLine::Heading(title) => write!(f, "{}", title),
+            HelpLine::Command { name, about, alias } => {
+                write!(f, "  {:<16}{}", name, about)?;
+                if let Some(alias) = alias {
+                    write!(f, " (also: bashers {})", alias)?;
+                }
+                Ok(())
+            }
+            HelpLine::Blank => Ok(()),
+        }
+    }
+}
+
+fn about(cmd: &Command) -> String {
+    cmd.get_about().map(|s| s.to_string()).unwrap_or_default()
+}
+
+/// `cmd` itself, or one line per subcommand when it's a parent like `kube`.
+fn command_lines(cmd: &Command) -> Vec<HelpLine> {
+    let parent = cmd.get_name();
+    let subcommands: Vec<&Command> = cmd.get_subcommands().filter(|c| !c.is_hide_set()).collect();
+    if subcommands.is_empty() {
+        return vec![HelpLine::Command {
+            name: parent.to_string(),
+            about: about(cmd),
+            alias: None,
+        }];
+    }
+    subcommands
+        .into_iter()
+        .map(|sub| HelpLine::Command {
+            name: format!("{} {}", parent, sub.get_name()),
+            about: about(sub),
+            alias: TOPLEVEL_ALIAS_PARENTS
+                .contains(&parent)
+                .then(|| sub.get_name().to_string()),
+        })
+        .collect()
+}
+
+fn help_lines(root: &Command) -> Vec<HelpLine> {
+    let commands: Vec<&Command> = root
+        .get_subcommands()
+        .filter(|c| !c.is_hide_set())
+        .collect();
+    let other: Vec<&str> = commands
+        .iter()
+        .map(|c| c.get_name())
+        .filter(|name| !GROUPS.iter().any(|(_, names)| names.contains(name)))
+        .collect();
+
+    let mut lines = vec![
+        HelpLine::Heading(format!("{} - {}", root.get_name(), about(root))),
+        HelpLine::Blank,
+    ];
+    let groups = GROUPS
+        .iter()
+        .copied()
+        .chain(Some(("Other", other.as_slice())));
+    for (title, names) in groups {
+        let group: Vec<HelpLine> = names
+            .iter()
+            .filter_map(|name| commands.iter().find(|c| c.get_name() == *name))
+            .flat_map(|cmd| command_lines(cmd))
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        lines.push(HelpLine::Heading(format!("{}:", title)));
+        lines.extend(group);
+        lines.push(HelpLine::Blank);
+    }
+    lines.push(HelpLine::Command {
+        name: "--help".to_string(),
+        about: "Run `bashers <command> --help` for a command's options".to_string(),
+        alias: None,
+    });
+    lines
+}
 
 pub fn run() -> Result<()> {
-    BashersApp::command().print_help()?;
+    let mut colors = Colors::new();
+    for line in help_lines(&BashersApp::command()) {
+        match &line {
+            HelpLine::Heading(_) => {
+                colors.bold()?;
+                colors.println(&line.to_string())?;
+                colors.reset()?;
+            }
+            HelpLine::Command { name, about, alias } => {
+                colors.print("  ")?;
+                colors.green()?;
+                colors.print(&format!("{:<16}", name))?;
+                colors.reset()?;
+                colors.print(about)?;
+                if let Some(alias) = alias {
+                    colors.cyan()?;
+                    colors.print(&format!(" (also: bashers {})", alias))?;
+                    colors.reset()?;
+                }
+                colors.println("")?;
+            }
+            HelpLine::Blank => colors.println("")?,
+        }
+    }
+    colors.flush()?;
     Ok(())
 }
 
@@ -11,10 +145,57 @@ pub fn run() -> Result<()> {
 mod tests {
     use super::*;
 
+    fn rendered() -> String {
+        help_lines(&BashersApp::command())
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
     #[test]
     fn test_help_run() {
         // Test that help command runs without error
         let result = run();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_help_lists_every_subcommand() {
+        let text = rendered();
+        let root = BashersApp::command();
+        for cmd in root.get_subcommands() {
+            let subs: Vec<&Command> = cmd.get_subcommands().collect();
+            if subs.is_empty() {
+                assert!(
+                    text.contains(&format!("  {} ", cmd.get_name())),
+                    "missing {}",
+                    cmd.get_name()
+                );
+            }
+            for sub in subs {
+                let name = format!("{} {}", cmd.get_name(), sub.get_name());
+                assert!(text.contains(&name), "missing {:?} in:\n{}", name, text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_help_groups_and_aliases() {
+        let text = rendered();
+        for heading in [
+            "Packages:",
+            "Git:",
+            "Kube:",
+            "Containers:",
+            "Logs:",
+            "Utility:",
+        ] {
+            assert!(text.contains(heading), "missing {}", heading);
+        }
+        assert!(!text.contains("Other:"));
+        assert!(text.contains("(also: bashers track)"));
+        assert!(!text.contains("(also: bashers update)"));
+        assert!(text.find("Packages:") < text.find("  update"));
+        assert!(text.find("  update") < text.find("Git:"));
+    }
 }
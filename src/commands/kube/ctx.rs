@@ -0,0 +1,95 @@
+use crate::utils::output::{self, OutputFormat};
+use crate::utils::packages;
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct KubeContext {
+    pub name: String,
+    pub current: bool,
+}
+
+pub fn run(name: Option<&str>, output: OutputFormat) -> Result<()> {
+    which::which("kubectl").context("kubectl not found on PATH; install it to manage contexts")?;
+
+    if let Some(name) = name {
+        return use_context(name);
+    }
+
+    let contexts = list_contexts()?;
+    if output.is_json() {
+        return output::emit(&contexts);
+    }
+    if contexts.is_empty() {
+        anyhow::bail!("No kube contexts found; check your kubeconfig");
+    }
+    if atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout) {
+        let current = contexts.iter().position(|c| c.current).unwrap_or(0);
+        let names: Vec<String> = contexts.into_iter().map(|c| c.name).collect();
+        let selected = packages::prompt_select("Switch to context:", names, current)?;
+        return use_context(&selected);
+    }
+
+    super::print_marked(contexts.iter().map(|c| (c.name.as_str(), c.current)));
+    Ok(())
+}
+
+fn list_contexts() -> Result<Vec<KubeContext>> {
+    let stdout = super::kubectl_stdout(&["config", "get-contexts", "--no-headers"])?;
+    Ok(parse_contexts(&stdout))
+}
+
+/// Parses `kubectl config get-contexts --no-headers`, where the current context's row starts
+/// with `*` and the name is the first remaining column.
+fn parse_contexts(stdout: &str) -> Vec<KubeContext> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (current, rest) = match line.strip_prefix('*') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let name = rest.split_whitespace().next()?;
+            Some(KubeContext {
+                name: name.to_string(),
+                current,
+            })
+        })
+        .collect()
+}
+
+fn use_context(name: &str) -> Result<()> {
+    super::kubectl_stdout(&["config", "use-context", name])?;
+    println!("Switched to context {name}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_contexts() {
+        let stdout = "          dev        kind-dev   kind-dev\n\
+                      *         prod       gke-prod   admin      payments\n\
+                      \n          staging    gke-stg    admin\n";
+        assert_eq!(
+            parse_contexts(stdout),
+            vec![
+                KubeContext {
+                    name: "dev".to_string(),
+                    current: false,
+                },
+                KubeContext {
+                    name: "prod".to_string(),
+                    current: true,
+                },
+                KubeContext {
+                    name: "staging".to_string(),
+                    current: false,
+                },
+            ]
+        );
+    }
+}
@@ -1,10 +1,96 @@
+use crate::commands::kube::track::pod_pattern_regex;
 use crate::utils::colors;
+use crate::utils::log::CommandExt;
 use crate::utils::multi_progress;
+use crate::utils::output;
 use anyhow::{Context, Result};
 use regex::Regex;
+use serde::Serialize;
 use std::collections::BTreeMap;
 use std::process::{Command, Stdio};
 
+/// How `kube kmg` prints matched pods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum KmgFormat {
+    /// Live spinners per pattern, with each pod's first image
+    #[default]
+    Table,
+    /// One row per pod with its namespace, pattern and every image
+    Wide,
+    /// The wide rows as a JSON array
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct PodImages {
+    pub namespace: String,
+    pub pod: String,
+    pub pattern: String,
+    pub images: Vec<String>,
+}
+
+/// `(namespace, pod, pattern index)` for each `NAMESPACE NAME` row whose pod name matches
+/// one of `regexes`; the first matching pattern wins.
+fn match_pod_rows(stdout: &str, regexes: &[Regex]) -> Vec<(String, String, usize)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let namespace = parts.next()?;
+            let pod_name = parts.next()?;
+            let pattern_idx = regexes.iter().position(|re| re.is_match(pod_name))?;
+            Some((namespace.to_string(), pod_name.to_string(), pattern_idx))
+        })
+        .collect()
+}
+
+/// The `Image:` values from `kubectl describe pod` output, init containers included.
+fn parse_images(describe: &str) -> Vec<String> {
+    describe
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Image:"))
+        .map(|image| image.trim().to_string())
+        .filter(|image| !image.is_empty())
+        .collect()
+}
+
+fn describe_images(namespace: &str, pod_name: &str) -> Vec<String> {
+    let describe_output = Command::new("kubectl")
+        .args(["describe", "pod", pod_name, "-n", namespace])
+        .stdout(Stdio::piped())
+        .logged_output();
+    match describe_output {
+        Ok(ref out) if out.status.success() => parse_images(&String::from_utf8_lossy(&out.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+fn format_wide(pods: &[PodImages]) -> Vec<String> {
+    let width = |column: &dyn Fn(&PodImages) -> &str, header: &str| {
+        pods.iter()
+            .map(|p| column(p).chars().count())
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let ns_width = width(&|p| &p.namespace, "NAMESPACE");
+    let pod_width = width(&|p| &p.pod, "POD");
+    let pattern_width = width(&|p| &p.pattern, "PATTERN");
+    let row = |ns: &str, pod: &str, pattern: &str, images: &str| {
+        format!("{ns:<ns_width$}  {pod:<pod_width$}  {pattern:<pattern_width$}  {images}")
+    };
+    std::iter::once(row("NAMESPACE", "POD", "PATTERN", "IMAGES"))
+        .chain(pods.iter().map(|p| {
+            let images = if p.images.is_empty() {
+                "(no image)".to_string()
+            } else {
+                p.images.join(",")
+            };
+            row(&p.namespace, &p.pod, &p.pattern, &images)
+        }))
+        .collect()
+}
+
 fn format_pod_prefix(pod_name: &str, use_color: bool) -> String {
     if use_color {
         format!(
@@ -17,8 +103,8 @@ fn format_pod_prefix(pod_name: &str, use_color: bool) -> String {
     }
 }
 
-pub fn run(patterns: &[String]) -> Result<()> {
-    let use_color = atty::is(atty::Stream::Stderr);
+pub fn run(patterns: &[String], format: KmgFormat) -> Result<()> {
+    let use_color = colors::use_color(atty::Stream::Stderr);
     let multi = multi_progress::multi_progress_stderr();
     let patterns_display = patterns.join(" ");
 
@@ -53,7 +139,7 @@ pub fn run(patterns: &[String]) -> Result<()> {
                     "custom-columns=NAMESPACE:.metadata.namespace,NAME:.metadata.name",
                     "--no-headers",
                 ])
-                .output()
+                .logged_output()
                 .context("Failed to run kubectl get pods")?;
             if !output.status.success() {
                 anyhow::bail!("kubectl get pods failed");
@@ -62,27 +148,37 @@ pub fn run(patterns: &[String]) -> Result<()> {
         })?;
 
     let stdout = String::from_utf8(pods_output.stdout)?;
-    let regexes: Vec<Regex> = patterns
+    let regexes = patterns
         .iter()
         .map(|p| pod_pattern_regex(p.as_str()))
-        .collect();
-    let pods_with_pattern: Vec<(String, String, usize)> = stdout
-        .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                return None;
-            }
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() < 2 {
-                return None;
-            }
-            let namespace = parts[0];
-            let pod_name = parts[1];
-            let pattern_idx = regexes.iter().position(|re| re.is_match(pod_name))?;
-            Some((namespace.to_string(), pod_name.to_string(), pattern_idx))
-        })
-        .collect();
+        .collect::<Result<Vec<Regex>>>()?;
+    let pods_with_pattern = match_pod_rows(&stdout, &regexes);
+
+    if format != KmgFormat::Table {
+        let pods: Vec<PodImages> = std::thread::scope(|scope| {
+            let handles: Vec<_> = pods_with_pattern
+                .iter()
+                .map(|(ns, name, _)| scope.spawn(move || describe_images(ns, name)))
+                .collect();
+            pods_with_pattern
+                .iter()
+                .zip(handles)
+                .map(|((ns, name, idx), handle)| PodImages {
+                    namespace: ns.clone(),
+                    pod: name.clone(),
+                    pattern: patterns[*idx].clone(),
+                    images: handle.join().unwrap_or_default(),
+                })
+                .collect()
+        });
+        if format == KmgFormat::Json {
+            return output::emit(&pods);
+        }
+        for line in format_wide(&pods) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
 
     let by_pattern: BTreeMap<usize, Vec<(String, String)>> =
         pods_with_pattern
@@ -109,22 +205,10 @@ pub fn run(patterns: &[String]) -> Result<()> {
             )
         },
         |(namespace, pod_name): (String, String)| {
-            let describe_output = Command::new("kubectl")
-                .args(["describe", "pod", &pod_name, "-n", &namespace])
-                .stdout(Stdio::piped())
-                .output();
-
-            match describe_output {
-                Ok(ref out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
-                    .lines()
-                    .find_map(|line| {
-                        line.trim()
-                            .strip_prefix("Image:")
-                            .map(|s| s.trim().to_string())
-                    })
-                    .unwrap_or_default(),
-                _ => String::new(),
-            }
+            describe_images(&namespace, &pod_name)
+                .into_iter()
+                .next()
+                .unwrap_or_default()
         },
         |image: &String| {
             if image.is_empty() {
@@ -138,34 +222,108 @@ pub fn run(patterns: &[String]) -> Result<()> {
     Ok(())
 }
 
-fn pod_pattern_regex(pattern: &str) -> Regex {
-    Regex::new(pattern).unwrap_or_else(|_| {
-        let escaped = regex::escape(pattern);
-        Regex::new(&format!("(?i){}", escaped)).expect("escaped pattern must be valid")
-    })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_pod_pattern_regex_valid() {
-        let re = pod_pattern_regex("my-pod");
+        let re = pod_pattern_regex("my-pod").unwrap();
         assert!(re.is_match("my-pod"));
         assert!(!re.is_match("other"));
     }
 
     #[test]
     fn test_pod_pattern_regex_invalid_falls_back_case_insensitive() {
-        let re = pod_pattern_regex("[invalid");
+        let re = pod_pattern_regex("[invalid").unwrap();
         assert!(re.is_match("[invalid"));
         assert!(re.is_match("[INVALID"));
     }
 
     #[test]
     fn test_pod_pattern_regex_literal_bracket_escaped_on_fallback() {
-        let re = pod_pattern_regex("[");
+        let re = pod_pattern_regex("[").unwrap();
         assert!(re.is_match("["));
     }
+
+    #[test]
+    fn test_match_pod_rows_first_pattern_wins() {
+        let regexes = vec![
+            pod_pattern_regex("api").unwrap(),
+            pod_pattern_regex("web|api").unwrap(),
+        ];
+        let stdout = "default   api-0\n\nprod  web-1\nkube-system coredns-1\nmalformed\n";
+        assert_eq!(
+            match_pod_rows(stdout, &regexes),
+            vec![
+                ("default".to_string(), "api-0".to_string(), 0),
+                ("prod".to_string(), "web-1".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_images_collects_every_container() {
+        let describe = "Name:         api-0
+Init Containers:
+  migrate:
+    Image:          registry/migrate:1.2
+    Image ID:       registry/migrate@sha256:abc
+Containers:
+  app:
+    Image:          registry/api:3.4
+  sidecar:
+    Image:          envoy:1.30
+";
+        assert_eq!(
+            parse_images(describe),
+            vec!["registry/migrate:1.2", "registry/api:3.4", "envoy:1.30"]
+        );
+        assert!(parse_images("Name: api-0\n").is_empty());
+    }
+
+    #[test]
+    fn test_format_wide_aligns_columns() {
+        let pods = vec![
+            PodImages {
+                namespace: "default".to_string(),
+                pod: "api-0".to_string(),
+                pattern: "api".to_string(),
+                images: vec!["api:1".to_string(), "envoy:1".to_string()],
+            },
+            PodImages {
+                namespace: "kube-system".to_string(),
+                pod: "web".to_string(),
+                pattern: "web".to_string(),
+                images: vec![],
+            },
+        ];
+        assert_eq!(
+            format_wide(&pods),
+            vec![
+                "NAMESPACE    POD    PATTERN  IMAGES",
+                "default      api-0  api      api:1,envoy:1",
+                "kube-system  web    web      (no image)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pod_images_json_shape() {
+        let pod = PodImages {
+            namespace: "default".to_string(),
+            pod: "api-0".to_string(),
+            pattern: "api".to_string(),
+            images: vec!["api:1".to_string()],
+        };
+        assert_eq!(
+            serde_json::to_value(&pod).unwrap(),
+            serde_json::json!({
+                "namespace": "default",
+                "pod": "api-0",
+                "pattern": "api",
+                "images": ["api:1"],
+            })
+        );
+    }
 }
@@ -1,2 +1,40 @@
+use crate::utils::colors;
+use crate::utils::log::CommandExt;
+use anyhow::{Context, Result};
+use std::process::Command;
+
+pub mod ctx;
 pub mod kmg;
+pub mod ns;
+pub mod pods;
 pub mod track;
+
+/// Runs `kubectl <args>` and returns its stdout, failing with kubectl's stderr.
+fn kubectl_stdout(args: &[&str]) -> Result<String> {
+    let display = args.join(" ");
+    let output = Command::new("kubectl")
+        .args(args)
+        .logged_output()
+        .with_context(|| format!("Failed to run kubectl {display}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "kubectl {display} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Prints one name per line, marking the current one with `*` (green when colored).
+fn print_marked<'a>(entries: impl Iterator<Item = (&'a str, bool)>) {
+    let use_color = colors::use_color(atty::Stream::Stdout);
+    for (name, current) in entries {
+        if !current {
+            println!("  {name}");
+        } else if use_color {
+            println!("{}* {name}{}", colors::ANSI_GREEN, colors::ANSI_RESET);
+        } else {
+            println!("* {name}");
+        }
+    }
+}
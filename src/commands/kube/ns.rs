@@ -0,0 +1,115 @@
+use crate::utils::output::{self, OutputFormat};
+use crate::utils::packages;
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct KubeNamespace {
+    pub name: String,
+    pub current: bool,
+}
+
+pub fn run(name: Option<&str>, output: OutputFormat) -> Result<()> {
+    which::which("kubectl")
+        .context("kubectl not found on PATH; install it to manage namespaces")?;
+
+    if let Some(name) = name {
+        return use_namespace(name);
+    }
+
+    let current = current_namespace()?;
+    let namespaces = list_namespaces(&current)?;
+    if output.is_json() {
+        return output::emit(&namespaces);
+    }
+    if namespaces.is_empty() {
+        println!("{current}");
+        return Ok(());
+    }
+    if atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout) {
+        let starting = namespaces.iter().position(|n| n.current).unwrap_or(0);
+        let names: Vec<String> = namespaces.into_iter().map(|n| n.name).collect();
+        let selected = packages::prompt_select("Switch to namespace:", names, starting)?;
+        return use_namespace(&selected);
+    }
+
+    super::print_marked(namespaces.iter().map(|n| (n.name.as_str(), n.current)));
+    Ok(())
+}
+
+/// The current context's namespace; kubectl leaves it empty when it is `default`.
+fn current_namespace() -> Result<String> {
+    let stdout =
+        super::kubectl_stdout(&["config", "view", "--minify", "-o", "jsonpath={..namespace}"])?;
+    Ok(namespace_or_default(&stdout))
+}
+
+fn namespace_or_default(stdout: &str) -> String {
+    match stdout.trim() {
+        "" => "default".to_string(),
+        namespace => namespace.to_string(),
+    }
+}
+
+fn list_namespaces(current: &str) -> Result<Vec<KubeNamespace>> {
+    let stdout = super::kubectl_stdout(&[
+        "get",
+        "namespaces",
+        "-o",
+        "custom-columns=NAME:.metadata.name",
+        "--no-headers",
+        "--request-timeout=10s",
+    ])?;
+    Ok(parse_namespaces(&stdout, current))
+}
+
+fn parse_namespaces(stdout: &str, current: &str) -> Vec<KubeNamespace> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|name| KubeNamespace {
+            name: name.to_string(),
+            current: name == current,
+        })
+        .collect()
+}
+
+fn use_namespace(name: &str) -> Result<()> {
+    super::kubectl_stdout(&[
+        "config",
+        "set-context",
+        "--current",
+        &format!("--namespace={name}"),
+    ])?;
+    println!("Default namespace set to {name}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_or_default() {
+        assert_eq!(namespace_or_default(""), "default");
+        assert_eq!(namespace_or_default("payments\n"), "payments");
+    }
+
+    #[test]
+    fn test_parse_namespaces_marks_current() {
+        let namespaces = parse_namespaces("default\nkube-system\n\npayments\n", "payments");
+        let marked: Vec<(&str, bool)> = namespaces
+            .iter()
+            .map(|n| (n.name.as_str(), n.current))
+            .collect();
+        assert_eq!(
+            marked,
+            vec![
+                ("default", false),
+                ("kube-system", false),
+                ("payments", true)
+            ]
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use crate::commands::kube::track::{
+    find_pods_with_retry, matched_pods, pod_pattern_regex, warn_unmatched_patterns, KubeTarget,
+    PodInfo,
+};
+use crate::utils::output::{self, OutputFormat};
+use anyhow::Result;
+use regex::Regex;
+
+/// Prints the pods matching `patterns` as `namespace name` lines (or JSON), for scripts.
+/// `kube track --output json` lists its pods through here too.
+pub fn run(patterns: &[String], target: &KubeTarget, output: OutputFormat) -> Result<()> {
+    let regexes = patterns
+        .iter()
+        .map(|p| pod_pattern_regex(p))
+        .collect::<Result<Vec<Regex>>>()?;
+    let pods = find_pods_with_retry(&regexes, target, &mut None)?;
+    warn_unmatched_patterns(patterns, &pods);
+    if output.is_json() {
+        return output::emit(&matched_pods(&pods, patterns));
+    }
+    for line in format_pods(&pods) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn format_pods(pods: &[PodInfo]) -> Vec<String> {
+    pods.iter()
+        .map(|pod| format!("{} {}", pod.namespace, pod.name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::kube::track::match_pods;
+
+    fn pod(namespace: &str, name: &str) -> PodInfo {
+        PodInfo {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            pattern_idx: 0,
+            details: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_format_pods_is_namespace_then_name() {
+        let regexes = vec![pod_pattern_regex("api").unwrap()];
+        let pods = match_pods(
+            vec![
+                pod("default", "api-0"),
+                pod("prod", "web-0"),
+                pod("prod", "api-1"),
+            ],
+            &regexes,
+        );
+        assert_eq!(format_pods(&pods), vec!["default api-0", "prod api-1"]);
+    }
+}
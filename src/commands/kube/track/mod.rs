@@ -1,21 +1,92 @@
 mod simple;
-mod tui;
+pub(crate) mod tui;
+
+pub use tui::theme::{Theme, ThemeName};
+#[cfg(feature = "bench")]
+pub use tui::bench::time_full_buffer_frames;
 
 use anyhow::{Context, Result};
+use memchr::memmem::Finder;
 use regex::Regex;
-use std::io::Read;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::mpsc;
+use std::sync::{mpsc, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
-use crate::utils::spinner;
+use crate::utils::log::CommandExt;
+use crate::utils::{colors, pattern, spinner};
 
 #[derive(Clone)]
 pub struct PodInfo {
     pub namespace: String,
     pub name: String,
     pub pattern_idx: usize,
+    pub details: PodDetails,
+}
+
+/// Optional pod fields that can be requested from kubectl in addition to namespace and name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum PodColumn {
+    Status,
+    Restarts,
+    Node,
+}
+
+impl PodColumn {
+    fn spec(self) -> &'static str {
+        match self {
+            PodColumn::Status => "STATUS:.status.phase",
+            PodColumn::Restarts => "RESTARTS:.status.containerStatuses[*].restartCount",
+            PodColumn::Node => "NODE:.spec.nodeName",
+        }
+    }
+}
+
+/// Values of the extra [`PodColumn`]s; fields that were not requested (or that kubectl
+/// reported as `<none>`) stay unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PodDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restarts: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
+
+/// The `custom-columns` output spec for kubectl: namespace and name first, then `columns`.
+fn custom_columns(columns: &[PodColumn]) -> String {
+    let mut specs = vec!["NAMESPACE:.metadata.namespace", "NAME:.metadata.name"];
+    specs.extend(columns.iter().map(|c| c.spec()));
+    format!("custom-columns={}", specs.join(","))
+}
+
+/// Parses one `--no-headers` row produced by [`custom_columns`] with the same `columns`.
+fn parse_pod_row(line: &str, columns: &[PodColumn]) -> Option<(String, String, PodDetails)> {
+    let mut parts = line.split_whitespace();
+    let namespace = parts.next()?.to_string();
+    let name = parts.next()?.to_string();
+    let mut details = PodDetails::default();
+    for (column, value) in columns.iter().zip(parts) {
+        if value == "<none>" {
+            continue;
+        }
+        match column {
+            PodColumn::Status => details.status = Some(value.to_string()),
+            PodColumn::Restarts => {
+                details.restarts = value
+                    .split(',')
+                    .map(|n| n.parse::<u32>().ok())
+                    .sum::<Option<u32>>()
+            }
+            PodColumn::Node => details.node = Some(value.to_string()),
+        }
+    }
+    Some((namespace, name, details))
 }
 
 impl PodInfo {
@@ -24,200 +95,981 @@ impl PodInfo {
     }
 }
 
-pub fn run(patterns: &[String], err_only: bool, simple: bool) -> Result<()> {
-    let regexes: Vec<Regex> = patterns.iter().map(|p| pod_pattern_regex(p)).collect();
+/// The kubeconfig context and namespace that kubectl calls target. Unset fields fall back to
+/// kubectl's defaults: the current context, and all namespaces when listing pods.
+/// `columns` are extra pod fields to fetch when listing; by default only namespace and name.
+/// `node` keeps only the pods scheduled on that node. `timeout` replaces the default kubectl
+/// timeouts, and `retry` retries the first lookup once when it looks like a login is pending.
+/// `since_restart` limits followed logs to the current life of the pod's containers.
+/// `kubectl` is the binary found by [`KubeTarget::with_kubectl`]; plain `kubectl` until then.
+#[derive(Clone, Debug, Default)]
+pub struct KubeTarget {
+    pub context: Option<String>,
+    pub namespace: Option<String>,
+    pub columns: Vec<PodColumn>,
+    pub node: Option<String>,
+    pub timeout: Option<Duration>,
+    pub retry: bool,
+    pub since_restart: bool,
+    pub kubectl: Option<PathBuf>,
+}
+
+impl KubeTarget {
+    /// `self` with kubectl resolved on PATH once, so pod lookups and polling reuse the path.
+    pub fn with_kubectl(mut self) -> Result<Self> {
+        let path = which::which("kubectl")
+            .context("kubectl not found on PATH; install it to track pods")?;
+        self.kubectl = Some(path);
+        Ok(self)
+    }
+
+    pub fn kubectl(&self) -> Command {
+        let mut cmd = Command::new(self.kubectl.as_deref().unwrap_or(Path::new("kubectl")));
+        if let Some(context) = &self.context {
+            cmd.args(["--context", context]);
+        }
+        cmd
+    }
+
+    /// `kubectl logs -f` for a pod. With `since_restart`, the containers' latest start time is
+    /// looked up on every call, so a reconnect after a restart starts from the new life.
+    pub fn logs_command(&self, name: &str, namespace: &str) -> Command {
+        let started_at = self
+            .since_restart
+            .then(|| self.container_started_at(name, namespace))
+            .flatten();
+        let mut cmd = self.kubectl();
+        cmd.args(logs_args(name, namespace, started_at.as_deref()));
+        cmd
+    }
+
+    fn container_started_at(&self, name: &str, namespace: &str) -> Option<String> {
+        let output = self
+            .kubectl()
+            .args(self.container_starts_args(name, namespace))
+            .logged_output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        latest_start_time(&String::from_utf8_lossy(&output.stdout)).map(str::to_string)
+    }
+
+    fn container_starts_args(&self, name: &str, namespace: &str) -> Vec<String> {
+        vec![
+            "get".to_string(),
+            "pod".to_string(),
+            name.to_string(),
+            "-n".to_string(),
+            namespace.to_string(),
+            "-o".to_string(),
+            CONTAINER_STARTS_JSONPATH.to_string(),
+            format!(
+                "--request-timeout={}s",
+                kubectl_timeouts(self.timeout).request.as_secs()
+            ),
+        ]
+    }
+
+    /// `self` with `column` also fetched, unless it already is.
+    pub fn with_column(mut self, column: PodColumn) -> Self {
+        if !self.columns.contains(&column) {
+            self.columns.push(column);
+        }
+        self
+    }
+
+    /// The requested columns, plus the node column when filtering by node.
+    fn query_columns(&self) -> Vec<PodColumn> {
+        let mut columns = self.columns.clone();
+        if self.node.is_some() && !columns.contains(&PodColumn::Node) {
+            columns.push(PodColumn::Node);
+        }
+        columns
+    }
+
+    fn on_target_node(&self, details: &PodDetails) -> bool {
+        match &self.node {
+            Some(node) => details.node.as_deref() == Some(node.as_str()),
+            None => true,
+        }
+    }
+
+    fn get_pods_args(&self) -> Vec<String> {
+        let mut args = vec!["get".to_string(), "pods".to_string()];
+        match &self.namespace {
+            Some(namespace) => args.extend(["-n".to_string(), namespace.clone()]),
+            None => args.push("-A".to_string()),
+        }
+        args.extend([
+            "-o".to_string(),
+            custom_columns(&self.query_columns()),
+            "--no-headers".to_string(),
+            format!(
+                "--request-timeout={}s",
+                kubectl_timeouts(self.timeout).request.as_secs()
+            ),
+        ]);
+        args
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct InitialView {
+    pub tab: Option<usize>,
+    pub select: Option<String>,
+}
+
+/// How pane and tab navigation behaves at the ends of the list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum NavMode {
+    /// Moving past the last item goes back to the first (and vice versa)
+    Wrap,
+    /// Moving past the last item stays on it
+    Clamp,
+}
+
+impl NavMode {
+    pub fn step(self, current: usize, len: usize, forward: bool) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        match (self, forward) {
+            (NavMode::Wrap, true) => (current + 1) % len,
+            (NavMode::Wrap, false) => (current + len - 1) % len,
+            (NavMode::Clamp, true) => (current + 1).min(len - 1),
+            (NavMode::Clamp, false) => current.saturating_sub(1),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TuiOptions {
+    pub initial_view: InitialView,
+    /// `None` keeps the original keys: tabs clamp, panes wrap.
+    pub nav_mode: Option<NavMode>,
+    pub follow_errors: bool,
+    pub raw: bool,
+    pub min_pane_height: Option<u16>,
+    pub single_tab: bool,
+    /// How long a pod listing is reused before `kubectl get pods` runs again (default 3s).
+    pub pod_cache_ttl: Option<Duration>,
+    /// How often to look for new pods matching the patterns (default 5s).
+    pub poll_interval: Option<Duration>,
+    pub theme: Theme,
+}
+
+pub fn run(
+    patterns: &[String],
+    filter: LogFilter,
+    simple: bool,
+    json_logs: bool,
+    target: KubeTarget,
+    tui_options: TuiOptions,
+) -> Result<()> {
+    let regexes = patterns
+        .iter()
+        .map(|p| pod_pattern_regex(p))
+        .collect::<Result<Vec<Regex>>>()?;
+    // The TUI shows each pod's node in its title, so fetch it with the first listing too.
+    let target = if simple {
+        target
+    } else {
+        target.with_column(PodColumn::Node)
+    };
 
     let mut sp = spinner::create_spinner("Finding pods...");
 
-    let pods = match find_matching_pods(&regexes) {
-        Ok(p) => p,
+    if simple {
+        let pods = match find_pods_with_retry(&regexes, &target, &mut sp) {
+            Ok(p) => p,
+            Err(e) => {
+                spinner::stop_spinner(sp.as_mut());
+                return Err(e);
+            }
+        };
+        spinner::finish_with_message(sp.as_mut(), "Found pods");
+        if warn_unmatched_patterns(patterns, &pods) == patterns.len() {
+            return Ok(());
+        }
+        return simple::run(pods, regexes, filter, json_logs, target);
+    }
+
+    // The TUI opens on the first matching pod; the rest of the listing keeps streaming in.
+    let (first, pending) = match first_matching_pod(&regexes, &target, &mut sp) {
+        Ok(found) => found,
         Err(e) => {
             spinner::stop_spinner(sp.as_mut());
             return Err(e);
         }
     };
+    let Some(first) = first else {
+        spinner::finish_with_message(sp.as_mut(), "Found pods");
+        warn_unmatched_patterns(patterns, &[]);
+        return Ok(());
+    };
+
+    let view = &tui_options.initial_view;
+    if view.tab.is_some() || view.select.is_some() {
+        // The initial tab and selection refer to the whole listing, so wait for it.
+        let pods = match collect_pending(first, pending) {
+            Ok(p) => p,
+            Err(e) => {
+                spinner::stop_spinner(sp.as_mut());
+                return Err(e);
+            }
+        };
+        spinner::finish_with_message(sp.as_mut(), "Found pods");
+        if warn_unmatched_patterns(patterns, &pods) > 0 {
+            thread::sleep(Duration::from_secs(2));
+        }
+        return tui::run(pods, regexes, filter, json_logs, target, tui_options, None);
+    }
 
     spinner::finish_with_message(sp.as_mut(), "Found pods");
+    let pending = PendingPods {
+        rx: pending,
+        patterns: patterns.to_vec(),
+    };
+    tui::run(
+        vec![first],
+        regexes,
+        filter,
+        json_logs,
+        target,
+        tui_options,
+        Some(pending),
+    )
+}
 
-    let use_color = atty::is(atty::Stream::Stdout);
+/// A pod listing running in the background, one message per matching pod.
+pub(crate) enum Discovered {
+    Pod(PodInfo),
+    Done(Result<()>),
+}
 
-    let mut any_match = false;
-    let mut has_warnings = false;
-    for (i, pattern) in patterns.iter().enumerate() {
-        let has_match = pods.iter().any(|p| p.pattern_idx == i);
-        if has_match {
-            any_match = true;
-        } else {
-            print_no_match_warning(pattern, use_color);
-            has_warnings = true;
+/// The rest of the startup listing, still arriving after the TUI has opened.
+pub(crate) struct PendingPods {
+    pub rx: mpsc::Receiver<Discovered>,
+    /// The command-line patterns, to report the ones nothing matched once the listing ends.
+    pub patterns: Vec<String>,
+}
+
+fn stream_matching_in_background(
+    regexes: Vec<Regex>,
+    target: KubeTarget,
+) -> mpsc::Receiver<Discovered> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = stream_pods(&target, &mut |pod| {
+            if let Some(pod) = match_pod(pod, &regexes) {
+                let _ = tx.send(Discovered::Pod(pod));
+            }
+        });
+        let _ = tx.send(Discovered::Done(result));
+    });
+    rx
+}
+
+/// Streams the listing until the first pod matching `regexes` arrives, retrying once like
+/// [`find_pods_with_retry`]. Returns that pod (`None` when nothing matched) and the rest.
+fn first_matching_pod(
+    regexes: &[Regex],
+    target: &KubeTarget,
+    sp: &mut Option<spinoff::Spinner>,
+) -> Result<(Option<PodInfo>, mpsc::Receiver<Discovered>)> {
+    let mut retried = false;
+    loop {
+        let rx = stream_matching_in_background(regexes.to_vec(), target.clone());
+        match rx.recv() {
+            Ok(Discovered::Pod(pod)) => return Ok((Some(pod), rx)),
+            Ok(Discovered::Done(Ok(()))) | Err(_) => return Ok((None, rx)),
+            Ok(Discovered::Done(Err(e))) => {
+                let pending = (target.retry && !retried)
+                    .then(|| e.downcast_ref::<AuthPending>().map(ToString::to_string))
+                    .flatten();
+                let Some(pending) = pending else {
+                    return Err(e);
+                };
+                retried = true;
+                wait_before_retry(&pending, sp);
+            }
         }
     }
+}
 
-    if !any_match {
-        return Ok(());
+/// `first` plus every pod still to come from `rest`.
+fn collect_pending(first: PodInfo, rest: mpsc::Receiver<Discovered>) -> Result<Vec<PodInfo>> {
+    let mut pods = vec![first];
+    for message in rest {
+        match message {
+            Discovered::Pod(pod) => pods.push(pod),
+            Discovered::Done(result) => return result.map(|()| pods),
+        }
     }
+    Ok(pods)
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct MatchedPod<'a> {
+    namespace: &'a str,
+    name: &'a str,
+    pattern: &'a str,
+    #[serde(flatten)]
+    details: &'a PodDetails,
+}
+
+pub(crate) fn matched_pods<'a>(pods: &'a [PodInfo], patterns: &'a [String]) -> Vec<MatchedPod<'a>> {
+    pods.iter()
+        .map(|pod| MatchedPod {
+            namespace: &pod.namespace,
+            name: &pod.name,
+            pattern: patterns.get(pod.pattern_idx).map_or("", String::as_str),
+            details: &pod.details,
+        })
+        .collect()
+}
+
+const KUBECTL_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const KUBECTL_AUTH_TIMEOUT: Duration = Duration::from_secs(15);
+const AUTH_RETRY_DELAY: Duration = Duration::from_secs(15);
+
+#[derive(Debug, PartialEq, Eq)]
+struct KubectlTimeouts {
+    /// Passed to kubectl as `--request-timeout`.
+    request: Duration,
+    /// How long to wait for kubectl before killing it (e.g. stuck on a login prompt).
+    deadline: Duration,
+}
 
-    if has_warnings && !simple {
-        thread::sleep(Duration::from_secs(2));
+/// The defaults, or `timeout` for both; the deadline stays at least as long as the request
+/// timeout so kubectl gets to report its own timeout first.
+fn kubectl_timeouts(timeout: Option<Duration>) -> KubectlTimeouts {
+    match timeout {
+        Some(timeout) => KubectlTimeouts {
+            request: timeout,
+            deadline: timeout + Duration::from_secs(1),
+        },
+        None => KubectlTimeouts {
+            request: KUBECTL_REQUEST_TIMEOUT,
+            deadline: KUBECTL_AUTH_TIMEOUT,
+        },
     }
+}
 
-    if simple {
-        simple::run(pods, regexes, err_only)
-    } else {
-        tui::run(pods, regexes, err_only)
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(3);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+/// A log stream that stayed up this long counts as healthy and resets the backoff.
+const STABLE_STREAM: Duration = Duration::from_secs(30);
+
+/// The wait before reconnect number `attempt` (0-based): the base delay doubled per attempt,
+/// capped, then scaled into its upper half by `jitter` (0.0..=1.0) so panes following a
+/// flapping pod don't all reconnect in lockstep.
+fn reconnect_delay(attempt: u32, jitter: f64) -> Duration {
+    let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+    let delay = RECONNECT_BASE_DELAY
+        .saturating_mul(factor)
+        .min(RECONNECT_MAX_DELAY);
+    delay.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+}
+
+/// Per-follower reconnect state for `kubectl logs -f` streams.
+#[derive(Debug, Default)]
+pub struct ReconnectBackoff {
+    attempt: u32,
+}
+
+impl ReconnectBackoff {
+    /// The delay before reconnecting a stream that ran for `uptime`; a long-lived stream
+    /// starts the sequence over from the base delay.
+    pub fn next_delay(&mut self, uptime: Duration) -> Duration {
+        if uptime >= STABLE_STREAM {
+            self.attempt = 0;
+        }
+        let delay = reconnect_delay(self.attempt, random_unit());
+        self.attempt = self.attempt.saturating_add(1);
+        delay
     }
 }
 
-const KUBECTL_AUTH_TIMEOUT: Duration = Duration::from_secs(15);
+/// A pseudo-random value in 0.0..1.0 from std's randomly keyed hasher.
+fn random_unit() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let bits = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A kubectl failure that looks like the cluster is waiting for a login.
+#[derive(Debug)]
+struct AuthPending(String);
+
+impl std::fmt::Display for AuthPending {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for AuthPending {}
 
-pub fn find_matching_pods(regexes: &[Regex]) -> Result<Vec<PodInfo>> {
-    let mut child = Command::new("kubectl")
-        .args([
-            "get",
-            "pods",
-            "-A",
-            "-o",
-            "custom-columns=NAMESPACE:.metadata.namespace,NAME:.metadata.name",
-            "--no-headers",
-            "--request-timeout=10s",
-        ])
+/// [`find_matching_pods`], retried once after a pause when `target.retry` is set and the
+/// first attempt printed an authentication hint.
+pub(crate) fn find_pods_with_retry(
+    regexes: &[Regex],
+    target: &KubeTarget,
+    sp: &mut Option<spinoff::Spinner>,
+) -> Result<Vec<PodInfo>> {
+    let result = find_matching_pods(regexes, target);
+    let pending = match &result {
+        Err(e) if target.retry => e.downcast_ref::<AuthPending>().map(ToString::to_string),
+        _ => None,
+    };
+    let Some(pending) = pending else {
+        return result;
+    };
+    wait_before_retry(&pending, sp);
+    find_matching_pods(regexes, target)
+}
+
+fn wait_before_retry(pending: &str, sp: &mut Option<spinoff::Spinner>) {
+    spinner::stop_spinner(sp.as_mut());
+    eprintln!(
+        "{pending}\n\nRetrying once in {}s...",
+        AUTH_RETRY_DELAY.as_secs()
+    );
+    thread::sleep(AUTH_RETRY_DELAY);
+    *sp = spinner::create_spinner("Finding pods...");
+}
+
+pub fn find_matching_pods(regexes: &[Regex], target: &KubeTarget) -> Result<Vec<PodInfo>> {
+    Ok(match_pods(get_pods(target)?, regexes))
+}
+
+/// The pods whose name matches one of `regexes`, tagged with the first matching pattern.
+pub fn match_pods(pods: Vec<PodInfo>, regexes: &[Regex]) -> Vec<PodInfo> {
+    pods.into_iter()
+        .filter_map(|pod| match_pod(pod, regexes))
+        .collect()
+}
+
+/// `pod` tagged with the first of `regexes` matching its name, or `None` when none does.
+pub fn match_pod(mut pod: PodInfo, regexes: &[Regex]) -> Option<PodInfo> {
+    pod.pattern_idx = regexes.iter().position(|re| re.is_match(&pod.name))?;
+    Some(pod)
+}
+
+/// Every pod `kubectl get pods` lists for `target` (on its node, when one is set), before
+/// matching against any pattern.
+pub fn get_pods(target: &KubeTarget) -> Result<Vec<PodInfo>> {
+    let mut pods = Vec::new();
+    stream_pods(target, &mut |pod| pods.push(pod))?;
+    Ok(pods)
+}
+
+/// Like [`get_pods`], but hands each pod to `on_pod` as soon as its row is read, so callers
+/// can start following logs before kubectl has listed a large cluster.
+pub fn stream_pods(target: &KubeTarget, on_pod: &mut dyn FnMut(PodInfo)) -> Result<()> {
+    let mut child = target
+        .kubectl()
+        .args(target.get_pods_args())
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .spawn()
+        .logged_spawn()
         .context("Failed to run kubectl get pods")?;
 
-    let mut stdout = child.stdout.take().unwrap();
+    let stdout = child.stdout.take().unwrap();
     let mut stderr = child.stderr.take().unwrap();
-    let (tx, rx) = mpsc::channel();
+    let (line_tx, line_rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let (err_tx, err_rx) = mpsc::channel();
     thread::spawn(move || {
-        let mut out = Vec::new();
-        let _ = stdout.read_to_end(&mut out);
         let mut err = Vec::new();
         let _ = stderr.read_to_end(&mut err);
-        let _ = tx.send((out, err));
+        let _ = err_tx.send(err);
     });
 
-    let deadline = Instant::now() + KUBECTL_AUTH_TIMEOUT;
+    let deadline_after = kubectl_timeouts(target.timeout).deadline;
+    let deadline = Instant::now() + deadline_after;
+    let timed_out = |child: &mut std::process::Child| -> anyhow::Error {
+        let _ = child.kill();
+        let _ = child.wait();
+        let stderr_msg = err_rx
+            .recv_timeout(Duration::from_secs(1))
+            .ok()
+            .and_then(|e| String::from_utf8(e).ok())
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| format!("\n\nkubectl stderr:\n{s}"))
+            .unwrap_or_default();
+        AuthPending(format!(
+            "kubectl get pods timed out ({}s). \
+             If your cluster requires authentication, run your auth command first \
+             (e.g. open the login URL in a browser or run the token command), then run track again.{}",
+            deadline_after.as_secs(),
+            stderr_msg
+        ))
+        .into()
+    };
+
+    let columns = target.query_columns();
+    loop {
+        match line_rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(line) => {
+                if let Some(pod) = parse_pod_line(&line, &columns, target) {
+                    on_pod(pod);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => return Err(timed_out(&mut child)),
+        }
+    }
+
     let status = loop {
         match child.try_wait()? {
             Some(s) => break s,
-            None => {
-                if Instant::now() >= deadline {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                    let stderr_msg = rx
-                        .recv_timeout(Duration::from_secs(1))
-                        .ok()
-                        .and_then(|(_, e)| String::from_utf8(e).ok())
-                        .filter(|s| !s.trim().is_empty())
-                        .map(|s| format!("\n\nkubectl stderr:\n{s}"))
-                        .unwrap_or_default();
-                    anyhow::bail!(
-                        "kubectl get pods timed out ({}s). \
-                         If your cluster requires authentication, run your auth command first \
-                         (e.g. open the login URL in a browser or run the token command), then run track again.{}",
-                        KUBECTL_AUTH_TIMEOUT.as_secs(),
-                        stderr_msg
-                    );
-                }
-                thread::sleep(Duration::from_millis(100));
-            }
+            None if Instant::now() >= deadline => return Err(timed_out(&mut child)),
+            None => thread::sleep(Duration::from_millis(100)),
         }
     };
 
-    let (stdout_bytes, stderr_bytes) = rx
-        .recv_timeout(Duration::from_secs(5))
-        .unwrap_or_else(|_| (Vec::new(), Vec::new()));
-
     if !status.success() {
+        let stderr_bytes = err_rx
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap_or_default();
         let stderr_str = String::from_utf8_lossy(&stderr_bytes);
-        let hint = if stderr_str.contains("could not open the browser")
-            || stderr_str.contains("Please visit the following URL")
-            || stderr_str.contains("authenticate")
-        {
-            " Authenticate to your cluster first (e.g. open the login URL in a browser or run your auth command), then run track again."
+        let stderr_msg = if stderr_str.trim().is_empty() {
+            String::new()
         } else {
-            ""
+            format!("\n\nkubectl stderr:\n{stderr_str}")
         };
+        if let Some(hint) = auth_hint(&stderr_str) {
+            return Err(AuthPending(format!("kubectl get pods failed.{hint}{stderr_msg}")).into());
+        }
         anyhow::bail!(
-            "kubectl get pods failed{}.{}",
-            hint,
-            if stderr_str.trim().is_empty() {
-                String::new()
-            } else {
-                format!("\n\nkubectl stderr:\n{stderr_str}")
-            }
+            "kubectl get pods failed.{}{}",
+            namespace_hint(&stderr_str).unwrap_or_default(),
+            stderr_msg
         );
     }
 
-    let stdout = String::from_utf8(stdout_bytes)?;
-    let mut pods = Vec::new();
+    Ok(())
+}
 
-    for line in stdout.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
+/// One `kubectl get pods` row as a pod, skipping blank rows and pods off the target node.
+fn parse_pod_line(line: &str, columns: &[PodColumn], target: &KubeTarget) -> Option<PodInfo> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let (namespace, name, details) = parse_pod_row(line, columns)?;
+    if !target.on_target_node(&details) {
+        return None;
+    }
+    Some(PodInfo {
+        namespace,
+        name,
+        pattern_idx: 0,
+        details,
+    })
+}
+
+/// An actionable hint when kubectl failed because the cluster wants a login,
+/// including the login URL when kubectl printed one.
+fn auth_hint(stderr: &str) -> Option<String> {
+    let needs_auth = stderr.contains("could not open the browser")
+        || stderr.contains("Please visit the following URL")
+        || stderr.contains("authenticate");
+    if !needs_auth {
+        return None;
+    }
+    let url = stderr
+        .split_whitespace()
+        .find(|word| word.starts_with("https://") || word.starts_with("http://"));
+    Some(match url {
+        Some(url) => format!(
+            " Authenticate to your cluster first by opening {url} in a browser, then run track again."
+        ),
+        None => " Authenticate to your cluster first (e.g. open the login URL in a browser or run your auth command), then run track again.".to_string(),
+    })
+}
+
+/// Whether a line kubectl (or docker) printed on stderr says the pod (or container) no
+/// longer exists, so following its logs again is pointless.
+pub fn is_pod_gone(stderr_line: &str) -> bool {
+    stderr_line.contains("(NotFound)")
+        || (stderr_line.contains("pods \"") && stderr_line.contains("not found"))
+        || stderr_line.contains("No such container")
+}
+
+/// A hint to pass `--namespace` when RBAC forbids listing pods across all namespaces.
+fn namespace_hint(stderr: &str) -> Option<String> {
+    if !stderr.to_lowercase().contains("forbidden") || !stderr.contains("at the cluster scope") {
+        return None;
+    }
+    Some(
+        " Your account cannot list pods in all namespaces; pass --namespace <ns> (or -n) to search a single namespace."
+            .to_string(),
+    )
+}
+
+/// Stack trace format that `--err-only` keeps intact; `Auto` recognizes any of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TraceStyle {
+    #[default]
+    Auto,
+    Python,
+    Java,
+    Go,
+    Node,
+}
+
+impl TraceStyle {
+    const DETECTED: [TraceStyle; 4] = [
+        TraceStyle::Python,
+        TraceStyle::Go,
+        TraceStyle::Java,
+        TraceStyle::Node,
+    ];
+
+    /// The style of the stack trace that `line` starts, if any.
+    fn trace_start(self, line: &str) -> Option<TraceStyle> {
+        match self {
+            TraceStyle::Auto => Self::DETECTED
+                .into_iter()
+                .find(|style| style.starts_trace(line)),
+            style => style.starts_trace(line).then_some(style),
         }
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 2 {
-            continue;
+    }
+
+    fn starts_trace(self, line: &str) -> bool {
+        match self {
+            TraceStyle::Auto => self.trace_start(line).is_some(),
+            TraceStyle::Python => line.contains("Traceback (most recent call last)"),
+            TraceStyle::Java => {
+                line.starts_with("Exception in thread ")
+                    || exception_class(line).is_some_and(|class| class.contains('.'))
+            }
+            TraceStyle::Go => {
+                line.starts_with("panic: ")
+                    || (line.starts_with("goroutine ") && line.ends_with("]:"))
+            }
+            TraceStyle::Node => exception_class(line.strip_prefix("Uncaught ").unwrap_or(line))
+                .is_some_and(|class| !class.contains('.')),
         }
-        let namespace = parts[0];
-        let pod_name = parts[1];
+    }
 
-        for (i, re) in regexes.iter().enumerate() {
-            if re.is_match(pod_name) {
-                pods.push(PodInfo {
-                    namespace: namespace.to_string(),
-                    name: pod_name.to_string(),
-                    pattern_idx: i,
-                });
-                break;
+    /// Whether `line` belongs to a stack trace of this style that is already in progress.
+    fn continues_trace(self, line: &str) -> bool {
+        let trimmed = line.trim_start();
+        let indented = trimmed.len() < line.len();
+        match self {
+            TraceStyle::Auto => false,
+            TraceStyle::Python => indented,
+            TraceStyle::Java => {
+                (indented
+                    && (trimmed.starts_with("at ")
+                        || (trimmed.starts_with("... ") && trimmed.ends_with(" more"))))
+                    || trimmed.starts_with("Caused by: ")
+                    || trimmed.starts_with("Suppressed: ")
+            }
+            TraceStyle::Go => {
+                line.is_empty()
+                    || line.starts_with('\t')
+                    || line.starts_with("goroutine ")
+                    || line.starts_with("created by ")
+                    || line.starts_with('[')
+                    || line.ends_with(')')
             }
+            TraceStyle::Node => indented && trimmed.starts_with("at "),
+        }
+    }
+}
+
+/// The leading `SomeError`/`pkg.SomeException` of a line like `TypeError: boom`.
+fn exception_class(line: &str) -> Option<&str> {
+    let class = line.split_once(':').map_or(line, |(class, _)| class);
+    let looks_like_class = !class.is_empty()
+        && !class.contains(char::is_whitespace)
+        && (class.ends_with("Error") || class.ends_with("Exception"));
+    looks_like_class.then_some(class)
+}
+
+/// A user-defined multi-line error block: a line matching `start` opens it and the lines
+/// after it that match `continuation` (by default, indented lines) stay part of it.
+#[derive(Clone, Debug)]
+pub struct BlockPattern {
+    start: Regex,
+    continuation: Regex,
+}
+
+impl BlockPattern {
+    pub fn new(start: &str, continuation: Option<&str>) -> Result<Self> {
+        let compile = |flag: &str, pattern: &str| {
+            pattern::build_regex(pattern, false)
+                .with_context(|| format!("Invalid {flag} pattern \"{pattern}\""))
+        };
+        Ok(Self {
+            start: compile("--block-start", start)?,
+            continuation: compile("--block-continue", continuation.unwrap_or(r"^\s"))?,
+        })
+    }
+}
+
+/// Which stack trace or block, if any, the previous lines of a log stream belong to.
+#[derive(Clone, Debug, Default)]
+pub struct TraceState {
+    style: TraceStyle,
+    block: Option<BlockPattern>,
+    active: Option<TraceStyle>,
+    in_block: bool,
+}
+
+impl TraceState {
+    pub fn new(style: TraceStyle) -> Self {
+        Self {
+            style,
+            ..Self::default()
         }
     }
 
-    Ok(pods)
+    pub fn with_block(mut self, block: Option<BlockPattern>) -> Self {
+        self.block = block;
+        self
+    }
+
+    pub fn in_trace(&self) -> bool {
+        self.active.is_some() || self.in_block
+    }
 }
 
-pub fn should_show_line(line: &str, in_traceback: &mut bool) -> bool {
-    if line.contains("Traceback (most recent call last)") {
-        *in_traceback = true;
-        return true;
+/// Which log lines to keep: everything, or with `err_only` just warnings, errors, stack
+/// traces of `trace_style` and `block`s.
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    pub err_only: bool,
+    pub trace_style: TraceStyle,
+    pub block: Option<BlockPattern>,
+}
+
+impl LogFilter {
+    pub fn trace_state(&self) -> TraceState {
+        TraceState::new(self.trace_style).with_block(self.block.clone())
+    }
+
+    pub fn keeps(&self, line: &str, trace: &mut TraceState) -> bool {
+        !self.err_only || should_show_line(line, trace)
     }
+}
+
+/// Start times of each container's current and previous life. A crash-looping container is
+/// usually `waiting` with no running state, so its last terminated start counts too.
+const CONTAINER_STARTS_JSONPATH: &str = "jsonpath=\
+    {.status.containerStatuses[*].state.running.startedAt} \
+    {.status.containerStatuses[*].state.terminated.startedAt} \
+    {.status.containerStatuses[*].lastState.terminated.startedAt}";
 
-    if *in_traceback {
-        if line.starts_with(' ') || line.starts_with('\t') {
+/// The most recent of the space-separated RFC 3339 container start times kubectl printed.
+fn latest_start_time(stdout: &str) -> Option<&str> {
+    stdout.split_whitespace().max()
+}
+
+fn logs_args(name: &str, namespace: &str, started_at: Option<&str>) -> Vec<String> {
+    let since = match started_at {
+        Some(time) => format!("--since-time={time}"),
+        None => "--tail=1000".to_string(),
+    };
+    ["logs", "-f", since.as_str(), name, "-n", namespace]
+        .map(str::to_string)
+        .to_vec()
+}
+
+pub fn should_show_line(line: &str, trace: &mut TraceState) -> bool {
+    if let Some(block) = &trace.block {
+        if trace.in_block && block.continuation.is_match(line) {
             return true;
         }
-        *in_traceback = false;
-        if !line.is_empty() {
+        trace.in_block = block.start.is_match(line);
+        if trace.in_block {
+            trace.active = None;
             return true;
         }
     }
 
-    let upper = line.to_uppercase();
-    upper.contains("WARNING")
-        || upper.contains("ERROR")
-        || upper.contains("CRITICAL")
-        || upper.contains("FATAL")
+    let previous = trace.active.take();
+    if let Some(style) = previous.filter(|style| style.continues_trace(line)) {
+        trace.active = Some(style);
+        return true;
+    }
+    // Python ends a traceback with the unindented exception line, which is still part of it.
+    if previous == Some(TraceStyle::Python)
+        && !line.is_empty()
+        && !TraceStyle::Python.starts_trace(line)
+    {
+        return true;
+    }
+    if let Some(style) = trace.style.trace_start(line) {
+        trace.active = Some(style);
+        return true;
+    }
+
+    contains_keyword(line, SHOW_KEYWORDS, &SHOW_MATCHER)
 }
 
-pub fn pod_pattern_regex(pattern: &str) -> Regex {
-    Regex::new(pattern).unwrap_or_else(|_| {
-        let escaped = regex::escape(pattern);
-        Regex::new(&format!("(?i){}", escaped)).expect("escaped pattern must be valid")
-    })
+pub fn is_error_line(line: &str) -> bool {
+    if line.contains("Traceback (most recent call last)") {
+        return true;
+    }
+    contains_keyword(line, ERROR_KEYWORDS, &ERROR_MATCHER)
+}
+
+const SHOW_KEYWORDS: &[&str] = &["WARNING", "ERROR", "CRITICAL", "FATAL"];
+const ERROR_KEYWORDS: &[&str] = &["ERROR", "CRITICAL", "FATAL"];
+static SHOW_MATCHER: OnceLock<Vec<Finder<'static>>> = OnceLock::new();
+static ERROR_MATCHER: OnceLock<Vec<Finder<'static>>> = OnceLock::new();
+const UPPERCASE_BUF_LEN: usize = 512;
+
+/// Whether the uppercased line contains one of `keywords`. ASCII lines are uppercased into a
+/// stack buffer; other lines still go through `to_uppercase`, since Unicode case mapping can
+/// produce ASCII letters (e.g. `ı` uppercases to `I`).
+fn contains_keyword(
+    line: &str,
+    keywords: &[&'static str],
+    matcher: &OnceLock<Vec<Finder<'static>>>,
+) -> bool {
+    let finders = matcher.get_or_init(|| {
+        keywords
+            .iter()
+            .map(|keyword| Finder::new(keyword.as_bytes()))
+            .collect()
+    });
+    let bytes = line.as_bytes();
+    if !line.is_ascii() || bytes.len() > UPPERCASE_BUF_LEN {
+        let upper = line.to_uppercase();
+        return finders.iter().any(|f| f.find(upper.as_bytes()).is_some());
+    }
+    let mut buf = [0u8; UPPERCASE_BUF_LEN];
+    let upper = &mut buf[..bytes.len()];
+    upper.copy_from_slice(bytes);
+    upper.make_ascii_uppercase();
+    finders.iter().any(|f| f.find(upper).is_some())
+}
+
+const JSON_KEY: &str = "\x1b[36m";
+const JSON_STRING: &str = "\x1b[32m";
+const JSON_NUMBER: &str = "\x1b[33m";
+const JSON_LITERAL: &str = "\x1b[35m";
+const JSON_RESET: &str = "\x1b[0m";
+
+/// Pretty-prints a log line holding a JSON object or array, one entry per output line.
+/// Returns `None` for anything else so plain log lines pass through untouched.
+pub fn render_json_line(line: &str, use_color: bool) -> Option<Vec<String>> {
+    let trimmed = line.trim();
+    if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+    let mut out = String::new();
+    write_json(&value, 0, use_color, &mut out);
+    Some(out.lines().map(str::to_string).collect())
+}
+
+fn write_json(value: &serde_json::Value, indent: usize, use_color: bool, out: &mut String) {
+    use serde_json::Value;
+
+    let paint = |out: &mut String, text: &str, color: &str| {
+        if use_color {
+            out.push_str(color);
+            out.push_str(text);
+            out.push_str(JSON_RESET);
+        } else {
+            out.push_str(text);
+        }
+    };
+    let pad = "  ".repeat(indent + 1);
+
+    match value {
+        Value::Object(map) if map.is_empty() => out.push_str("{}"),
+        Value::Object(map) => {
+            out.push_str("{\n");
+            for (i, (key, val)) in map.iter().enumerate() {
+                out.push_str(&pad);
+                paint(out, &Value::String(key.clone()).to_string(), JSON_KEY);
+                out.push_str(": ");
+                write_json(val, indent + 1, use_color, out);
+                if i + 1 < map.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&"  ".repeat(indent));
+            out.push('}');
+        }
+        Value::Array(items) if items.is_empty() => out.push_str("[]"),
+        Value::Array(items) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&pad);
+                write_json(item, indent + 1, use_color, out);
+                if i + 1 < items.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&"  ".repeat(indent));
+            out.push(']');
+        }
+        Value::String(_) => paint(out, &value.to_string(), JSON_STRING),
+        Value::Number(_) => paint(out, &value.to_string(), JSON_NUMBER),
+        Value::Bool(_) | Value::Null => paint(out, &value.to_string(), JSON_LITERAL),
+    }
+}
+
+/// See [`pattern::name_pattern_regex`]: regex when valid, case-insensitive literal otherwise.
+pub fn pod_pattern_regex(pattern: &str) -> Result<Regex> {
+    pattern::name_pattern_regex(pattern)
+}
+
+fn no_match_message(pattern: &str) -> String {
+    format!("No pods found matching pattern: \"{pattern}\"")
 }
 
 fn print_no_match_warning(pattern: &str, use_color: bool) {
+    print_warning(&no_match_message(pattern), use_color);
+}
+
+/// The warning for each of `patterns` whose index isn't in `matched`.
+pub(crate) fn unmatched_messages(patterns: &[String], matched: &HashSet<usize>) -> Vec<String> {
+    patterns
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !matched.contains(i))
+        .map(|(_, pattern)| no_match_message(pattern))
+        .collect()
+}
+
+/// Warns about each pattern none of `pods` matched; returns how many there were.
+pub(crate) fn warn_unmatched_patterns(patterns: &[String], pods: &[PodInfo]) -> usize {
+    let use_color = colors::use_color(atty::Stream::Stderr);
+    let mut unmatched = 0;
+    for (i, pattern) in patterns.iter().enumerate() {
+        if !pods.iter().any(|p| p.pattern_idx == i) {
+            print_no_match_warning(pattern, use_color);
+            unmatched += 1;
+        }
+    }
+    unmatched
+}
+
+pub(crate) fn print_warning(message: &str, use_color: bool) {
     if use_color {
-        eprintln!(
-            "\n\x1b[93m\x1b[1m\u{26a0}  No pods found matching pattern: \"{pattern}\"\x1b[0m\n"
-        );
+        eprintln!("\n\x1b[93m\x1b[1m\u{26a0}  {message}\x1b[0m\n");
     } else {
-        eprintln!("\nNo pods found matching pattern: \"{pattern}\"\n");
+        eprintln!("\n{message}\n");
     }
 }
 
@@ -225,6 +1077,29 @@ fn print_no_match_warning(pattern: &str, use_color: bool) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_matched_pods() {
+        let pods = vec![
+            PodInfo {
+                namespace: "default".to_string(),
+                name: "api-1".to_string(),
+                pattern_idx: 1,
+                details: PodDetails::default(),
+            },
+            PodInfo {
+                namespace: "jobs".to_string(),
+                name: "worker-1".to_string(),
+                pattern_idx: 0,
+                details: PodDetails::default(),
+            },
+        ];
+        let patterns = vec!["worker".to_string(), "api".to_string()];
+        assert_eq!(
+            serde_json::to_string(&matched_pods(&pods, &patterns)).unwrap(),
+            r#"[{"namespace":"default","name":"api-1","pattern":"api"},{"namespace":"jobs","name":"worker-1","pattern":"worker"}]"#
+        );
+    }
+
     #[test]
     fn test_pod_info_key() {
         let cases = vec![
@@ -237,14 +1112,301 @@ mod tests {
                 namespace: ns.to_string(),
                 name: name.to_string(),
                 pattern_idx: 0,
+                details: PodDetails::default(),
             };
             assert_eq!(pod.key(), expected);
         }
     }
 
+    #[test]
+    fn test_kube_target_defaults_to_current_context_all_namespaces() {
+        let target = KubeTarget::default();
+        assert_eq!(target.kubectl().get_args().count(), 0);
+        let args = target.get_pods_args();
+        assert!(args.iter().any(|a| a == "-A"));
+        assert!(!args.iter().any(|a| a == "-n"));
+    }
+
+    #[test]
+    fn test_kube_target_context_and_namespace() {
+        let target = KubeTarget {
+            context: Some("staging".to_string()),
+            namespace: Some("payments".to_string()),
+            columns: vec![],
+            node: None,
+            timeout: None,
+            retry: false,
+            since_restart: false,
+            kubectl: None,
+        };
+        let cmd = target.kubectl();
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec!["--context", "staging"]);
+        let pod_args = target.get_pods_args();
+        assert_eq!(&pod_args[..4], &["get", "pods", "-n", "payments"]);
+        assert!(!pod_args.iter().any(|a| a == "-A"));
+    }
+
+    #[test]
+    fn test_logs_args_since_restart() {
+        assert_eq!(
+            logs_args("api-1", "prod", None),
+            vec!["logs", "-f", "--tail=1000", "api-1", "-n", "prod"]
+        );
+        let started_at = latest_start_time("2026-01-02T10:00:00Z 2026-01-02T11:30:00Z\n");
+        assert_eq!(started_at, Some("2026-01-02T11:30:00Z"));
+        assert_eq!(
+            logs_args("api-1", "prod", started_at),
+            vec![
+                "logs",
+                "-f",
+                "--since-time=2026-01-02T11:30:00Z",
+                "api-1",
+                "-n",
+                "prod"
+            ]
+        );
+        assert_eq!(latest_start_time(""), None);
+    }
+
+    #[test]
+    fn test_latest_start_time_without_running_state() {
+        // A crash-looping pod: nothing running, only the last terminated life.
+        assert!(CONTAINER_STARTS_JSONPATH.contains("lastState.terminated.startedAt"));
+        assert_eq!(
+            latest_start_time("  2026-01-02T11:30:00Z"),
+            Some("2026-01-02T11:30:00Z")
+        );
+        assert_eq!(
+            latest_start_time("2026-01-02T12:00:00Z  2026-01-02T11:30:00Z"),
+            Some("2026-01-02T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_custom_columns() {
+        assert_eq!(
+            custom_columns(&[]),
+            "custom-columns=NAMESPACE:.metadata.namespace,NAME:.metadata.name"
+        );
+        assert_eq!(
+            custom_columns(&[PodColumn::Node, PodColumn::Status]),
+            "custom-columns=NAMESPACE:.metadata.namespace,NAME:.metadata.name,NODE:.spec.nodeName,STATUS:.status.phase"
+        );
+    }
+
+    #[test]
+    fn test_node_filter_adds_node_column() {
+        let mut target = KubeTarget {
+            columns: vec![PodColumn::Status],
+            ..KubeTarget::default()
+        };
+        assert_eq!(target.query_columns(), vec![PodColumn::Status]);
+        assert!(target.on_target_node(&PodDetails::default()));
+
+        target.node = Some("node-a".to_string());
+        assert_eq!(
+            target.query_columns(),
+            vec![PodColumn::Status, PodColumn::Node]
+        );
+        let on = |node: Option<&str>| PodDetails {
+            node: node.map(str::to_string),
+            ..PodDetails::default()
+        };
+        assert!(target.on_target_node(&on(Some("node-a"))));
+        assert!(!target.on_target_node(&on(Some("node-b"))));
+        assert!(!target.on_target_node(&on(None)));
+    }
+
+    #[test]
+    fn test_collect_pending_waits_for_the_whole_listing() {
+        let pod = |name: &str| PodInfo {
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            pattern_idx: 0,
+            details: PodDetails::default(),
+        };
+        let (tx, rx) = mpsc::channel();
+        tx.send(Discovered::Pod(pod("api-2"))).unwrap();
+        tx.send(Discovered::Done(Ok(()))).unwrap();
+        let pods = collect_pending(pod("api-1"), rx).unwrap();
+        let names: Vec<&str> = pods.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["api-1", "api-2"]);
+
+        let (tx, rx) = mpsc::channel();
+        let failed = Discovered::Done(Err(anyhow::anyhow!("boom")));
+        tx.send(failed).unwrap();
+        assert!(collect_pending(pod("api-1"), rx).is_err());
+
+        let matched = HashSet::from([1]);
+        let patterns = ["api".to_string(), "web".to_string()];
+        assert_eq!(
+            unmatched_messages(&patterns, &matched),
+            vec!["No pods found matching pattern: \"api\""]
+        );
+    }
+
+    #[test]
+    fn test_with_column_adds_once() {
+        let target = KubeTarget {
+            columns: vec![PodColumn::Status],
+            ..KubeTarget::default()
+        }
+        .with_column(PodColumn::Node)
+        .with_column(PodColumn::Node);
+        assert_eq!(target.columns, vec![PodColumn::Status, PodColumn::Node]);
+        let args = target.get_pods_args().join(" ");
+        assert!(args.contains("NODE:.spec.nodeName"), "{args}");
+    }
+
+    #[test]
+    fn test_kubectl_uses_resolved_path() {
+        assert_eq!(KubeTarget::default().kubectl().get_program(), "kubectl");
+        let target = KubeTarget {
+            context: Some("prod".to_string()),
+            kubectl: Some(PathBuf::from("/opt/bin/kubectl")),
+            ..KubeTarget::default()
+        };
+        let cmd = target.kubectl();
+        assert_eq!(cmd.get_program(), "/opt/bin/kubectl");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), ["--context", "prod"]);
+    }
+
+    #[test]
+    fn test_parse_pod_row() {
+        let (ns, name, details) = parse_pod_row("default   api-1", &[]).unwrap();
+        assert_eq!((ns.as_str(), name.as_str()), ("default", "api-1"));
+        assert_eq!(details, PodDetails::default());
+        assert!(parse_pod_row("lonely", &[]).is_none());
+
+        let columns = [PodColumn::Status, PodColumn::Restarts, PodColumn::Node];
+        let (_, _, details) = parse_pod_row("jobs worker-1 Running 1,2 node-a", &columns).unwrap();
+        assert_eq!(details.status.as_deref(), Some("Running"));
+        assert_eq!(details.restarts, Some(3));
+        assert_eq!(details.node.as_deref(), Some("node-a"));
+
+        let (_, _, details) =
+            parse_pod_row("jobs worker-2 Pending <none> <none>", &columns).unwrap();
+        assert_eq!(details.status.as_deref(), Some("Pending"));
+        assert_eq!(details.restarts, None);
+        assert_eq!(details.node, None);
+    }
+
+    #[test]
+    fn test_kubectl_timeouts() {
+        assert_eq!(
+            kubectl_timeouts(None),
+            KubectlTimeouts {
+                request: Duration::from_secs(10),
+                deadline: Duration::from_secs(15),
+            }
+        );
+        assert_eq!(
+            kubectl_timeouts(Some(Duration::from_secs(60))),
+            KubectlTimeouts {
+                request: Duration::from_secs(60),
+                deadline: Duration::from_secs(61),
+            }
+        );
+
+        let target = KubeTarget {
+            timeout: Some(Duration::from_secs(45)),
+            ..KubeTarget::default()
+        };
+        assert!(target
+            .get_pods_args()
+            .contains(&"--request-timeout=45s".to_string()));
+        assert!(target
+            .container_starts_args("api-1", "default")
+            .contains(&"--request-timeout=45s".to_string()));
+    }
+
+    #[test]
+    fn test_reconnect_delay_doubles_up_to_cap() {
+        let secs: Vec<u64> = (0..7).map(|a| reconnect_delay(a, 1.0).as_secs()).collect();
+        assert_eq!(secs, vec![3, 6, 12, 24, 30, 30, 30]);
+        assert_eq!(reconnect_delay(u32::MAX, 1.0), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_reconnect_delay_jitter_stays_in_upper_half() {
+        assert_eq!(reconnect_delay(0, 0.0), Duration::from_millis(1500));
+        assert_eq!(reconnect_delay(4, 0.0), Duration::from_secs(15));
+        assert_eq!(reconnect_delay(4, 0.5), Duration::from_millis(22500));
+        assert_eq!(reconnect_delay(4, 7.0), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_reconnect_backoff_resets_after_stable_stream() {
+        let mut backoff = ReconnectBackoff::default();
+        let quick = Duration::from_secs(1);
+        assert!(backoff.next_delay(quick) <= Duration::from_secs(3));
+        assert!(backoff.next_delay(quick) >= Duration::from_secs(3));
+        assert!(backoff.next_delay(quick) >= Duration::from_secs(6));
+        assert!(backoff.next_delay(STABLE_STREAM) <= Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_auth_hint() {
+        assert!(auth_hint("error: the server doesn't have a resource type").is_none());
+
+        let with_url = auth_hint(
+            "error: could not open the browser\nPlease visit the following URL:\n\nhttps://login.example.com/device?code=abc\n",
+        )
+        .unwrap();
+        assert!(with_url.contains("https://login.example.com/device?code=abc"));
+
+        let without_url = auth_hint("You must authenticate first").unwrap();
+        assert!(without_url.contains("run your auth command"));
+    }
+
+    #[test]
+    fn test_namespace_hint() {
+        let cluster = "Error from server (Forbidden): pods is forbidden: User \"dev\" cannot list resource \"pods\" in API group \"\" at the cluster scope";
+        assert!(namespace_hint(cluster).unwrap().contains("--namespace"));
+
+        let in_namespace = "Error from server (Forbidden): pods is forbidden: User \"dev\" cannot list resource \"pods\" in API group \"\" in the namespace \"prod\"";
+        assert!(namespace_hint(in_namespace).is_none());
+        assert!(namespace_hint("error: You must be logged in to the server").is_none());
+    }
+
+    #[test]
+    fn test_is_pod_gone() {
+        assert!(is_pod_gone(
+            "Error from server (NotFound): pods \"api-1\" not found"
+        ));
+        assert!(is_pod_gone(
+            "Error response from daemon: No such container: web-1"
+        ));
+        assert!(!is_pod_gone(
+            "Error from server (BadRequest): container \"app\" in pod \"api-1\" is waiting to start"
+        ));
+        assert!(!is_pod_gone("user not found in cache"));
+    }
+
+    #[test]
+    fn test_nav_mode_step() {
+        let cases = vec![
+            (NavMode::Wrap, 0, 3, true, 1),
+            (NavMode::Wrap, 2, 3, true, 0),
+            (NavMode::Wrap, 0, 3, false, 2),
+            (NavMode::Clamp, 2, 3, true, 2),
+            (NavMode::Clamp, 0, 3, false, 0),
+            (NavMode::Clamp, 1, 3, false, 0),
+            (NavMode::Wrap, 0, 0, true, 0),
+        ];
+        for (mode, current, len, forward, expected) in cases {
+            assert_eq!(
+                mode.step(current, len, forward),
+                expected,
+                "{mode:?} {current}/{len} forward={forward}"
+            );
+        }
+    }
+
     #[test]
     fn test_pod_pattern_regex_valid() {
-        let re = pod_pattern_regex("api-.*");
+        let re = pod_pattern_regex("api-.*").unwrap();
         assert!(re.is_match("api-server"));
         assert!(re.is_match("api-worker-123"));
         assert!(!re.is_match("frontend"));
@@ -252,7 +1414,7 @@ mod tests {
 
     #[test]
     fn test_pod_pattern_regex_invalid_falls_back_case_insensitive() {
-        let re = pod_pattern_regex("[invalid");
+        let re = pod_pattern_regex("[invalid").unwrap();
         assert!(re.is_match("[invalid"));
         assert!(re.is_match("[INVALID"));
         assert!(re.is_match("[Invalid"));
@@ -260,11 +1422,51 @@ mod tests {
 
     #[test]
     fn test_pod_pattern_regex_case_sensitive_by_default() {
-        let re = pod_pattern_regex("MyPod");
+        let re = pod_pattern_regex("MyPod").unwrap();
         assert!(re.is_match("MyPod"));
         assert!(!re.is_match("mypod"));
     }
 
+    /// The `to_uppercase` implementation the keyword matcher replaced.
+    fn uppercase_contains(line: &str, keywords: &[&str]) -> bool {
+        let upper = line.to_uppercase();
+        keywords.iter().any(|keyword| upper.contains(keyword))
+    }
+
+    #[test]
+    fn test_keyword_matching_matches_uppercase_semantics() {
+        let lines = [
+            "2026-01-01 ERROR something broke",
+            "warning: deprecated",
+            "Critical failure",
+            "fAtAl: x",
+            "INFO all good",
+            "errors=0",
+            "ERRO",
+            "warnıng with dotless i",
+            "crıtıcal",
+            "straße ERROR",
+            "\u{1F600} fatal emoji",
+            "ﬁnal warning",
+            "",
+        ];
+        for line in lines {
+            assert_eq!(
+                contains_keyword(line, SHOW_KEYWORDS, &SHOW_MATCHER),
+                uppercase_contains(line, SHOW_KEYWORDS),
+                "{line}"
+            );
+            assert_eq!(
+                contains_keyword(line, ERROR_KEYWORDS, &ERROR_MATCHER),
+                uppercase_contains(line, ERROR_KEYWORDS),
+                "{line}"
+            );
+        }
+        let long = format!("{} fatal", "x".repeat(UPPERCASE_BUF_LEN));
+        assert!(contains_keyword(&long, SHOW_KEYWORDS, &SHOW_MATCHER));
+        assert!(contains_keyword("warnıng", SHOW_KEYWORDS, &SHOW_MATCHER));
+    }
+
     #[test]
     fn test_should_show_line_error_keywords() {
         let cases = vec![
@@ -280,66 +1482,187 @@ mod tests {
             ("", false),
         ];
         for (line, expected) in cases {
-            let mut in_traceback = false;
+            let mut trace = TraceState::default();
             assert_eq!(
-                should_show_line(line, &mut in_traceback),
+                should_show_line(line, &mut trace),
                 expected,
                 "Failed for line: {line:?}"
             );
         }
     }
 
+    #[test]
+    fn test_is_error_line() {
+        assert!(is_error_line("2026-01-01 ERROR something broke"));
+        assert!(is_error_line("fatal: crash"));
+        assert!(is_error_line("Traceback (most recent call last):"));
+        assert!(!is_error_line("2026-01-01 WARNING disk full"));
+        assert!(!is_error_line("INFO all good"));
+    }
+
+    #[test]
+    fn test_render_json_line_pretty_prints_objects() {
+        let rendered = render_json_line(
+            r#"{"level":"info","msg":"ok","ctx":{"n":1,"tags":[]}}"#,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            rendered,
+            vec![
+                "{",
+                r#"  "level": "info","#,
+                r#"  "msg": "ok","#,
+                r#"  "ctx": {"#,
+                r#"    "n": 1,"#,
+                r#"    "tags": []"#,
+                "  }",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_json_line_colors_keys_and_values() {
+        let rendered = render_json_line(r#"{"ok":true}"#, true).unwrap();
+        assert_eq!(rendered[1], "  \x1b[36m\"ok\"\x1b[0m: \x1b[35mtrue\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_json_line_ignores_non_json() {
+        assert!(render_json_line("2026-01-01 INFO started", false).is_none());
+        assert!(render_json_line("{not json", false).is_none());
+        assert!(render_json_line("42", false).is_none());
+        assert!(render_json_line("", false).is_none());
+    }
+
     #[test]
     fn test_should_show_line_traceback_sequence() {
-        let mut in_traceback = false;
+        let mut trace = TraceState::default();
 
         assert!(should_show_line(
             "Traceback (most recent call last):",
-            &mut in_traceback
+            &mut trace
         ));
-        assert!(in_traceback);
+        assert!(trace.in_trace());
 
         assert!(should_show_line(
             "  File \"main.py\", line 10, in <module>",
-            &mut in_traceback
+            &mut trace
         ));
-        assert!(in_traceback);
+        assert!(trace.in_trace());
 
-        assert!(should_show_line(
-            "    result = do_thing()",
-            &mut in_traceback
-        ));
-        assert!(in_traceback);
+        assert!(should_show_line("    result = do_thing()", &mut trace));
+        assert!(trace.in_trace());
 
-        assert!(should_show_line("ValueError: bad value", &mut in_traceback));
-        assert!(!in_traceback);
+        assert!(should_show_line("ValueError: bad value", &mut trace));
+        assert!(!trace.in_trace());
 
-        assert!(!should_show_line("normal log after traceback", &mut in_traceback));
+        assert!(!should_show_line("normal log after traceback", &mut trace));
     }
 
     #[test]
     fn test_should_show_line_traceback_with_tabs() {
-        let mut in_traceback = false;
+        let mut trace = TraceState::default();
 
         assert!(should_show_line(
             "Traceback (most recent call last):",
-            &mut in_traceback
-        ));
-        assert!(should_show_line(
-            "\tFile \"main.py\", line 5",
-            &mut in_traceback
+            &mut trace
         ));
-        assert!(in_traceback);
+        assert!(should_show_line("\tFile \"main.py\", line 5", &mut trace));
+        assert!(trace.in_trace());
+    }
+
+    fn shown_lines<'a>(style: TraceStyle, lines: &[&'a str]) -> Vec<&'a str> {
+        let mut trace = TraceState::new(style);
+        lines
+            .iter()
+            .copied()
+            .filter(|line| should_show_line(line, &mut trace))
+            .collect()
+    }
+
+    #[test]
+    fn test_should_show_line_java_stack() {
+        let lines = [
+            "12:00:01 INFO handling request",
+            "Exception in thread \"main\" java.lang.IllegalStateException: boom",
+            "\tat com.example.App.run(App.java:42)",
+            "\tat com.example.App.main(App.java:10)",
+            "Caused by: java.io.IOException: disk",
+            "\t... 2 more",
+            "12:00:02 INFO recovered",
+        ];
+        let shown = shown_lines(TraceStyle::Java, &lines);
+        assert_eq!(shown, &lines[1..6]);
+        assert_eq!(shown_lines(TraceStyle::Auto, &lines), shown);
+        assert_eq!(shown_lines(TraceStyle::Python, &lines), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_should_show_line_go_panic() {
+        let lines = [
+            "panic: runtime error: index out of range [3] with length 2",
+            "",
+            "goroutine 1 [running]:",
+            "main.handler({0x1, 0x2})",
+            "\t/app/main.go:12 +0x1d",
+            "created by main.serve in goroutine 1",
+            "exit status 2",
+        ];
+        assert_eq!(shown_lines(TraceStyle::Go, &lines), &lines[..6]);
+        assert_eq!(shown_lines(TraceStyle::Auto, &lines), &lines[..6]);
+    }
+
+    #[test]
+    fn test_should_show_line_node_stack() {
+        let lines = [
+            "TypeError: Cannot read properties of undefined (reading 'id')",
+            "    at Object.<anonymous> (/app/index.js:3:15)",
+            "    at Module._compile (node:internal/modules/cjs/loader:1256:14)",
+            "listening on :3000",
+        ];
+        assert_eq!(shown_lines(TraceStyle::Node, &lines), &lines[..3]);
+        assert_eq!(shown_lines(TraceStyle::Auto, &lines), &lines[..3]);
+        assert_eq!(shown_lines(TraceStyle::Python, &lines), &lines[..1]);
+    }
+
+    #[test]
+    fn test_should_show_line_custom_block() {
+        let block = BlockPattern::new(r"^BEGIN ERROR", Some(r"^(\s|END)")).unwrap();
+        let mut trace = TraceState::default().with_block(Some(block));
+        let lines = [
+            ("BEGIN ERROR report", true),
+            ("  code=42", true),
+            ("  detail: upstream", true),
+            ("END", true),
+            ("INFO next request", false),
+            ("  indented but outside a block", false),
+        ];
+        for (line, expected) in lines {
+            assert_eq!(should_show_line(line, &mut trace), expected, "{line}");
+        }
+        assert!(!trace.in_trace());
+    }
+
+    #[test]
+    fn test_block_pattern_defaults_to_indented_continuation() {
+        let block = BlockPattern::new("^Failed", None).unwrap();
+        let mut trace = TraceState::default().with_block(Some(block));
+        assert!(should_show_line("Failed to sync", &mut trace));
+        assert!(should_show_line("\tretrying in 5s", &mut trace));
+        assert!(!should_show_line("synced", &mut trace));
+        assert!(BlockPattern::new("(", None).is_err());
     }
 
     #[test]
     fn test_should_show_line_no_traceback_state_leak() {
-        let mut in_traceback = false;
+        let mut trace = TraceState::default();
 
-        assert!(!should_show_line("INFO all good", &mut in_traceback));
-        assert!(!in_traceback);
+        assert!(!should_show_line("INFO all good", &mut trace));
+        assert!(!trace.in_trace());
 
-        assert!(!should_show_line("DEBUG details", &mut in_traceback));
-        assert!(!in_traceback);
+        assert!(!should_show_line("DEBUG details", &mut trace));
+        assert!(!trace.in_trace());
     }
 }
@@ -1,13 +1,17 @@
-use super::{find_matching_pods, should_show_line, PodInfo};
+use crate::utils::colors;
+use crate::utils::log::CommandExt;
+use super::{
+    find_matching_pods, render_json_line, KubeTarget, LogFilter, PodInfo, ReconnectBackoff,
+};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::collections::HashSet;
 use std::io::{BufRead, BufReader};
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const RESET: &str = "\x1b[0m";
 const BOLD: &str = "\x1b[1m";
@@ -27,10 +31,17 @@ const POD_COLORS: &[&str] = &[
 struct OutputState {
     last_pod: String,
     use_color: bool,
+    json_logs: bool,
 }
 
-pub fn run(pods: Vec<PodInfo>, regexes: Vec<Regex>, err_only: bool) -> Result<()> {
-    let use_color = atty::is(atty::Stream::Stdout);
+pub fn run(
+    pods: Vec<PodInfo>,
+    regexes: Vec<Regex>,
+    filter: LogFilter,
+    json_logs: bool,
+    target: KubeTarget,
+) -> Result<()> {
+    let use_color = colors::use_color(atty::Stream::Stdout);
     let running = Arc::new(AtomicBool::new(true));
 
     let r = running.clone();
@@ -43,15 +54,15 @@ pub fn run(pods: Vec<PodInfo>, regexes: Vec<Regex>, err_only: bool) -> Result<()
     let output_state = Arc::new(Mutex::new(OutputState {
         last_pod: String::new(),
         use_color,
+        json_logs,
     }));
 
     for pod in &pods {
         active_pods.lock().unwrap().insert(pod.key());
         spawn_log_follower(
-            &pod.namespace,
-            &pod.name,
-            pod.pattern_idx,
-            err_only,
+            pod,
+            &target,
+            &filter,
             running.clone(),
             active_pods.clone(),
             output_state.clone(),
@@ -64,7 +75,7 @@ pub fn run(pods: Vec<PodInfo>, regexes: Vec<Regex>, err_only: bool) -> Result<()
             break;
         }
 
-        if let Ok(new_pods) = find_matching_pods(&regexes) {
+        if let Ok(new_pods) = find_matching_pods(&regexes, &target) {
             for pod in &new_pods {
                 let key = pod.key();
                 let should_spawn = {
@@ -79,10 +90,9 @@ pub fn run(pods: Vec<PodInfo>, regexes: Vec<Regex>, err_only: bool) -> Result<()
 
                 if should_spawn {
                     spawn_log_follower(
-                        &pod.namespace,
-                        &pod.name,
-                        pod.pattern_idx,
-                        err_only,
+                        pod,
+                        &target,
+                        &filter,
                         running.clone(),
                         active_pods.clone(),
                         output_state.clone(),
@@ -96,37 +106,40 @@ pub fn run(pods: Vec<PodInfo>, regexes: Vec<Regex>, err_only: bool) -> Result<()
 }
 
 fn spawn_log_follower(
-    namespace: &str,
-    pod_name: &str,
-    pattern_idx: usize,
-    err_only: bool,
+    pod: &PodInfo,
+    target: &KubeTarget,
+    filter: &LogFilter,
     running: Arc<AtomicBool>,
     active_pods: Arc<Mutex<HashSet<String>>>,
     output_state: Arc<Mutex<OutputState>>,
 ) {
-    let ns = namespace.to_string();
-    let name = pod_name.to_string();
-    let color = POD_COLORS[pattern_idx % POD_COLORS.len()];
+    let ns = pod.namespace.clone();
+    let name = pod.name.clone();
+    let target = target.clone();
+    let filter = filter.clone();
+    let color = POD_COLORS[pod.pattern_idx % POD_COLORS.len()];
 
     thread::spawn(move || {
         let key = format!("{}/{}", ns, name);
+        let mut backoff = ReconnectBackoff::default();
 
         loop {
             if !running.load(Ordering::SeqCst) {
                 break;
             }
 
-            let result = Command::new("kubectl")
-                .args(["logs", "-f", "--tail=1000", &name, "-n", &ns])
+            let started = Instant::now();
+            let result = target
+                .logs_command(&name, &ns)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
-                .spawn();
+                .logged_spawn();
 
             match result {
                 Ok(mut child) => {
                     if let Some(stdout) = child.stdout.take() {
                         let reader = BufReader::new(stdout);
-                        let mut in_traceback = false;
+                        let mut trace = filter.trace_state();
 
                         for line in reader.lines() {
                             if !running.load(Ordering::SeqCst) {
@@ -136,7 +149,7 @@ fn spawn_log_follower(
 
                             match line {
                                 Ok(text) => {
-                                    if err_only && !should_show_line(&text, &mut in_traceback) {
+                                    if !filter.keeps(&text, &mut trace) {
                                         continue;
                                     }
                                     let mut state = output_state.lock().unwrap();
@@ -151,7 +164,14 @@ fn spawn_log_follower(
                                         }
                                         state.last_pod = key.clone();
                                     }
-                                    println!("{text}");
+                                    match state
+                                        .json_logs
+                                        .then(|| render_json_line(&text, state.use_color))
+                                        .flatten()
+                                    {
+                                        Some(rendered) => println!("{}", rendered.join("\n")),
+                                        None => println!("{text}"),
+                                    }
                                 }
                                 Err(_) => break,
                             }
@@ -171,7 +191,7 @@ fn spawn_log_follower(
                 break;
             }
 
-            thread::sleep(Duration::from_secs(3));
+            thread::sleep(backoff.next_delay(started.elapsed()));
         }
 
         active_pods.lock().unwrap().remove(&key);
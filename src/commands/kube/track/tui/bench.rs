@@ -0,0 +1,64 @@
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::super::LogFilter;
+use super::state::{PodPane, MAX_LOG_LINES};
+use super::theme::Theme;
+use super::{build_with, file, ThreadSleeper, TrackDeps, DEFAULT_POLL_INTERVAL};
+use crate::tui::TuiApp;
+
+/// Time per frame for rendering `pane_count` panes with full log buffers. Used by
+/// `benches/render_panes.rs`.
+pub fn time_full_buffer_frames(
+    pane_count: usize,
+    width: u16,
+    height: u16,
+    frames: u32,
+) -> Duration {
+    let files = Arc::new(file::FileGlobs::default());
+    let deps = TrackDeps {
+        discovery: files.clone(),
+        spawner: Arc::new(file::FileLogSpawner),
+        pattern_to_regex: files,
+        sleeper: Arc::new(ThreadSleeper),
+    };
+    let mut app = build_with(
+        vec![],
+        vec![],
+        LogFilter::default(),
+        deps,
+        DEFAULT_POLL_INTERVAL,
+        Theme::default(),
+    );
+    for p in 0..pane_count {
+        let color = app.theme.pane_color(p);
+        let alive = Arc::new(AtomicBool::new(true));
+        let mut pane = PodPane::new(format!("default/api-{p}"), color, alive);
+        for i in 0..MAX_LOG_LINES {
+            pane.push_line(format!(
+                "\x1b[3{}mINFO\x1b[0m request {i} handled in {}ms {}",
+                i % 7,
+                i % 250,
+                "x".repeat(120)
+            ));
+        }
+        app.state.add_pane(pane);
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend");
+    app.update_layout(Rect::new(0, 0, width, height), height.saturating_sub(1));
+    let start = Instant::now();
+    for _ in 0..frames {
+        terminal
+            .draw(|frame| app.render(frame))
+            .expect("test backend");
+    }
+    let per_frame = start.elapsed() / frames;
+    // Stops the poll loop `build_with` started.
+    app.shared.running.store(false, Ordering::SeqCst);
+    per_frame
+}
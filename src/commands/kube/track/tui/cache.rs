@@ -0,0 +1,153 @@
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::super::PodInfo;
+use super::traits::PodDiscovery;
+
+/// Reuses the last pod listing for `ttl`, so adding several patterns in quick succession
+/// runs one `kubectl get pods` instead of one per pattern. The poll loop refreshes it.
+pub struct CachedDiscovery {
+    inner: Arc<dyn PodDiscovery>,
+    ttl: Duration,
+    last: Mutex<Option<(Instant, Vec<PodInfo>)>>,
+}
+
+impl CachedDiscovery {
+    pub fn new(inner: Arc<dyn PodDiscovery>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            last: Mutex::new(None),
+        }
+    }
+}
+
+impl PodDiscovery for CachedDiscovery {
+    fn list_pods(&self) -> Result<Vec<PodInfo>> {
+        // Held across the lookup so concurrent callers wait for it and share the result.
+        let mut last = self.last.lock().unwrap();
+        if let Some((at, pods)) = last.as_ref() {
+            if at.elapsed() < self.ttl {
+                return Ok(pods.clone());
+            }
+        }
+        let pods = self.inner.refresh_pods()?;
+        *last = Some((Instant::now(), pods.clone()));
+        Ok(pods)
+    }
+
+    fn refresh_pods(&self) -> Result<Vec<PodInfo>> {
+        let mut last = self.last.lock().unwrap();
+        let pods = self.inner.refresh_pods()?;
+        *last = Some((Instant::now(), pods.clone()));
+        Ok(pods)
+    }
+
+    fn stream_pods(&self, on_pod: &mut dyn FnMut(PodInfo)) -> Result<()> {
+        let mut last = self.last.lock().unwrap();
+        if let Some((at, pods)) = last.as_ref() {
+            if at.elapsed() < self.ttl {
+                pods.iter().cloned().for_each(on_pod);
+                return Ok(());
+            }
+        }
+        let mut pods = Vec::new();
+        self.inner.stream_pods(&mut |pod| {
+            pods.push(pod.clone());
+            on_pod(pod);
+        })?;
+        *last = Some((Instant::now(), pods));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingDiscovery {
+        calls: AtomicUsize,
+    }
+
+    impl PodDiscovery for CountingDiscovery {
+        fn list_pods(&self) -> Result<Vec<PodInfo>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(["api-1", "web-1"]
+                .iter()
+                .map(|name| PodInfo {
+                    namespace: "default".to_string(),
+                    name: name.to_string(),
+                    pattern_idx: 0,
+                    details: Default::default(),
+                })
+                .collect())
+        }
+    }
+
+    fn counting() -> Arc<CountingDiscovery> {
+        Arc::new(CountingDiscovery {
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    #[test]
+    fn test_pattern_lookups_within_ttl_share_one_listing() {
+        let inner = counting();
+        let cache = CachedDiscovery::new(inner.clone(), Duration::from_secs(60));
+
+        let mut matched = Vec::new();
+        for pattern in ["api", "web"] {
+            cache
+                .stream_matching_pods(&[Regex::new(pattern).unwrap()], &mut |pod| {
+                    matched.push(pod.name)
+                })
+                .unwrap();
+        }
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(matched, vec!["api-1", "web-1"]);
+    }
+
+    #[test]
+    fn test_refresh_bypasses_and_repopulates_cache() {
+        let inner = counting();
+        let cache = CachedDiscovery::new(inner.clone(), Duration::from_secs(60));
+
+        cache.refresh_pods().unwrap();
+        cache.list_pods().unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        cache.refresh_pods().unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_streamed_listing_populates_cache() {
+        let inner = counting();
+        let cache = CachedDiscovery::new(inner.clone(), Duration::from_secs(60));
+
+        let mut streamed = Vec::new();
+        cache
+            .stream_matching_pods(&[Regex::new("web").unwrap()], &mut |pod| {
+                streamed.push(pod.name)
+            })
+            .unwrap();
+        assert_eq!(streamed, vec!["web-1"]);
+
+        assert_eq!(cache.list_pods().unwrap().len(), 2);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_expired_listing_is_queried_again() {
+        let inner = counting();
+        let cache = CachedDiscovery::new(inner.clone(), Duration::ZERO);
+
+        cache.list_pods().unwrap();
+        cache.list_pods().unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
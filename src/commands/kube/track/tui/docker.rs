@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::process::Command;
+
+use super::super::{match_pods, PodDetails, PodInfo};
+use super::stream::follow_command;
+use super::traits::{LogStreamSpawnOpts, LogStreamSpawner, PodDiscovery};
+
+/// Pane namespace used for containers, so pane keys read `docker/<name>`.
+pub const DOCKER_NAMESPACE: &str = "docker";
+
+pub fn find_matching_containers(regexes: &[Regex]) -> Result<Vec<PodInfo>> {
+    Ok(match_containers(&running_container_names()?, regexes))
+}
+
+fn running_container_names() -> Result<String> {
+    which::which("docker")
+        .context("docker not found on PATH; install it to follow container logs")?;
+    let output = Command::new("docker")
+        .args(["ps", "--format", "{{.Names}}"])
+        .output()
+        .context("Failed to run docker ps")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "docker ps failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn match_containers(names: &str, regexes: &[Regex]) -> Vec<PodInfo> {
+    match_pods(containers(names), regexes)
+}
+
+fn containers(names: &str) -> Vec<PodInfo> {
+    names
+        .lines()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(|name| PodInfo {
+            namespace: DOCKER_NAMESPACE.to_string(),
+            name: name.to_string(),
+            pattern_idx: 0,
+            details: PodDetails::default(),
+        })
+        .collect()
+}
+
+pub struct DockerContainerDiscovery;
+
+impl PodDiscovery for DockerContainerDiscovery {
+    fn list_pods(&self) -> Result<Vec<PodInfo>> {
+        Ok(containers(&running_container_names()?))
+    }
+}
+
+pub struct DockerLogSpawner;
+
+impl LogStreamSpawner for DockerLogSpawner {
+    fn spawn(&self, pod: &PodInfo, opts: LogStreamSpawnOpts) {
+        let name = pod.name.clone();
+        // `docker logs` replays the container's stderr on its own stderr, so read both.
+        follow_command(pod.key(), opts, true, move || {
+            let mut cmd = Command::new("docker");
+            cmd.args(["logs", "-f", "--tail=1000", &name]);
+            cmd
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_containers() {
+        let regexes = vec![Regex::new("^web").unwrap(), Regex::new("db").unwrap()];
+        let containers = match_containers("web-1\nredis\n\npostgres-db\nweb-2\n", &regexes);
+        let found: Vec<(String, usize)> = containers
+            .iter()
+            .map(|c| (c.key(), c.pattern_idx))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("docker/web-1".to_string(), 0),
+                ("docker/postgres-db".to_string(), 1),
+                ("docker/web-2".to_string(), 0),
+            ]
+        );
+    }
+}
@@ -13,4 +13,10 @@ pub enum TrackEvent {
         pod: PodInfo,
         alive: Arc<AtomicBool>,
     },
+    /// The pod (or container) was deleted; its follower has stopped.
+    PodGone {
+        pod_key: String,
+    },
+    /// A message for the status bar, e.g. a pattern the startup listing never matched.
+    Notice(String),
 }
@@ -0,0 +1,358 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::fs::{File, Metadata};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use super::super::{PodDetails, PodInfo};
+use super::event::TrackEvent;
+use super::stream::should_stop;
+use super::traits::{LogStreamSpawnOpts, LogStreamSpawner, PatternToRegex, PodDiscovery};
+
+/// Pane namespace used for files, so pane keys read `file/<path>`.
+pub const FILE_NAMESPACE: &str = "file";
+
+/// How much of an existing file is shown when following starts, like `tail`.
+const INITIAL_TAIL_BYTES: u64 = 256 * 1024;
+
+const FILE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Every existing file matching the glob `patterns`, in pattern order, without duplicates.
+pub fn expand_paths(patterns: &[String]) -> Result<Vec<PodInfo>> {
+    let mut files: Vec<PodInfo> = Vec::new();
+    for (i, pattern) in patterns.iter().enumerate() {
+        let paths =
+            glob::glob(pattern).with_context(|| format!("Invalid path pattern {pattern:?}"))?;
+        for path in paths.filter_map(|p| p.ok()).filter(|p| p.is_file()) {
+            let name = path.to_string_lossy().into_owned();
+            if files.iter().any(|f| f.name == name) {
+                continue;
+            }
+            files.push(PodInfo {
+                namespace: FILE_NAMESPACE.to_string(),
+                name,
+                pattern_idx: i,
+                details: PodDetails::default(),
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// A regex matching the same paths as the glob `pattern`, so files created later are picked
+/// up by the poll loop.
+pub fn glob_regex(pattern: &str) -> Result<Regex> {
+    let mut re = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches no directories at all, like `glob` does
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => {
+                re.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    re.push('^');
+                }
+                for c in chars.by_ref() {
+                    re.push(c);
+                    if c == ']' {
+                        break;
+                    }
+                }
+            }
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).with_context(|| format!("Invalid path pattern {pattern:?}"))
+}
+
+/// Lists the files matching the tracked globs. Patterns added in the TUI are globbed too.
+#[derive(Default)]
+pub struct FileGlobs {
+    patterns: Mutex<Vec<String>>,
+}
+
+impl FileGlobs {
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: Mutex::new(patterns.to_vec()),
+        }
+    }
+}
+
+impl PodDiscovery for FileGlobs {
+    fn list_pods(&self) -> Result<Vec<PodInfo>> {
+        expand_paths(&self.patterns.lock().unwrap())
+    }
+}
+
+impl PatternToRegex for FileGlobs {
+    fn build(&self, pattern: &str) -> Result<Regex> {
+        let re = glob_regex(pattern)?;
+        self.patterns.lock().unwrap().push(pattern.to_string());
+        Ok(re)
+    }
+}
+
+/// Identifies the file behind an open handle, to notice when its path is rotated.
+#[cfg(unix)]
+fn file_id(meta: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Reads lines appended to a file, reopening it from the start when it's truncated or
+/// replaced (log rotation). A line is only returned once its newline is written.
+pub struct FileFollower {
+    path: PathBuf,
+    reader: Option<BufReader<File>>,
+    id: Option<(u64, u64)>,
+    pos: u64,
+    partial: Vec<u8>,
+}
+
+impl FileFollower {
+    /// Starts near the end of the file, keeping up to `tail_bytes` of existing whole lines.
+    pub fn new(path: impl Into<PathBuf>, tail_bytes: u64) -> Self {
+        let mut follower = Self {
+            path: path.into(),
+            reader: None,
+            id: None,
+            pos: 0,
+            partial: Vec::new(),
+        };
+        if follower.open().is_ok() {
+            let len = follower.pos;
+            let start = len.saturating_sub(tail_bytes);
+            if follower.seek(start).is_ok() && start > 0 {
+                // Drop the line cut off by starting mid-file.
+                let _ = follower.read_line(&mut Vec::new());
+            }
+        }
+        follower
+    }
+
+    fn open(&mut self) -> io::Result<()> {
+        let file = File::open(&self.path)?;
+        let meta = file.metadata()?;
+        self.id = file_id(&meta);
+        self.pos = meta.len();
+        self.reader = Some(BufReader::new(file));
+        self.partial.clear();
+        Ok(())
+    }
+
+    fn seek(&mut self, pos: u64) -> io::Result<()> {
+        if let Some(reader) = self.reader.as_mut() {
+            reader.seek(SeekFrom::Start(pos))?;
+            self.pos = pos;
+        }
+        Ok(())
+    }
+
+    fn read_line(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let Some(reader) = self.reader.as_mut() else {
+            return Ok(0);
+        };
+        let n = reader.read_until(b'\n', buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    /// Reopens the path from the start when it now names a different or shorter file.
+    fn reopen_if_replaced(&mut self) {
+        let Ok(meta) = std::fs::metadata(&self.path) else {
+            return;
+        };
+        let replaced = self.reader.is_none() || file_id(&meta) != self.id;
+        if (replaced || meta.len() < self.pos) && self.open().is_ok() {
+            let _ = self.seek(0);
+        }
+    }
+
+    /// Complete lines written since the last call, without their line endings.
+    pub fn read_new_lines(&mut self) -> io::Result<Vec<String>> {
+        self.reopen_if_replaced();
+        let mut lines = Vec::new();
+        loop {
+            let mut buf = std::mem::take(&mut self.partial);
+            let n = self.read_line(&mut buf)?;
+            if n == 0 || !buf.ends_with(b"\n") {
+                self.partial = buf;
+                break;
+            }
+            let line = String::from_utf8_lossy(&buf);
+            lines.push(line.trim_end_matches(['\n', '\r']).to_string());
+        }
+        Ok(lines)
+    }
+}
+
+pub struct FileLogSpawner;
+
+impl LogStreamSpawner for FileLogSpawner {
+    fn spawn(&self, pod: &PodInfo, opts: LogStreamSpawnOpts) {
+        let key = pod.key();
+        let path = pod.name.clone();
+        let LogStreamSpawnOpts {
+            filter,
+            running,
+            alive,
+            active_pods,
+            tx,
+        } = opts;
+
+        thread::spawn(move || {
+            let mut follower = FileFollower::new(path, INITIAL_TAIL_BYTES);
+            let mut trace = filter.trace_state();
+            'follow: while !should_stop(&running, &alive) {
+                for text in follower.read_new_lines().unwrap_or_default() {
+                    if !filter.keeps(&text, &mut trace) {
+                        continue;
+                    }
+                    let event = TrackEvent::LogLine {
+                        pod_key: key.clone(),
+                        text,
+                    };
+                    if tx.send(event).is_err() {
+                        break 'follow;
+                    }
+                }
+                thread::sleep(FILE_POLL_INTERVAL);
+            }
+            active_pods.lock().unwrap().remove(&key);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("bashers_tail_{}_{}", name, nanos))
+    }
+
+    fn append(path: &PathBuf, text: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_follower_reads_appended_lines() {
+        let path = temp_path("append");
+        append(&path, "old 1\nold 2\n");
+        let mut follower = FileFollower::new(&path, INITIAL_TAIL_BYTES);
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["old 1", "old 2"]);
+        assert!(follower.read_new_lines().unwrap().is_empty());
+
+        append(&path, "new 1\nnew ");
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["new 1"]);
+        append(&path, "2\r\n");
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["new 2"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_follower_starts_at_tail_on_a_line_boundary() {
+        let path = temp_path("tail");
+        append(&path, "first line\nsecond\nthird\n");
+        let mut follower = FileFollower::new(&path, 10);
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["third"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_follower_reopens_after_truncation_and_rotation() {
+        let path = temp_path("rotate");
+        append(&path, "before\n");
+        let mut follower = FileFollower::new(&path, INITIAL_TAIL_BYTES);
+        follower.read_new_lines().unwrap();
+
+        std::fs::write(&path, "").unwrap();
+        assert!(follower.read_new_lines().unwrap().is_empty());
+        append(&path, "after truncate\n");
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["after truncate"]);
+
+        let rotated = path.with_extension("1");
+        std::fs::rename(&path, &rotated).unwrap();
+        append(&path, "rotated in\n");
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["rotated in"]);
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&rotated).unwrap();
+    }
+
+    #[test]
+    fn test_follower_waits_for_missing_file() {
+        let path = temp_path("missing");
+        let mut follower = FileFollower::new(&path, INITIAL_TAIL_BYTES);
+        assert!(follower.read_new_lines().unwrap().is_empty());
+        append(&path, "created\n");
+        assert_eq!(follower.read_new_lines().unwrap(), vec!["created"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_glob_regex() {
+        let re = glob_regex("logs/*.log").unwrap();
+        assert!(re.is_match("logs/app.log"));
+        assert!(!re.is_match("logs/old/app.log"));
+        assert!(!re.is_match("logs/app.log.1"));
+        let re = glob_regex("logs/**/*.log").unwrap();
+        assert!(re.is_match("logs/a/b/c.log"));
+        assert!(re.is_match("logs/app.log"));
+        assert!(!re.is_match("logsapp.log"));
+        assert!(glob_regex("app-[!0].log").unwrap().is_match("app-1.log"));
+        assert!(!glob_regex("app-[!0].log").unwrap().is_match("app-0.log"));
+        assert!(glob_regex("a+b?.txt").unwrap().is_match("a+b1.txt"));
+    }
+
+    #[test]
+    fn test_expand_paths_dedupes_across_patterns() {
+        let dir = temp_path("expand");
+        std::fs::create_dir_all(&dir).unwrap();
+        append(&dir.join("a.log"), "");
+        append(&dir.join("b.txt"), "");
+        let dir_str = dir.to_string_lossy();
+        let files = expand_paths(&[format!("{dir_str}/*.log"), format!("{dir_str}/*")]).unwrap();
+        let found: Vec<(String, usize)> = files
+            .iter()
+            .map(|f| (f.name.replace(&*dir_str, ""), f.pattern_idx))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("/a.log".to_string(), 0), ("/b.txt".to_string(), 1)]
+        );
+        assert_eq!(files[0].key(), format!("file/{dir_str}/a.log"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
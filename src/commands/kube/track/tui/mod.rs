@@ -1,39 +1,62 @@
+#[cfg(feature = "bench")]
+pub mod bench;
+mod cache;
+pub mod docker;
 mod event;
+pub mod file;
 mod kube;
 mod shared;
 mod state;
-mod theme;
+mod stream;
+pub mod theme;
 mod traits;
 
-use ansi_to_tui::IntoText;
 use anyhow::Result;
 use crossterm::event::{Event, KeyEventKind};
 use ratatui::{
     layout::{Constraint, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+    widgets::{
+        Block, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, Wrap,
+    },
     Frame,
 };
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{mpsc, Arc};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use super::PodInfo;
+use super::{
+    is_error_line, match_pods, print_warning, render_json_line, unmatched_messages, Discovered,
+    InitialView, KubeTarget, LogFilter, PendingPods, PodInfo, TuiOptions,
+};
+use cache::CachedDiscovery;
 use event::TrackEvent;
 use regex::Regex;
 use shared::SharedState;
-use state::{KeyAction, PodPane, TuiState};
+use state::{escape_control, Activity, InputKind, KeyAction, PodPane, TuiState};
 use theme::Theme;
-use traits::{LogStreamSpawnOpts, LogStreamSpawner, PodDiscovery, PatternToRegex};
+use traits::{LogStreamSpawnOpts, LogStreamSpawner, PodDiscovery, PatternToRegex, Sleeper};
+
+/// Most matches the jump picker lists at once.
+const MAX_JUMP_CANDIDATES: usize = 8;
+/// How long a status-bar notice (e.g. "saved log to ...") stays visible.
+const NOTICE_DURATION: Duration = Duration::from_secs(4);
+
+/// How long a pod listing is reused when `TuiOptions::pod_cache_ttl` is unset.
+const DEFAULT_POD_CACHE_TTL: Duration = Duration::from_secs(3);
+
+/// How often new pods are looked for when `TuiOptions::poll_interval` is unset.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 const MAIN_LAYOUT: [Constraint; 2] =
     [Constraint::Min(0), Constraint::Length(1)];
 
 fn spawn_opts(shared: &SharedState, alive: Arc<AtomicBool>) -> LogStreamSpawnOpts {
     LogStreamSpawnOpts {
-        err_only: shared.err_only,
+        filter: shared.filter.clone(),
         running: shared.running.clone(),
         alive,
         active_pods: shared.active_pods.clone(),
@@ -75,26 +98,53 @@ fn key_hint(key: &str, desc: &str, color: Color) -> Vec<Span<'static>> {
     ]
 }
 
-fn ratio_chunks(area: Rect, count: usize) -> Vec<Rect> {
-    let n = count.max(1) as u32;
-    let constraints: Vec<Constraint> = (0..count).map(|_| Constraint::Ratio(1, n)).collect();
-    Layout::vertical(constraints).split(area).to_vec()
+/// Each pane's share of the height, in proportion to its weight.
+fn weight_constraints(weights: &[u32]) -> Vec<Constraint> {
+    let total = weights.iter().sum::<u32>().max(1);
+    weights
+        .iter()
+        .map(|&weight| Constraint::Ratio(weight, total))
+        .collect()
+}
+
+fn ratio_chunks(area: Rect, weights: &[u32]) -> Vec<Rect> {
+    Layout::vertical(weight_constraints(weights))
+        .split(area)
+        .to_vec()
+}
+
+/// The TUI's pluggable collaborators; tests swap in fakes.
+pub struct TrackDeps {
+    pub discovery: Arc<dyn PodDiscovery>,
+    pub spawner: Arc<dyn LogStreamSpawner>,
+    pub pattern_to_regex: Arc<dyn PatternToRegex>,
+    pub sleeper: Arc<dyn Sleeper>,
+}
+
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
 }
 
 fn start_poll_loop(
     shared: Arc<SharedState>,
     discovery: Arc<dyn PodDiscovery>,
     spawner: Arc<dyn LogStreamSpawner>,
+    sleeper: Arc<dyn Sleeper>,
+    interval: Duration,
 ) {
     thread::spawn(move || {
         while shared.running.load(Ordering::SeqCst) {
-            thread::sleep(Duration::from_secs(5));
+            sleeper.sleep(interval);
             if !shared.running.load(Ordering::SeqCst) {
                 break;
             }
             let current_regexes = shared.clone_regexes();
-            if let Ok(new_pods) = discovery.find_matching_pods(&current_regexes) {
-                try_spawn_pods(&shared, new_pods, &spawner);
+            if let Ok(pods) = discovery.refresh_pods() {
+                try_spawn_pods(&shared, match_pods(pods, &current_regexes), &spawner);
             }
         }
     });
@@ -111,6 +161,7 @@ pub struct TrackTui {
     pattern_to_regex: Arc<dyn PatternToRegex>,
     layout_available_height: u16,
     layout_page_size: usize,
+    notice: Option<(String, Instant)>,
 }
 
 impl TrackTui {
@@ -118,13 +169,24 @@ impl TrackTui {
         pods: Vec<PodInfo>,
         shared: SharedState,
         rx: mpsc::Receiver<TrackEvent>,
-        discovery: Arc<dyn PodDiscovery>,
-        spawner: Arc<dyn LogStreamSpawner>,
-        pattern_to_regex: Arc<dyn PatternToRegex>,
+        deps: TrackDeps,
+        poll_interval: Duration,
         theme: Theme,
     ) -> Self {
+        let TrackDeps {
+            discovery,
+            spawner,
+            pattern_to_regex,
+            sleeper,
+        } = deps;
         let shared = Arc::new(shared);
-        start_poll_loop(shared.clone(), discovery.clone(), spawner.clone());
+        start_poll_loop(
+            shared.clone(),
+            discovery.clone(),
+            spawner.clone(),
+            sleeper,
+            poll_interval,
+        );
 
         let color_counter = Arc::new(AtomicUsize::new(0));
         let mut state = TuiState::new();
@@ -134,7 +196,9 @@ impl TrackTui {
             let cidx = color_counter.fetch_add(1, Ordering::SeqCst);
             let color = theme.pane_color(cidx);
             let alive = Arc::new(AtomicBool::new(true));
-            state.add_pane(PodPane::new(key.clone(), color, alive.clone()));
+            let mut pane = PodPane::new(key.clone(), color, alive.clone());
+            pane.node = pod.details.node.clone();
+            state.add_pane(pane);
             shared.active_pods.lock().unwrap().insert(key.clone());
             spawner.spawn(pod, spawn_opts(&shared, alive));
         }
@@ -150,11 +214,88 @@ impl TrackTui {
             pattern_to_regex,
             layout_available_height: 0,
             layout_page_size: 0,
+            notice: None,
         }
     }
 
-    fn add_pattern(&self, pattern: &str) {
-        let new_regex = self.pattern_to_regex.build(pattern);
+    /// Adds panes for the rest of the startup listing as it arrives.
+    /// `matched` holds the pattern indexes the initial panes already matched.
+    fn follow_pending(&self, pending: PendingPods, mut matched: HashSet<usize>) {
+        let shared = self.shared.clone();
+        let spawner = self.spawner.clone();
+        thread::spawn(move || {
+            for message in pending.rx {
+                if !shared.running.load(Ordering::SeqCst) {
+                    break;
+                }
+                match message {
+                    Discovered::Pod(pod) => {
+                        matched.insert(pod.pattern_idx);
+                        try_spawn_pods(&shared, vec![pod], &spawner);
+                    }
+                    Discovered::Done(Err(e)) => {
+                        let _ = shared.tx.send(TrackEvent::Notice(format!("{:#}", e)));
+                    }
+                    Discovered::Done(Ok(())) => {
+                        let unmatched = unmatched_messages(&pending.patterns, &matched);
+                        if !unmatched.is_empty() {
+                            let _ = shared.tx.send(TrackEvent::Notice(unmatched.join("; ")));
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn apply_options(&mut self, options: &TuiOptions) {
+        self.state.nav_mode = options.nav_mode;
+        self.state.follow_errors = options.follow_errors;
+        self.state.raw = options.raw;
+        if let Some(height) = options.min_pane_height {
+            self.state.min_pane_height = height;
+        }
+        self.state.single_tab = options.single_tab;
+        self.apply_initial_view(&options.initial_view);
+    }
+
+    fn apply_initial_view(&mut self, view: &InitialView) {
+        let available_height = crossterm::terminal::size()
+            .map(|(_, rows)| rows.saturating_sub(1))
+            .unwrap_or(0);
+        let warnings = self.state.apply_initial_view(view, available_height);
+        if !warnings.is_empty() {
+            let use_color = crate::utils::colors::use_color(atty::Stream::Stderr);
+            for warning in &warnings {
+                print_warning(warning, use_color);
+            }
+            thread::sleep(Duration::from_secs(2));
+        }
+    }
+
+    fn save_log(&mut self, idx: usize) {
+        let Some(pane) = self.state.panes.get(idx) else {
+            return;
+        };
+        let unix_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = std::path::PathBuf::from(pane.log_file_name(unix_secs));
+        let message = match std::fs::write(&path, pane.plain_text()) {
+            Ok(()) => format!("saved {}", path.display()),
+            Err(e) => format!("failed to save {}: {}", path.display(), e),
+        };
+        self.notice = Some((message, Instant::now()));
+    }
+
+    fn add_pattern(&mut self, pattern: &str) {
+        let new_regex = match self.pattern_to_regex.build(pattern) {
+            Ok(re) => re,
+            Err(e) => {
+                self.notice = Some((e.to_string(), Instant::now()));
+                return;
+            }
+        };
         self.shared.add_regex(new_regex.clone());
 
         let shared = self.shared.clone();
@@ -162,9 +303,9 @@ impl TrackTui {
         let spawner = self.spawner.clone();
 
         thread::spawn(move || {
-            if let Ok(pods) = discovery.find_matching_pods(&[new_regex]) {
-                try_spawn_pods(&shared, pods, &spawner);
-            }
+            let _ = discovery.stream_matching_pods(&[new_regex], &mut |pod| {
+                try_spawn_pods(&shared, vec![pod], &spawner);
+            });
         });
     }
 
@@ -173,25 +314,82 @@ impl TrackTui {
             match evt {
                 TrackEvent::LogLine { pod_key, text } => {
                     if let Some(&idx) = self.state.pane_index.get(&pod_key) {
-                        self.state.panes[idx].push_line(text);
+                        let is_error = is_error_line(&text);
+                        let pane = &mut self.state.panes[idx];
+                        pane.mark_unseen(is_error);
+                        if is_error {
+                            pane.error_count += 1;
+                        }
+                        let lines = match self
+                            .state
+                            .json_logs
+                            .then(|| render_json_line(&text, true))
+                            .flatten()
+                        {
+                            Some(rendered) => rendered,
+                            None => vec![text],
+                        };
+                        for line in &lines {
+                            pane.push_line(line.clone());
+                        }
+                        for line in lines {
+                            self.state.push_merged(&pod_key, line);
+                        }
+                        if is_error {
+                            self.state.follow_error(
+                                idx,
+                                Instant::now(),
+                                self.layout_available_height,
+                            );
+                        }
+                        self.state.auto_focus_on(
+                            idx,
+                            Instant::now(),
+                            self.layout_available_height,
+                        );
                     }
                 }
                 TrackEvent::NewPod { pod, alive } => {
                     let key = pod.key();
-                    if !self.state.pane_index.contains_key(&key) {
+                    if let Some(&idx) = self.state.pane_index.get(&key) {
+                        // A new pod took the name of one that was deleted.
+                        let pane = &mut self.state.panes[idx];
+                        pane.alive = alive;
+                        pane.gone = false;
+                        pane.node = pod.details.node;
+                    } else {
                         let cidx = self.color_counter.fetch_add(1, Ordering::SeqCst);
                         let color = self.theme.pane_color(cidx);
-                        self.state.add_pane(PodPane::new(key, color, alive));
+                        let mut pane = PodPane::new(key, color, alive);
+                        pane.node = pod.details.node;
+                        self.state.add_pane(pane);
+                    }
+                }
+                TrackEvent::PodGone { pod_key } => {
+                    if let Some(&idx) = self.state.pane_index.get(&pod_key) {
+                        self.state.panes[idx].gone = true;
                     }
                 }
+                TrackEvent::Notice(message) => {
+                    self.notice = Some((message, Instant::now()));
+                }
             }
         }
     }
 
     fn update_pane_rects(&mut self, term_size: Rect, available_height: u16) {
         let main_layout = Layout::vertical(MAIN_LAYOUT).split(term_size);
+        self.state.merged_rect = main_layout[0];
+        if self.state.merged_view {
+            self.state.pane_rects.clear();
+            return;
+        }
         let visible_indices = self.state.visible_indices(available_height);
-        let chunks = ratio_chunks(main_layout[0], visible_indices.len());
+        let weights: Vec<u32> = visible_indices
+            .iter()
+            .map(|&i| self.state.pane_weight(i))
+            .collect();
+        let chunks = ratio_chunks(main_layout[0], &weights);
         self.state.pane_rects = visible_indices
             .iter()
             .zip(chunks.iter())
@@ -199,16 +397,270 @@ impl TrackTui {
             .collect();
     }
 
+    fn status_line(&self, total_tabs: usize) -> Line<'_> {
+        if self.state.input_mode && self.state.input_kind == InputKind::JumpToPod {
+            let mut spans = vec![
+                Span::styled(" Jump to: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(self.state.input_buffer.as_str()),
+                Span::styled("\u{2588}", Style::default().fg(Color::White)),
+                Span::raw("  "),
+            ];
+            spans.extend(key_hint("\u{2191}\u{2193}", "choose", Color::LightCyan));
+            spans.extend(key_hint("Enter", "jump", Color::Green));
+            spans.extend(key_hint("Esc", "cancel", Color::Red));
+            Line::from(spans)
+        } else if self.state.input_mode {
+            let mut spans = vec![
+                Span::styled(
+                    " Pattern: ",
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(self.state.input_buffer.as_str()),
+                Span::styled("\u{2588}", Style::default().fg(Color::White)),
+                Span::raw("  "),
+            ];
+            spans.extend(key_hint("Enter", "add", Color::Green));
+            spans.extend(key_hint("Esc", "cancel", Color::Red));
+            Line::from(spans)
+        } else if self.state.dashboard_open {
+            let mut spans = vec![Span::styled(
+                " Errors by pod  ",
+                Style::default().add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(key_hint("\u{2191}\u{2193}", "select", Color::LightCyan));
+            spans.extend(key_hint("Enter", "jump", Color::Green));
+            spans.extend(key_hint(
+                "o",
+                &format!("sort: {}", self.state.dashboard_sort.label()),
+                Color::LightCyan,
+            ));
+            spans.extend(key_hint("Esc", "close", Color::Red));
+            Line::from(spans)
+        } else {
+            let mut spans = vec![Span::raw(" ")];
+
+            if let Some((message, at)) = &self.notice {
+                if at.elapsed() < NOTICE_DURATION {
+                    spans.push(Span::styled(
+                        format!("{message}  "),
+                        Style::default()
+                            .fg(Color::LightGreen)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+            }
+
+            if total_tabs > 1 {
+                spans.push(Span::styled(
+                    format!("[{}/{}]", self.state.current_tab + 1, total_tabs),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::raw(" "));
+                for (tab, activity) in self
+                    .state
+                    .tab_activity(self.layout_available_height)
+                    .into_iter()
+                    .enumerate()
+                {
+                    if tab == self.state.current_tab {
+                        continue;
+                    }
+                    let color = match activity {
+                        Activity::None => continue,
+                        Activity::Lines => Color::LightYellow,
+                        Activity::Errors => Color::LightRed,
+                    };
+                    spans.push(Span::styled(
+                        format!("\u{25cf}{} ", tab + 1),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                spans.push(Span::raw(" "));
+                if !self.state.expanded {
+                    spans.extend(key_hint("\u{2190}\u{2192}", "tabs", Color::LightCyan));
+                }
+            }
+            if self.state.expanded {
+                spans.extend(key_hint("\u{2190}\u{2192}", "cycle tab", Color::LightCyan));
+            }
+            if self.state.pans_horizontally(self.layout_available_height) {
+                spans.extend(key_hint("\u{2190}\u{2192}", "pan", Color::LightCyan));
+            }
+            spans.extend(key_hint("Tab", "switch", Color::LightCyan));
+            spans.extend(key_hint("\u{2191}\u{2193}", "scroll", Color::LightCyan));
+            spans.extend(key_hint("End", "follow", Color::LightCyan));
+            spans.extend(key_hint(
+                "f",
+                if self.state.expanded { "collapse" } else { "expand" },
+                Color::LightCyan,
+            ));
+            spans.extend(key_hint(
+                "e",
+                if self.state.follow_errors {
+                    "errors: follow"
+                } else {
+                    "errors: stay"
+                },
+                Color::LightCyan,
+            ));
+            spans.extend(key_hint(
+                "o",
+                if self.state.auto_focus {
+                    "focus: newest"
+                } else {
+                    "focus: manual"
+                },
+                Color::LightCyan,
+            ));
+            spans.extend(key_hint("E", "error dashboard", Color::LightCyan));
+            spans.extend(key_hint(":", "jump to pod", Color::LightCyan));
+            spans.extend(key_hint(
+                "g",
+                if self.state.merged_view {
+                    "panes"
+                } else {
+                    "merged"
+                },
+                Color::LightCyan,
+            ));
+            spans.extend(key_hint(
+                "J",
+                if self.state.json_logs {
+                    "json: pretty"
+                } else {
+                    "json: raw"
+                },
+                Color::LightCyan,
+            ));
+            let wrap = self
+                .state
+                .panes
+                .get(self.state.selected)
+                .map_or(self.state.wrap_new_panes, |pane| pane.wrap);
+            spans.extend(key_hint(
+                "w",
+                if wrap { "wrap: on" } else { "wrap: off" },
+                Color::LightCyan,
+            ));
+            if self.state.panes.iter().any(|pane| pane.node.is_some()) {
+                spans.extend(key_hint(
+                    "n",
+                    if self.state.show_node {
+                        "node: shown"
+                    } else {
+                        "node: hidden"
+                    },
+                    Color::LightCyan,
+                ));
+            }
+            spans.extend(key_hint("s", "save log", Color::LightGreen));
+            spans.extend(key_hint("y", "copy name", Color::LightGreen));
+            spans.extend(key_hint("a /", "add pod", Color::LightGreen));
+            spans.extend(key_hint("d", "close pane", Color::LightRed));
+            spans.extend(key_hint("D", "close tab", Color::LightRed));
+            spans.extend(key_hint(
+                "m",
+                if self.state.mouse_captured {
+                    "select text"
+                } else {
+                    "mouse mode"
+                },
+                Color::LightCyan,
+            ));
+            if !self.state.mouse_captured {
+                spans.extend(key_hint("Ctrl+\u{2191}\u{2193}", "scroll", Color::LightCyan));
+            }
+            spans.extend(key_hint("q", "quit", Color::LightYellow));
+
+            Line::from(spans)
+        }
+    }
+
+    fn render_merged(&self, frame: &mut Frame, area: Rect) {
+        let merged = &self.state.merged;
+        let title = if merged.is_following() {
+            format!(" merged: {} pods ", self.state.panes.len())
+        } else {
+            format!(" merged: {} pods [SCROLLED] ", self.state.panes.len())
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::White));
+
+        let inner_height = area.height.saturating_sub(2) as usize;
+        let scroll_offset = merged.scroll_offset(inner_height) as usize;
+        let lines: Vec<Line> = merged
+            .lines
+            .iter()
+            .zip(&self.state.merged_sources)
+            .skip(scroll_offset)
+            .zip(merged.styled_lines(scroll_offset, inner_height))
+            .map(|((text, key), styled)| {
+                let color = self
+                    .state
+                    .pane_index
+                    .get(key)
+                    .map_or(Color::DarkGray, |&idx| self.state.panes[idx].color);
+                let body = if self.state.raw {
+                    Line::raw(escape_control(text))
+                } else {
+                    styled
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        key.as_str(),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" "),
+                ];
+                spans.extend(body.spans);
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+            area,
+        );
+
+        if merged.lines.len() > inner_height {
+            let max_scroll = merged.lines.len().saturating_sub(inner_height);
+            let mut scrollbar_state =
+                ScrollbarState::new(max_scroll).position(scroll_offset.min(max_scroll));
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .style(Style::default().fg(Color::White)),
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut scrollbar_state,
+            );
+        }
+    }
+
     fn render_frame(&self, frame: &mut Frame, total_tabs: usize, available_height: u16) {
         let main_chunks = Layout::vertical(MAIN_LAYOUT).split(frame.area());
 
-        if !self.state.panes.is_empty() {
+        if self.state.merged_view {
+            self.render_merged(frame, main_chunks[0]);
+        } else if !self.state.panes.is_empty() {
             let visible_indices = self.state.visible_indices(available_height);
             let visible: Vec<(usize, &PodPane)> = visible_indices
                 .iter()
                 .filter_map(|&i| self.state.panes.get(i).map(|p| (i, p)))
                 .collect();
-            let chunks = ratio_chunks(main_chunks[0], visible.len());
+            let weights: Vec<u32> = visible
+                .iter()
+                .map(|(i, _)| self.state.pane_weight(*i))
+                .collect();
+            let chunks = ratio_chunks(main_chunks[0], &weights);
 
             for (ci, (i, pane)) in visible.iter().enumerate() {
                 let is_selected = *i == self.state.selected;
@@ -222,14 +674,18 @@ impl TrackTui {
                         .add_modifier(Modifier::DIM)
                 };
 
+                let label = pane.title_label(self.state.show_node);
+                let gone = if pane.gone { " [GONE]" } else { "" };
                 let title = if pane.is_following() {
-                    format!(" {} ", pane.key)
+                    format!(" {}{} ", label, gone)
                 } else {
-                    format!(" {} [SCROLLED] ", pane.key)
+                    format!(" {}{} [SCROLLED] ", label, gone)
                 };
 
                 let title_color = self.theme.title_color(*i);
-                let title_style = if !pane.is_following() {
+                let title_style = if pane.gone {
+                    Style::default().fg(title_color).add_modifier(Modifier::DIM)
+                } else if !pane.is_following() {
                     Style::default()
                         .fg(Color::Rgb(0xff, 0xcc, 0x00))
                         .add_modifier(Modifier::BOLD)
@@ -247,29 +703,34 @@ impl TrackTui {
                 let inner_height = chunks[ci].height.saturating_sub(2) as usize;
                 let scroll_offset = pane.scroll_offset(inner_height) as usize;
 
-                let visible_end = (scroll_offset + inner_height).min(pane.lines.len());
-                let visible_slice: String = pane
-                    .lines
-                    .iter()
-                    .skip(scroll_offset)
-                    .take(visible_end - scroll_offset)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                let text = visible_slice
-                    .as_bytes()
-                    .into_text()
-                    .unwrap_or_else(|_| Text::raw(&visible_slice));
-                let paragraph = Paragraph::new(text)
-                    .block(block)
-                    .wrap(Wrap { trim: true });
+                let text = if self.state.raw {
+                    let escaped: Vec<String> = pane
+                        .lines
+                        .iter()
+                        .skip(scroll_offset)
+                        .take(inner_height)
+                        .map(|l| escape_control(l))
+                        .collect();
+                    Text::raw(escaped.join("\n"))
+                } else {
+                    Text::from(
+                        pane.styled_lines(scroll_offset, inner_height)
+                            .collect::<Vec<_>>(),
+                    )
+                };
+                let paragraph = Paragraph::new(text).block(block);
+                let paragraph = if pane.wrap {
+                    paragraph.wrap(Wrap { trim: true })
+                } else {
+                    paragraph.scroll((0, pane.h_scroll))
+                };
 
                 frame.render_widget(paragraph, chunks[ci]);
 
                 if pane.lines.len() > inner_height {
                     let max_scroll = pane.lines.len().saturating_sub(inner_height);
                     let mut scrollbar_state =
-                        ScrollbarState::new(max_scroll).position(scroll_offset);
+                        ScrollbarState::new(max_scroll).position(scroll_offset.min(max_scroll));
                     let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                         .style(Style::default().fg(if is_selected {
                             pane.color
@@ -288,59 +749,14 @@ impl TrackTui {
             }
         }
 
-        let status_line = if self.state.input_mode {
-            let mut spans = vec![
-                Span::styled(
-                    " Pattern: ",
-                    Style::default().add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(self.state.input_buffer.as_str()),
-                Span::styled("\u{2588}", Style::default().fg(Color::White)),
-                Span::raw("  "),
-            ];
-            spans.extend(key_hint("Enter", "add", Color::Green));
-            spans.extend(key_hint("Esc", "cancel", Color::Red));
-            Line::from(spans)
-        } else {
-            let mut spans = vec![Span::raw(" ")];
-
-            if total_tabs > 1 {
-                spans.push(Span::styled(
-                    format!("[{}/{}]", self.state.current_tab + 1, total_tabs),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ));
-                spans.push(Span::raw("  "));
-                spans.extend(key_hint("\u{2190}\u{2192}", "tabs", Color::LightCyan));
-            }
-            spans.extend(key_hint("Tab", "switch", Color::LightCyan));
-            spans.extend(key_hint("\u{2191}\u{2193}", "scroll", Color::LightCyan));
-            spans.extend(key_hint("End", "follow", Color::LightCyan));
-            spans.extend(key_hint(
-                "f",
-                if self.state.expanded { "collapse" } else { "expand" },
-                Color::LightCyan,
-            ));
-            spans.extend(key_hint("a", "add pod", Color::LightGreen));
-            spans.extend(key_hint("d", "close pane", Color::LightRed));
-            spans.extend(key_hint("D", "close tab", Color::LightRed));
-            spans.extend(key_hint(
-                "m",
-                if self.state.mouse_captured {
-                    "select text"
-                } else {
-                    "mouse mode"
-                },
-                Color::LightCyan,
-            ));
-            if !self.state.mouse_captured {
-                spans.extend(key_hint("Ctrl+\u{2191}\u{2193}", "scroll", Color::LightCyan));
-            }
-            spans.extend(key_hint("q", "quit", Color::LightYellow));
+        if self.state.dashboard_open {
+            self.render_dashboard(frame, main_chunks[0]);
+        }
+        if self.state.input_mode && self.state.input_kind == InputKind::JumpToPod {
+            self.render_jump_candidates(frame, main_chunks[0]);
+        }
 
-            Line::from(spans)
-        };
+        let status_line = self.status_line(total_tabs);
 
         frame.render_widget(
             Paragraph::new(status_line).style(Style::default().bg(Color::Rgb(30, 30, 30))),
@@ -349,15 +765,89 @@ impl TrackTui {
     }
 }
 
+impl TrackTui {
+    /// The jump picker's matches, in a box just above the status line.
+    fn render_jump_candidates(&self, frame: &mut Frame, area: Rect) {
+        let candidates = self.state.jump_candidates();
+        let height = (candidates.len().min(MAX_JUMP_CANDIDATES) as u16 + 2).min(area.height);
+        let overlay = Rect::new(area.x, area.y + area.height - height, area.width, height);
+        // Keep the highlighted candidate inside the window.
+        let skip = (self.state.jump_choice + 1).saturating_sub(MAX_JUMP_CANDIDATES);
+        let lines: Vec<Line> = candidates
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(MAX_JUMP_CANDIDATES)
+            .map(|(row, key)| {
+                let style = if row == self.state.jump_choice {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                Line::styled(key.clone(), style)
+            })
+            .collect();
+        let title = format!(" {} of {} pods ", candidates.len(), self.state.panes.len());
+        frame.render_widget(Clear, overlay);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(title)),
+            overlay,
+        );
+    }
+
+    /// The error dashboard: a table of panes by error count, drawn over the centre of `area`.
+    fn render_dashboard(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.state.dashboard_rows();
+        let height = (rows.len() as u16 + 3).min(area.height);
+        let width = (area.width * 3 / 4).max(40).min(area.width);
+        let overlay = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let table_rows = rows.iter().enumerate().map(|(row, &idx)| {
+            let pane = &self.state.panes[idx];
+            let style = if row == self.state.dashboard_row {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else if pane.error_count > 0 {
+                Style::default().fg(Color::LightRed)
+            } else {
+                Style::default()
+            };
+            Row::new([pane.error_count.to_string(), pane.key.clone()]).style(style)
+        });
+        let table = Table::new(table_rows, [Constraint::Length(8), Constraint::Min(0)])
+            .header(
+                Row::new(["Errors", "Pod"]).style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::bordered().title(format!(
+                " Errors by pod (sorted by {}) ",
+                self.state.dashboard_sort.label()
+            )));
+        frame.render_widget(Clear, overlay);
+        frame.render_widget(table, overlay);
+    }
+}
+
 impl crate::tui::TuiApp for TrackTui {
     fn update_layout(&mut self, term_size: Rect, available_height: u16) {
         self.layout_available_height = available_height;
         self.update_pane_rects(term_size, available_height);
-        let per_tab = self.state.max_panes_per_tab(available_height);
-        let tab_start = self.state.current_tab * per_tab;
-        let tab_end = (tab_start + per_tab).min(self.state.panes.len());
-        let visible_count = tab_end.saturating_sub(tab_start).max(1);
-        let pane_h = available_height / visible_count as u16;
+        self.state.mark_visible_seen(available_height);
+        let visible_count = if self.state.single_tab {
+            self.state.visible_indices(available_height).len().max(1)
+        } else {
+            let per_tab = self.state.max_panes_per_tab(available_height);
+            let tab_start = self.state.current_tab * per_tab;
+            let tab_end = (tab_start + per_tab).min(self.state.panes.len());
+            tab_end.saturating_sub(tab_start).max(1)
+        };
+        let pane_h = if self.state.merged_view {
+            available_height
+        } else {
+            available_height / visible_count as u16
+        };
         self.layout_page_size = pane_h.saturating_sub(2) as usize;
     }
 
@@ -375,13 +865,21 @@ impl crate::tui::TuiApp for TrackTui {
     }
 
     fn handle_event(&mut self, event: Event) -> Result<bool> {
+        if matches!(event, Event::Key(_) | Event::Mouse(_)) {
+            self.state.last_user_input = Some(Instant::now());
+        }
         match event {
             Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
                 if self.state.input_mode {
-                    let action = self.state.handle_input_key(key_event.code);
+                    let action = self
+                        .state
+                        .handle_input_key(key_event.code, self.layout_available_height);
                     if let KeyAction::AddPattern(pattern) = action {
                         self.add_pattern(&pattern);
                     }
+                } else if self.state.dashboard_open {
+                    self.state
+                        .handle_dashboard_key(key_event.code, self.layout_available_height);
                 } else {
                     let action = self.state.handle_key(
                         key_event.code,
@@ -391,8 +889,17 @@ impl crate::tui::TuiApp for TrackTui {
                         &self.shared.running,
                         &self.shared.closed_pods,
                     );
-                    if let KeyAction::Quit = action {
-                        return Ok(true);
+                    match action {
+                        KeyAction::Quit => return Ok(true),
+                        KeyAction::SaveLog(idx) => self.save_log(idx),
+                        KeyAction::Copy(text) => {
+                            let message = match crate::tui::copy_to_clipboard(&text) {
+                                Ok(()) => format!("copied {}", text),
+                                Err(e) => format!("copy failed: {}", e),
+                            };
+                            self.notice = Some((message, Instant::now()));
+                        }
+                        _ => {}
                     }
                 }
             }
@@ -405,36 +912,591 @@ impl crate::tui::TuiApp for TrackTui {
     }
 }
 
-pub fn run(pods: Vec<PodInfo>, regexes: Vec<Regex>, err_only: bool) -> Result<()> {
-    run_with(
+pub fn run(
+    pods: Vec<PodInfo>,
+    regexes: Vec<Regex>,
+    filter: LogFilter,
+    json_logs: bool,
+    target: KubeTarget,
+    options: TuiOptions,
+    pending: Option<PendingPods>,
+) -> Result<()> {
+    let matched = pods.iter().map(|pod| pod.pattern_idx).collect();
+    let mut app = build_with(
         pods,
         regexes,
-        err_only,
-        Arc::new(kube::KubePodDiscovery),
-        Arc::new(kube::KubectlLogSpawner),
-        Arc::new(kube::KubePatternToRegex),
-        Theme::default(),
-    )
+        filter,
+        TrackDeps {
+            discovery: Arc::new(CachedDiscovery::new(
+                Arc::new(kube::KubePodDiscovery {
+                    target: target.clone(),
+                }),
+                options.pod_cache_ttl.unwrap_or(DEFAULT_POD_CACHE_TTL),
+            )),
+            spawner: Arc::new(kube::KubectlLogSpawner { target }),
+            pattern_to_regex: Arc::new(kube::KubePatternToRegex),
+            sleeper: Arc::new(ThreadSleeper),
+        },
+        options.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+        options.theme.clone(),
+    );
+    app.state.json_logs = json_logs;
+    app.apply_options(&options);
+    if let Some(pending) = pending {
+        app.follow_pending(pending, matched);
+    }
+    crate::tui::run(app)
 }
 
-pub fn run_with(
-    pods: Vec<PodInfo>,
-    initial_regexes: Vec<Regex>,
-    err_only: bool,
-    discovery: Arc<dyn PodDiscovery>,
-    spawner: Arc<dyn LogStreamSpawner>,
-    pattern_to_regex: Arc<dyn PatternToRegex>,
-    theme: Theme,
+/// Follows `docker logs` for each container in the same multi-pane TUI as pods.
+pub fn run_docker(
+    containers: Vec<PodInfo>,
+    regexes: Vec<Regex>,
+    filter: LogFilter,
+    options: TuiOptions,
 ) -> Result<()> {
-    let (shared, rx) = SharedState::new(err_only, initial_regexes);
-    let app = TrackTui::with_deps(
-        pods,
-        shared,
-        rx,
-        discovery,
-        spawner,
-        pattern_to_regex,
-        theme,
+    let mut app = build_with(
+        containers,
+        regexes,
+        filter,
+        TrackDeps {
+            discovery: Arc::new(CachedDiscovery::new(
+                Arc::new(docker::DockerContainerDiscovery),
+                options.pod_cache_ttl.unwrap_or(DEFAULT_POD_CACHE_TTL),
+            )),
+            spawner: Arc::new(docker::DockerLogSpawner),
+            pattern_to_regex: Arc::new(kube::KubePatternToRegex),
+            sleeper: Arc::new(ThreadSleeper),
+        },
+        options.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+        options.theme.clone(),
     );
+    app.apply_options(&options);
     crate::tui::run(app)
 }
+
+/// Follows local files in the same multi-pane TUI, picking up new files matching `globs`.
+pub fn run_files(
+    files: Vec<PodInfo>,
+    globs: &[String],
+    filter: LogFilter,
+    options: TuiOptions,
+) -> Result<()> {
+    let regexes = globs
+        .iter()
+        .map(|g| file::glob_regex(g))
+        .collect::<Result<Vec<Regex>>>()?;
+    let file_globs = Arc::new(file::FileGlobs::new(globs));
+    let mut app = build_with(
+        files,
+        regexes,
+        filter,
+        TrackDeps {
+            discovery: file_globs.clone(),
+            spawner: Arc::new(file::FileLogSpawner),
+            pattern_to_regex: file_globs,
+            sleeper: Arc::new(ThreadSleeper),
+        },
+        options.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+        options.theme.clone(),
+    );
+    app.apply_options(&options);
+    crate::tui::run(app)
+}
+
+pub fn build_with(
+    pods: Vec<PodInfo>,
+    initial_regexes: Vec<Regex>,
+    filter: LogFilter,
+    deps: TrackDeps,
+    poll_interval: Duration,
+    theme: Theme,
+) -> TrackTui {
+    let (shared, rx) = SharedState::new(filter, initial_regexes);
+    TrackTui::with_deps(pods, shared, rx, deps, poll_interval, theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::TuiApp;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    struct NoDiscovery;
+
+    impl PodDiscovery for NoDiscovery {
+        fn list_pods(&self) -> Result<Vec<PodInfo>> {
+            Ok(vec![])
+        }
+    }
+
+    struct NoSpawner;
+
+    impl LogStreamSpawner for NoSpawner {
+        fn spawn(&self, _pod: &PodInfo, _opts: LogStreamSpawnOpts) {}
+    }
+
+    struct LiteralRegex;
+
+    impl PatternToRegex for LiteralRegex {
+        fn build(&self, pattern: &str) -> Result<Regex> {
+            Ok(Regex::new(&regex::escape(pattern))?)
+        }
+    }
+
+    fn test_app(names: &[&str]) -> TrackTui {
+        let pods = names
+            .iter()
+            .map(|name| PodInfo {
+                namespace: "default".to_string(),
+                name: name.to_string(),
+                pattern_idx: 0,
+                details: Default::default(),
+            })
+            .collect();
+        build_with(
+            pods,
+            vec![],
+            LogFilter::default(),
+            TrackDeps {
+                discovery: Arc::new(NoDiscovery),
+                spawner: Arc::new(NoSpawner),
+                pattern_to_regex: Arc::new(LiteralRegex),
+                sleeper: Arc::new(ThreadSleeper),
+            },
+            DEFAULT_POLL_INTERVAL,
+            Theme::default(),
+        )
+    }
+
+    struct OnePod;
+
+    impl PodDiscovery for OnePod {
+        fn list_pods(&self) -> Result<Vec<PodInfo>> {
+            Ok(vec![PodInfo {
+                namespace: "default".to_string(),
+                name: "web-1".to_string(),
+                pattern_idx: 0,
+                details: Default::default(),
+            }])
+        }
+    }
+
+    /// Returns from each `sleep` only when the test sends a tick, recording the interval.
+    struct TickSleeper {
+        ticks: std::sync::Mutex<mpsc::Receiver<()>>,
+        slept: std::sync::Mutex<Vec<Duration>>,
+    }
+
+    impl Sleeper for TickSleeper {
+        fn sleep(&self, duration: Duration) {
+            self.slept.lock().unwrap().push(duration);
+            let _ = self.ticks.lock().unwrap().recv();
+        }
+    }
+
+    #[test]
+    fn test_follow_pending_adds_panes_and_reports_unmatched_patterns() {
+        let mut app = test_app(&["web-1"]);
+        let (tx, rx) = mpsc::channel();
+        app.follow_pending(
+            PendingPods {
+                rx,
+                patterns: vec!["web".to_string(), "worker".to_string()],
+            },
+            HashSet::from([0]),
+        );
+        tx.send(Discovered::Pod(PodInfo {
+            namespace: "default".to_string(),
+            name: "web-2".to_string(),
+            pattern_idx: 0,
+            details: Default::default(),
+        }))
+        .unwrap();
+        tx.send(Discovered::Done(Ok(()))).unwrap();
+        drop(tx);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.notice.is_none() && Instant::now() < deadline {
+            app.process_track_events();
+            thread::sleep(Duration::from_millis(10));
+        }
+        let keys: Vec<&str> = app.state.panes.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, vec!["default/web-1", "default/web-2"]);
+        let (notice, _) = app.notice.as_ref().expect("no notice");
+        assert_eq!(notice, "No pods found matching pattern: \"worker\"");
+    }
+
+    #[test]
+    fn test_poll_loop_discovers_new_pod_in_one_cycle() {
+        let (tick, ticks) = mpsc::channel();
+        let sleeper = Arc::new(TickSleeper {
+            ticks: std::sync::Mutex::new(ticks),
+            slept: std::sync::Mutex::new(Vec::new()),
+        });
+        let app = build_with(
+            vec![],
+            vec![Regex::new("web").unwrap()],
+            LogFilter::default(),
+            TrackDeps {
+                discovery: Arc::new(OnePod),
+                spawner: Arc::new(NoSpawner),
+                pattern_to_regex: Arc::new(LiteralRegex),
+                sleeper: sleeper.clone(),
+            },
+            Duration::from_secs(7),
+            Theme::default(),
+        );
+        assert!(app.rx.try_recv().is_err());
+
+        tick.send(()).unwrap();
+        match app.rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(TrackEvent::NewPod { pod, .. }) => assert_eq!(pod.key(), "default/web-1"),
+            _ => panic!("expected a NewPod event after one poll"),
+        }
+        assert_eq!(sleeper.slept.lock().unwrap()[0], Duration::from_secs(7));
+
+        app.shared.running.store(false, Ordering::SeqCst);
+        drop(tick);
+    }
+
+    fn render_rows(app: &mut TrackTui, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        app.update_layout(Rect::new(0, 0, width, height), height.saturating_sub(1));
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn find_span<'a>(line: &'a Line, text: &str) -> &'a Span<'a> {
+        line.spans
+            .iter()
+            .find(|s| s.content == text)
+            .unwrap_or_else(|| panic!("no span {:?} in {:?}", text, line_text(line)))
+    }
+
+    #[test]
+    fn test_key_hint_spans() {
+        let spans = key_hint("q", "quit", Color::LightYellow);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].content, "q");
+        assert_eq!(spans[0].style.fg, Some(Color::LightYellow));
+        assert!(spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(spans[1].content, ": quit  ");
+        assert_eq!(spans[1].style.fg, Some(Color::White));
+    }
+
+    #[test]
+    fn test_status_line_single_tab() {
+        let mut app = test_app(&["api-1"]);
+        let text = line_text(&app.status_line(1));
+        assert!(!text.contains("[1/"));
+        assert!(!text.contains("tabs"));
+        for hint in [
+            "Tab: switch",
+            "End: follow",
+            "f: expand",
+            "e: errors: stay",
+            "o: focus: manual",
+            "J: json: raw",
+            "w: wrap: on",
+            "s: save log",
+            "a /: add pod",
+            "m: select text",
+            "q: quit",
+        ] {
+            assert!(text.contains(hint), "missing {:?} in {:?}", hint, text);
+        }
+
+        app.state.expanded = true;
+        app.state.follow_errors = true;
+        app.state.auto_focus = true;
+        app.state.json_logs = true;
+        app.state.mouse_captured = false;
+        let text = line_text(&app.status_line(1));
+        for hint in [
+            "\u{2190}\u{2192}: cycle tab",
+            "f: collapse",
+            "e: errors: follow",
+            "o: focus: newest",
+            "J: json: pretty",
+            "m: mouse mode",
+            "Ctrl+\u{2191}\u{2193}: scroll",
+        ] {
+            assert!(text.contains(hint), "missing {:?} in {:?}", hint, text);
+        }
+    }
+
+    #[test]
+    fn test_status_line_tab_indicators() {
+        let mut app = test_app(&["api-1", "api-2", "api-3"]);
+        app.update_layout(Rect::new(0, 0, 200, 20), 19);
+        let total_tabs = app.state.total_tabs(19);
+        assert_eq!(total_tabs, 3);
+        app.state.panes[1].mark_unseen(false);
+        app.state.panes[2].mark_unseen(true);
+
+        let line = app.status_line(total_tabs);
+        let text = line_text(&line);
+        assert!(text.contains("[1/3]"));
+        assert!(text.contains("\u{2190}\u{2192}: tabs"));
+        assert_eq!(
+            find_span(&line, "\u{25cf}2 ").style.fg,
+            Some(Color::LightYellow)
+        );
+        assert_eq!(find_span(&line, "\u{25cf}3 ").style.fg, Some(Color::LightRed));
+        assert!(!text.contains("\u{25cf}1"));
+    }
+
+    #[test]
+    fn test_status_line_notice() {
+        let mut app = test_app(&["api-1"]);
+        app.notice = Some(("saved pod.log".to_string(), Instant::now()));
+        let line = app.status_line(1);
+        assert_eq!(
+            find_span(&line, "saved pod.log  ").style.fg,
+            Some(Color::LightGreen)
+        );
+
+        app.notice = Some((
+            "saved pod.log".to_string(),
+            Instant::now() - NOTICE_DURATION,
+        ));
+        assert!(!line_text(&app.status_line(1)).contains("saved"));
+    }
+
+    #[test]
+    fn test_single_tab_pane_rects_follow_scrolled_window() {
+        let mut app = test_app(&["p0", "p1", "p2", "p3", "p4", "p5"]);
+        app.state.single_tab = true;
+        app.state.selected = 5;
+        app.update_layout(Rect::new(0, 0, 80, 10), 9);
+        let indices: Vec<usize> = app.state.pane_rects.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![3, 4, 5]);
+        assert_eq!(app.state.pane_rects[0].1.y, 0);
+        assert_eq!(app.state.pane_rects[2].1.y, 6);
+    }
+
+    #[test]
+    fn test_render_pane_title_shows_pod_key() {
+        let mut app = test_app(&["api-1"]);
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows[0].contains(" default/api-1 "));
+        assert!(!rows[0].contains("[SCROLLED]"));
+    }
+
+    #[test]
+    fn test_render_pane_title_shows_node() {
+        let mut app = test_app(&["api-1"]);
+        app.state.panes[0].node = Some("node-a".to_string());
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows[0].contains(" default/api-1 @ node-a "));
+        assert!(rows.last().unwrap().contains("n: node: shown"));
+
+        app.state.show_node = false;
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows[0].contains(" default/api-1 "));
+        assert!(!rows[0].contains("node-a"));
+    }
+
+    #[test]
+    fn test_pod_gone_marks_pane_until_pod_returns() {
+        let mut app = test_app(&["api-1"]);
+        app.shared
+            .tx
+            .send(TrackEvent::PodGone {
+                pod_key: "default/api-1".to_string(),
+            })
+            .unwrap();
+        app.process_track_events();
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows[0].contains(" default/api-1 [GONE] "));
+
+        let pod = PodInfo {
+            namespace: "default".to_string(),
+            name: "api-1".to_string(),
+            pattern_idx: 0,
+            details: Default::default(),
+        };
+        let alive = Arc::new(AtomicBool::new(true));
+        app.shared
+            .tx
+            .send(TrackEvent::NewPod { pod, alive })
+            .unwrap();
+        app.process_track_events();
+        assert_eq!(app.state.panes.len(), 1);
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(!rows[0].contains("[GONE]"));
+    }
+
+    #[test]
+    fn test_render_scrolled_pane_title() {
+        let mut app = test_app(&["api-1"]);
+        for i in 0..50 {
+            app.state.panes[0].push_line(format!("line {}", i));
+        }
+        app.state.panes[0].scroll_up_by(10, 5);
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows[0].contains("default/api-1 [SCROLLED]"));
+    }
+
+    #[test]
+    fn test_render_shows_log_lines() {
+        let mut app = test_app(&["api-1"]);
+        app.state.panes[0].push_line("\x1b[31mboom\x1b[0m".to_string());
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows.iter().any(|r| r.contains("boom") && !r.contains("[31m")));
+
+        app.state.raw = true;
+        let rows = render_rows(&mut app, 200, 20);
+        assert!(rows.iter().any(|r| r.contains("^[[31mboom^[[0m")));
+    }
+
+    #[test]
+    fn test_render_unwrapped_pane_pans_long_lines() {
+        let mut app = test_app(&["api-1"]);
+        app.state.panes[0].push_line(format!("start {} end", "x".repeat(60)));
+        let rows = render_rows(&mut app, 40, 20);
+        assert!(rows.iter().any(|r| r.contains("end")));
+
+        app.state.panes[0].wrap = false;
+        let rows = render_rows(&mut app, 40, 20);
+        assert!(rows.iter().any(|r| r.contains("start")));
+        assert!(!rows.iter().any(|r| r.contains("end")));
+
+        app.state.panes[0].h_scroll = 40;
+        let rows = render_rows(&mut app, 40, 20);
+        assert!(rows.iter().any(|r| r.contains("end")));
+        assert!(!rows.iter().any(|r| r.contains("start")));
+    }
+
+    #[test]
+    fn test_merged_view_interleaves_panes_in_arrival_order() {
+        let mut app = test_app(&["api-1", "web-1"]);
+        for (key, text) in [
+            ("default/api-1", "first"),
+            ("default/web-1", "second"),
+            ("default/api-1", "third"),
+        ] {
+            app.shared
+                .tx
+                .send(TrackEvent::LogLine {
+                    pod_key: key.to_string(),
+                    text: text.to_string(),
+                })
+                .unwrap();
+        }
+        app.process_track_events();
+        assert_eq!(app.state.panes[0].lines.len(), 2);
+
+        app.state.merged_view = true;
+        let rows = render_rows(&mut app, 80, 20);
+        assert!(rows[0].contains("merged: 2 pods"));
+        let logged: Vec<&String> = rows.iter().filter(|r| r.contains("default/")).collect();
+        assert_eq!(logged.len(), 3);
+        assert!(logged[0].contains("default/api-1 first"));
+        assert!(logged[1].contains("default/web-1 second"));
+        assert!(logged[2].contains("default/api-1 third"));
+    }
+
+    #[test]
+    fn test_weight_constraints() {
+        assert_eq!(
+            weight_constraints(&[100, 100]),
+            vec![Constraint::Ratio(100, 200), Constraint::Ratio(100, 200)]
+        );
+        assert_eq!(
+            weight_constraints(&[150, 50, 100]),
+            vec![
+                Constraint::Ratio(150, 300),
+                Constraint::Ratio(50, 300),
+                Constraint::Ratio(100, 300),
+            ]
+        );
+        assert!(weight_constraints(&[]).is_empty());
+
+        let chunks = ratio_chunks(Rect::new(0, 0, 80, 40), &[150, 50]);
+        assert_eq!((chunks[0].height, chunks[1].height), (30, 10));
+    }
+
+    #[test]
+    fn test_pane_weights_survive_reflow() {
+        let mut app = test_app(&["api-1", "api-2"]);
+        app.state
+            .pane_weights
+            .insert("default/api-2".to_string(), 300);
+        app.update_layout(Rect::new(0, 0, 80, 41), 40);
+        let heights: Vec<u16> = app.state.pane_rects.iter().map(|(_, r)| r.height).collect();
+        assert_eq!(heights, vec![10, 30]);
+
+        // A new pane joins the tab; the resized pane keeps its share relative to the others.
+        let alive = Arc::new(AtomicBool::new(true));
+        app.state.add_pane(PodPane::new(
+            "default/api-3".to_string(),
+            Color::Cyan,
+            alive,
+        ));
+        app.update_layout(Rect::new(0, 0, 80, 41), 40);
+        let heights: Vec<u16> = app.state.pane_rects.iter().map(|(_, r)| r.height).collect();
+        assert_eq!(heights, vec![8, 24, 8]);
+    }
+
+    #[test]
+    fn test_render_status_bar_shows_keybindings() {
+        let mut app = test_app(&["api-1"]);
+        let rows = render_rows(&mut app, 300, 20);
+        let status = rows.last().unwrap();
+        assert!(status.contains("Tab: switch"));
+        assert!(status.contains("q: quit"));
+        assert!(!status.contains("Pattern:"));
+
+        app.state.input_mode = true;
+        app.state.input_buffer = "web".to_string();
+        let rows = render_rows(&mut app, 300, 20);
+        let status = rows.last().unwrap();
+        assert!(status.contains("Pattern: web"));
+        assert!(status.contains("Enter: add"));
+    }
+
+    #[test]
+    fn test_render_jump_picker_lists_matches() {
+        let mut app = test_app(&["api-1", "web-1", "web-2"]);
+        app.state.input_mode = true;
+        app.state.input_kind = InputKind::JumpToPod;
+        app.state.input_buffer = "web".to_string();
+        let rows = render_rows(&mut app, 120, 20);
+        assert!(rows.last().unwrap().contains("Jump to: web"));
+        assert!(rows.iter().any(|r| r.contains("2 of 3 pods")));
+        // Only the first pane fits on screen, so the other keys come from the picker.
+        let listed = |key: &str| rows[..19].iter().filter(|r| r.contains(key)).count();
+        assert_eq!(listed("default/api-1"), 1);
+        assert_eq!(listed("default/web-1"), 1);
+        assert_eq!(listed("default/web-2"), 1);
+    }
+
+    #[test]
+    fn test_render_error_dashboard() {
+        let mut app = test_app(&["api-1", "api-2"]);
+        app.state.panes[1].error_count = 12;
+        app.state.dashboard_open = true;
+        let rows = render_rows(&mut app, 200, 20);
+        let row_of = |count: &str, pod: &str| {
+            rows.iter()
+                .position(|r| r.contains(count) && r.contains(pod))
+                .unwrap_or_else(|| panic!("no {pod} row in {rows:#?}"))
+        };
+        assert!(row_of("12", "default/api-2") < row_of("0", "default/api-1"));
+        assert!(rows.last().unwrap().contains("Enter: jump"));
+    }
+}
@@ -3,10 +3,11 @@ use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::{mpsc, Arc, Mutex};
 
+use super::super::LogFilter;
 use super::event::TrackEvent;
 
 pub struct SharedState {
-    pub err_only: bool,
+    pub filter: LogFilter,
     pub running: Arc<AtomicBool>,
     pub active_pods: Arc<Mutex<HashSet<String>>>,
     pub closed_pods: Arc<Mutex<HashSet<String>>>,
@@ -16,12 +17,12 @@ pub struct SharedState {
 
 impl SharedState {
     pub fn new(
-        err_only: bool,
+        filter: LogFilter,
         initial_regexes: Vec<Regex>,
     ) -> (Self, mpsc::Receiver<TrackEvent>) {
         let (tx, rx) = mpsc::channel();
         let shared = Self {
-            err_only,
+            filter,
             running: Arc::new(AtomicBool::new(true)),
             active_pods: Arc::new(Mutex::new(HashSet::new())),
             closed_pods: Arc::new(Mutex::new(HashSet::new())),
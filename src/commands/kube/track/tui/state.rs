@@ -1,23 +1,102 @@
+use ansi_to_tui::IntoText;
 use crossterm::event::{
     DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers, MouseButton, MouseEventKind,
 };
 use crossterm::ExecutableCommand;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
+use ratatui::text::{Line, Span};
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::super::{InitialView, NavMode};
+use crate::utils::colors::strip_ansi;
+use crate::utils::packages::fuzzy_match_scored;
 
 pub const MAX_LOG_LINES: usize = 5000;
 pub const MIN_PANE_HEIGHT: u16 = 12;
+/// Smallest pane (border plus one log line) in `single_tab` mode.
+pub const SINGLE_TAB_MIN_PANE_HEIGHT: u16 = 3;
+/// How long after the last key/mouse input before `follow_errors` or `auto_focus` may move
+/// the selection.
+pub const FOLLOW_ERRORS_IDLE: Duration = Duration::from_secs(3);
+/// Minimum time between two automatic jumps, so an error storm doesn't thrash tabs.
+pub const FOLLOW_ERRORS_DEBOUNCE: Duration = Duration::from_secs(5);
+/// Columns moved per Left/Right press when a pane isn't wrapping.
+pub const H_SCROLL_STEP: u16 = 8;
+/// Layout weight of a pane that hasn't been resized.
+pub const DEFAULT_PANE_WEIGHT: u32 = 100;
+/// Smallest height (border plus one line) dragging a border can shrink a pane to.
+const MIN_DRAG_PANE_HEIGHT: u16 = 3;
+
+/// Replaces control characters with caret notation (`ESC` becomes `^[`) so raw
+/// escape sequences show up verbatim instead of being interpreted.
+pub fn escape_control(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '\t' => out.push(c),
+            '\x7f' => out.push_str("^?"),
+            c if (c as u32) < 0x20 => {
+                out.push('^');
+                out.push((c as u8 + b'@') as char);
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `line` with its ANSI escapes parsed into styled spans.
+fn parse_ansi_line(line: &str) -> Line<'static> {
+    line.as_bytes()
+        .into_text()
+        .ok()
+        .and_then(|text| text.lines.into_iter().next())
+        .unwrap_or_else(|| Line::raw(line.to_string()))
+}
+
+/// A view of a cached line that borrows its text instead of copying it.
+fn borrow_line<'a>(line: &'a Line<'static>) -> Line<'a> {
+    line.spans
+        .iter()
+        .map(|span| Span::styled(span.content.as_ref(), span.style))
+        .collect::<Line>()
+        .style(line.style)
+}
 
 #[derive(Clone)]
 pub struct PodPane {
     pub key: String,
     pub color: Color,
+    /// Raw log text, for saving, copying and `--raw`.
     pub lines: std::collections::VecDeque<String>,
+    /// `lines` parsed once on push, so frames don't re-parse ANSI escapes.
+    parsed: std::collections::VecDeque<Line<'static>>,
     pub alive: Arc<std::sync::atomic::AtomicBool>,
+    /// Index of the top visible line while scrolled back; `None` follows the tail.
     pub scroll_up: Option<usize>,
+    pub activity: Activity,
+    pub node: Option<String>,
+    /// Error-keyword lines received this session, for the error dashboard.
+    pub error_count: usize,
+    /// The pod was deleted and its log stream stopped.
+    pub gone: bool,
+    /// Wrap long lines; when off, `h_scroll` pans them horizontally instead.
+    pub wrap: bool,
+    pub h_scroll: u16,
+}
+
+/// Unseen output in a pane (or tab) since it was last on screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Activity {
+    #[default]
+    None,
+    Lines,
+    Errors,
 }
 
 impl PodPane {
@@ -30,27 +109,75 @@ impl PodPane {
             key,
             color,
             lines: std::collections::VecDeque::with_capacity(MAX_LOG_LINES),
+            parsed: std::collections::VecDeque::with_capacity(MAX_LOG_LINES),
             alive,
             scroll_up: None,
+            activity: Activity::None,
+            node: None,
+            error_count: 0,
+            gone: false,
+            wrap: true,
+            h_scroll: 0,
+        }
+    }
+
+    /// The pane title: the pod key, followed by its node when known and `show_node` is on.
+    pub fn title_label(&self, show_node: bool) -> String {
+        match &self.node {
+            Some(node) if show_node => format!("{} @ {}", self.key, node),
+            _ => self.key.clone(),
+        }
+    }
+
+    /// File name for a saved copy of this pane's log, e.g. `default_api-0-1760000000.log`.
+    pub fn log_file_name(&self, unix_secs: u64) -> String {
+        format!("{}-{}.log", self.key.replace('/', "_"), unix_secs)
+    }
+
+    /// The buffered log with ANSI escapes removed, ready to write to disk.
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for line in &self.lines {
+            text.push_str(&strip_ansi(line));
+            text.push('\n');
         }
+        text
+    }
+
+    pub fn mark_unseen(&mut self, is_error: bool) {
+        let activity = if is_error {
+            Activity::Errors
+        } else {
+            Activity::Lines
+        };
+        self.activity = self.activity.max(activity);
     }
 
     pub fn push_line(&mut self, line: String) {
         let was_at_max = self.lines.len() >= MAX_LOG_LINES;
         if was_at_max {
             self.lines.pop_front();
+            self.parsed.pop_front();
             if let Some(ref mut pos) = self.scroll_up {
                 *pos = pos.saturating_sub(1);
             }
         }
+        self.parsed.push_back(parse_ansi_line(&line));
         self.lines.push_back(line);
     }
 
+    /// Up to `count` styled lines starting at `start`, borrowed from the parse cache.
+    pub fn styled_lines(&self, start: usize, count: usize) -> impl Iterator<Item = Line<'_>> {
+        self.parsed.iter().skip(start).take(count).map(borrow_line)
+    }
+
     pub fn scroll_offset(&self, inner_height: usize) -> u16 {
         let auto = self.lines.len().saturating_sub(inner_height);
         match self.scroll_up {
-            None => auto as u16,
-            Some(pos) => (pos as u16).min(auto as u16),
+            // Anchored to a line rather than clamped to `auto`, so a reflow that makes the
+            // pane taller keeps the same top line instead of jumping.
+            Some(pos) if pos < self.lines.len() => pos as u16,
+            _ => auto as u16,
         }
     }
 
@@ -84,11 +211,49 @@ impl PodPane {
     pub fn scroll_end(&mut self) {
         self.scroll_up = None;
     }
+
+    /// Scrolls an unwrapped pane sideways by one step.
+    pub fn pan(&mut self, right: bool) {
+        self.h_scroll = if right {
+            self.h_scroll.saturating_add(H_SCROLL_STEP)
+        } else {
+            self.h_scroll.saturating_sub(H_SCROLL_STEP)
+        };
+    }
+}
+
+/// What the text typed in input mode is for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputKind {
+    /// A pod pattern to start tracking (`a` or `/`).
+    #[default]
+    AddPattern,
+    /// A fuzzy query over the open panes (`:`).
+    JumpToPod,
+}
+
+/// Row order of the error dashboard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DashboardSort {
+    #[default]
+    Errors,
+    Name,
+}
+
+impl DashboardSort {
+    pub fn label(self) -> &'static str {
+        match self {
+            DashboardSort::Errors => "errors",
+            DashboardSort::Name => "name",
+        }
+    }
 }
 
 pub enum KeyAction {
     Quit,
     AddPattern(String),
+    SaveLog(usize),
+    Copy(String),
     None,
 }
 
@@ -97,12 +262,43 @@ pub struct TuiState {
     pub current_tab: usize,
     pub expanded: bool,
     pub input_mode: bool,
+    pub input_kind: InputKind,
     pub input_buffer: String,
+    /// Highlighted entry of `jump_candidates`.
+    pub jump_choice: usize,
     pub panes: Vec<PodPane>,
     pub pane_index: HashMap<String, usize>,
     pub pane_rects: Vec<(usize, Rect)>,
     last_click: Option<(usize, std::time::Instant)>,
     pub mouse_captured: bool,
+    pub nav_mode: Option<NavMode>,
+    pub follow_errors: bool,
+    /// Select whichever pane most recently received a line.
+    pub auto_focus: bool,
+    pub json_logs: bool,
+    pub show_node: bool,
+    pub raw: bool,
+    pub min_pane_height: u16,
+    pub single_tab: bool,
+    pub last_user_input: Option<Instant>,
+    last_error_jump: Option<Instant>,
+    pub dashboard_open: bool,
+    pub dashboard_sort: DashboardSort,
+    /// Highlighted row of the error dashboard (an index into `dashboard_rows`).
+    pub dashboard_row: usize,
+    /// Wrap setting given to newly added panes; follows the last `w` toggle.
+    pub wrap_new_panes: bool,
+    /// Show every pane's lines interleaved in arrival order instead of the panes. The
+    /// merged view ignores per-pane filters and settings such as wrap and scroll position.
+    pub merged_view: bool,
+    /// The merged scrollback; `merged_sources` holds the pane key of each of its lines.
+    pub merged: PodPane,
+    pub merged_sources: std::collections::VecDeque<String>,
+    pub merged_rect: Rect,
+    /// Layout weights of resized panes by pane key, so they survive reflows and tab moves.
+    pub pane_weights: HashMap<String, u32>,
+    /// Index into `pane_rects` of the pane above the border being dragged.
+    resize_drag: Option<usize>,
 }
 
 impl TuiState {
@@ -112,20 +308,110 @@ impl TuiState {
             current_tab: 0,
             expanded: false,
             input_mode: false,
+            input_kind: InputKind::default(),
             input_buffer: String::new(),
+            jump_choice: 0,
             panes: Vec::new(),
             pane_index: HashMap::new(),
             pane_rects: vec![],
             last_click: None,
             mouse_captured: true,
+            nav_mode: None,
+            follow_errors: false,
+            auto_focus: false,
+            json_logs: false,
+            show_node: true,
+            raw: false,
+            min_pane_height: MIN_PANE_HEIGHT,
+            single_tab: false,
+            last_user_input: None,
+            last_error_jump: None,
+            dashboard_open: false,
+            dashboard_sort: DashboardSort::default(),
+            dashboard_row: 0,
+            wrap_new_panes: true,
+            merged_view: false,
+            merged: PodPane::new(
+                "merged".to_string(),
+                Color::White,
+                Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            ),
+            merged_sources: std::collections::VecDeque::with_capacity(MAX_LOG_LINES),
+            merged_rect: Rect::default(),
+            pane_weights: HashMap::new(),
+            resize_drag: None,
+        }
+    }
+
+    pub fn pane_weight(&self, idx: usize) -> u32 {
+        self.panes
+            .get(idx)
+            .and_then(|pane| self.pane_weights.get(&pane.key))
+            .copied()
+            .unwrap_or(DEFAULT_PANE_WEIGHT)
+    }
+
+    /// The `pane_rects` index of the upper pane when (`col`, `row`) is on the border rows
+    /// between two stacked panes.
+    fn border_at(&self, col: u16, row: u16) -> Option<usize> {
+        self.pane_rects.windows(2).position(|pair| {
+            let (upper, lower) = (pair[0].1, pair[1].1);
+            lower.y == upper.y + upper.height
+                && (row + 1 == lower.y || row == lower.y)
+                && col >= upper.x
+                && col < upper.x + upper.width
+        })
+    }
+
+    /// Moves the border below `pane_rects[upper]` to `row`, trading weight between the two
+    /// panes so their heights follow the mouse.
+    fn drag_border(&mut self, upper: usize, row: u16) {
+        let (Some(&(top_idx, top)), Some(&(bottom_idx, bottom))) =
+            (self.pane_rects.get(upper), self.pane_rects.get(upper + 1))
+        else {
+            return;
+        };
+        let combined = top.height + bottom.height;
+        if combined < MIN_DRAG_PANE_HEIGHT * 2 {
+            return;
+        }
+        let top_height = (row + 1)
+            .saturating_sub(top.y)
+            .clamp(MIN_DRAG_PANE_HEIGHT, combined - MIN_DRAG_PANE_HEIGHT);
+        let total = self.pane_weight(top_idx) + self.pane_weight(bottom_idx);
+        let top_weight = (total * top_height as u32 / combined as u32).max(1);
+        let bottom_weight = total.saturating_sub(top_weight).max(1);
+        for (idx, weight) in [(top_idx, top_weight), (bottom_idx, bottom_weight)] {
+            if let Some(pane) = self.panes.get(idx) {
+                self.pane_weights.insert(pane.key.clone(), weight);
+            }
         }
     }
 
-    pub fn add_pane(&mut self, pane: PodPane) {
+    pub fn add_pane(&mut self, mut pane: PodPane) {
+        pane.wrap = self.wrap_new_panes;
         self.pane_index.insert(pane.key.clone(), self.panes.len());
         self.panes.push(pane);
     }
 
+    /// Appends a line from pane `key` to the merged scrollback, capped like a pane.
+    pub fn push_merged(&mut self, key: &str, line: String) {
+        if self.merged.lines.len() >= MAX_LOG_LINES {
+            self.merged_sources.pop_front();
+        }
+        self.merged_sources.push_back(key.to_string());
+        self.merged.push_line(line);
+    }
+
+    /// The pane that scroll keys act on: the merged scrollback in merged view.
+    fn scroll_target(&mut self) -> Option<&mut PodPane> {
+        if self.merged_view {
+            Some(&mut self.merged)
+        } else {
+            self.panes.get_mut(self.selected)
+        }
+    }
+
     pub fn rebuild_index(&mut self) {
         self.pane_index = self
             .panes
@@ -136,7 +422,10 @@ impl TuiState {
     }
 
     pub fn max_panes_per_tab(&self, available_height: u16) -> usize {
-        (available_height / MIN_PANE_HEIGHT).max(1) as usize
+        if self.single_tab {
+            return self.panes.len().max(1);
+        }
+        (available_height / self.min_pane_height.max(1)).max(1) as usize
     }
 
     pub fn total_tabs(&self, available_height: u16) -> usize {
@@ -147,14 +436,123 @@ impl TuiState {
         self.panes.len().div_ceil(per_tab)
     }
 
+    /// Whether Left/Right pan the selected pane instead of switching tabs: it isn't
+    /// wrapping, the view isn't expanded, and there's only one tab.
+    pub fn pans_horizontally(&self, available_height: u16) -> bool {
+        !self.expanded
+            && self.total_tabs(available_height) <= 1
+            && self.panes.get(self.selected).is_some_and(|pane| !pane.wrap)
+    }
+
+    pub fn tab_range(&self, available_height: u16) -> std::ops::Range<usize> {
+        let per_tab = self.max_panes_per_tab(available_height);
+        let start = self.current_tab * per_tab;
+        let end = (start + per_tab).min(self.panes.len());
+        start..end
+    }
+
     pub fn visible_indices(&self, available_height: u16) -> Vec<usize> {
         if self.expanded {
             return vec![self.selected];
         }
+        if self.single_tab {
+            // Show as many panes as fit and scroll the window to keep the selection on screen.
+            let fit = (available_height / SINGLE_TAB_MIN_PANE_HEIGHT).max(1) as usize;
+            let start = (self.selected + 1).saturating_sub(fit);
+            return (start..(start + fit).min(self.panes.len())).collect();
+        }
+        self.tab_range(available_height).collect()
+    }
+
+    pub fn mark_visible_seen(&mut self, available_height: u16) {
+        for idx in self.visible_indices(available_height) {
+            if let Some(pane) = self.panes.get_mut(idx) {
+                pane.activity = Activity::None;
+            }
+        }
+    }
+
+    pub fn tab_activity(&self, available_height: u16) -> Vec<Activity> {
         let per_tab = self.max_panes_per_tab(available_height);
-        let start = self.current_tab * per_tab;
-        let end = (start + per_tab).min(self.panes.len());
-        (start..end).collect()
+        let mut tabs = vec![Activity::None; self.total_tabs(available_height)];
+        for (i, pane) in self.panes.iter().enumerate() {
+            let tab = &mut tabs[i / per_tab];
+            *tab = (*tab).max(pane.activity);
+        }
+        tabs
+    }
+
+    pub fn follow_error(&mut self, idx: usize, now: Instant, available_height: u16) -> bool {
+        if !self.follow_errors
+            || self.input_mode
+            || idx == self.selected
+            || idx >= self.panes.len()
+        {
+            return false;
+        }
+        let recent = |t: Option<Instant>, window: Duration| {
+            t.is_some_and(|t| now.saturating_duration_since(t) < window)
+        };
+        if recent(self.last_user_input, FOLLOW_ERRORS_IDLE)
+            || recent(self.last_error_jump, FOLLOW_ERRORS_DEBOUNCE)
+        {
+            return false;
+        }
+        self.selected = idx;
+        self.ensure_selected_visible(available_height);
+        self.last_error_jump = Some(now);
+        true
+    }
+
+    /// With `auto_focus` on, selects pane `idx` after it received a line, unless the user
+    /// scrolled or moved the selection recently. Returns whether the selection changed.
+    pub fn auto_focus_on(&mut self, idx: usize, now: Instant, available_height: u16) -> bool {
+        if !self.auto_focus
+            || self.input_mode
+            || self.dashboard_open
+            || idx == self.selected
+            || idx >= self.panes.len()
+        {
+            return false;
+        }
+        let user_active = self
+            .last_user_input
+            .is_some_and(|t| now.saturating_duration_since(t) < FOLLOW_ERRORS_IDLE);
+        if user_active {
+            return false;
+        }
+        self.selected = idx;
+        self.ensure_selected_visible(available_height);
+        true
+    }
+
+    pub fn cycle_expanded_in_tab(&mut self, available_height: u16, forward: bool) {
+        let range = self.tab_range(available_height);
+        if range.is_empty() {
+            return;
+        }
+        let pos = self.selected.clamp(range.start, range.end - 1) - range.start;
+        self.selected = range.start + self.pane_nav().step(pos, range.len(), forward);
+    }
+
+    /// How pane cycling (and the dashboard) behaves at the ends; wraps unless `--nav` says.
+    fn pane_nav(&self) -> NavMode {
+        self.nav_mode.unwrap_or(NavMode::Wrap)
+    }
+
+    /// How Left/Right tab switching behaves at the ends; clamps unless `--nav` says.
+    fn tab_nav(&self) -> NavMode {
+        self.nav_mode.unwrap_or(NavMode::Clamp)
+    }
+
+    fn step_tab(&mut self, available_height: u16, forward: bool) {
+        let total = self.total_tabs(available_height);
+        let next = self.tab_nav().step(self.current_tab, total, forward);
+        if next != self.current_tab {
+            let per_tab = self.max_panes_per_tab(available_height);
+            self.current_tab = next;
+            self.selected = (next * per_tab).min(self.panes.len().saturating_sub(1));
+        }
     }
 
     pub fn ensure_selected_visible(&mut self, available_height: u16) {
@@ -169,6 +567,46 @@ impl TuiState {
         self.current_tab = tab_for_selected.min(total.saturating_sub(1));
     }
 
+    pub fn apply_initial_view(&mut self, view: &InitialView, available_height: u16) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.panes.is_empty() {
+            return warnings;
+        }
+
+        if let Some(tab) = view.tab {
+            let total = self.total_tabs(available_height);
+            let idx = tab.saturating_sub(1).min(total - 1);
+            if tab == 0 || tab > total {
+                warnings.push(format!(
+                    "--initial-tab {tab} is out of range (1-{total}); using tab {}",
+                    idx + 1
+                ));
+            }
+            let per_tab = self.max_panes_per_tab(available_height);
+            self.current_tab = idx;
+            self.selected = (idx * per_tab).min(self.panes.len() - 1);
+        }
+
+        if let Some(ref key) = view.select {
+            let found = self.pane_index.get(key).copied().or_else(|| {
+                self.panes
+                    .iter()
+                    .position(|p| p.key.rsplit('/').next() == Some(key.as_str()))
+            });
+            match found {
+                Some(idx) => {
+                    self.selected = idx;
+                    self.ensure_selected_visible(available_height);
+                }
+                None => warnings.push(format!(
+                    "--select {key}: no tracked pod with that name; keeping the default selection"
+                )),
+            }
+        }
+
+        warnings
+    }
+
     pub(crate) fn scroll_to_scrollbar_pos(
         col: u16,
         row: u16,
@@ -200,6 +638,16 @@ impl TuiState {
         let col = mouse.column;
         let row = mouse.row;
 
+        if self.merged_view {
+            let inner_h = self.merged_rect.height.saturating_sub(2) as usize;
+            match mouse.kind {
+                MouseEventKind::ScrollUp => self.merged.scroll_up_by(inner_h, 3),
+                MouseEventKind::ScrollDown => self.merged.scroll_down_by(inner_h, 3),
+                _ => {}
+            }
+            return;
+        }
+
         let hit = self
             .pane_rects
             .iter()
@@ -211,10 +659,44 @@ impl TuiState {
             })
             .copied();
 
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.resize_drag = self.border_at(col, row);
+                if self.resize_drag.is_some() {
+                    return;
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(upper) = self.resize_drag {
+                    self.drag_border(upper, row);
+                    return;
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.resize_drag = None,
+            _ => {}
+        }
+
         let Some((pane_idx, rect)) = hit else {
             return;
         };
 
+        // Shift+wheel (or a sideways trackpad swipe) pans panes that aren't wrapping.
+        let shift = mouse.modifiers.contains(KeyModifiers::SHIFT);
+        let pan_right = match mouse.kind {
+            MouseEventKind::ScrollLeft => Some(false),
+            MouseEventKind::ScrollRight => Some(true),
+            MouseEventKind::ScrollUp if shift => Some(false),
+            MouseEventKind::ScrollDown if shift => Some(true),
+            _ => None,
+        };
+        if let Some(right) = pan_right {
+            if let Some(pane) = self.panes.get_mut(pane_idx).filter(|pane| !pane.wrap) {
+                pane.pan(right);
+                self.selected = pane_idx;
+                return;
+            }
+        }
+
         let scrollbar_col = rect.x + rect.width - 1;
         let on_scrollbar = col >= scrollbar_col.saturating_sub(1);
         let inner_h = rect.height.saturating_sub(2) as usize;
@@ -272,7 +754,105 @@ impl TuiState {
         }
     }
 
-    pub fn handle_input_key(&mut self, code: KeyCode) -> KeyAction {
+    /// Pane indices in error dashboard order: most errors first (ties by name), or by name.
+    pub fn dashboard_rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = (0..self.panes.len()).collect();
+        let by_name = |a: &usize, b: &usize| self.panes[*a].key.cmp(&self.panes[*b].key);
+        match self.dashboard_sort {
+            DashboardSort::Errors => rows.sort_by(|a, b| {
+                self.panes[*b]
+                    .error_count
+                    .cmp(&self.panes[*a].error_count)
+                    .then_with(|| by_name(a, b))
+            }),
+            DashboardSort::Name => rows.sort_by(by_name),
+        }
+        rows
+    }
+
+    pub fn handle_dashboard_key(&mut self, code: KeyCode, available_height: u16) {
+        let row_count = self.panes.len();
+        match code {
+            KeyCode::Esc | KeyCode::Char('E') | KeyCode::Char('q') => {
+                self.dashboard_open = false;
+            }
+            KeyCode::Up | KeyCode::Char('k') if row_count > 0 => {
+                self.dashboard_row = self.pane_nav().step(self.dashboard_row, row_count, false);
+            }
+            KeyCode::Down | KeyCode::Char('j') if row_count > 0 => {
+                self.dashboard_row = self.pane_nav().step(self.dashboard_row, row_count, true);
+            }
+            KeyCode::Char('o') => {
+                self.dashboard_sort = match self.dashboard_sort {
+                    DashboardSort::Errors => DashboardSort::Name,
+                    DashboardSort::Name => DashboardSort::Errors,
+                };
+            }
+            KeyCode::Enter => {
+                if let Some(&idx) = self.dashboard_rows().get(self.dashboard_row) {
+                    self.selected = idx;
+                    self.ensure_selected_visible(available_height);
+                }
+                self.dashboard_open = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Pane keys matching the jump query, best match first (all panes when it's empty).
+    pub fn jump_candidates(&self) -> Vec<String> {
+        let keys: Vec<String> = self.panes.iter().map(|pane| pane.key.clone()).collect();
+        if self.input_buffer.is_empty() {
+            return keys;
+        }
+        fuzzy_match_scored(&keys, &self.input_buffer)
+            .into_iter()
+            .map(|(_, key)| key)
+            .collect()
+    }
+
+    fn handle_jump_key(&mut self, code: KeyCode, available_height: u16) {
+        let count = self.jump_candidates().len();
+        match code {
+            KeyCode::Enter => {
+                if let Some(&idx) = self
+                    .jump_candidates()
+                    .get(self.jump_choice)
+                    .and_then(|key| self.pane_index.get(key))
+                {
+                    self.selected = idx;
+                    self.ensure_selected_visible(available_height);
+                    self.input_buffer.clear();
+                    self.input_mode = false;
+                }
+            }
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = false;
+            }
+            KeyCode::Up if count > 0 => {
+                self.jump_choice = self.jump_choice.saturating_sub(1);
+            }
+            KeyCode::Down if count > 0 => {
+                self.jump_choice = (self.jump_choice + 1).min(count - 1);
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+                self.jump_choice = 0;
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+                self.jump_choice = 0;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_input_key(&mut self, code: KeyCode, available_height: u16) -> KeyAction {
+        if self.input_kind == InputKind::JumpToPod {
+            self.handle_jump_key(code, available_height);
+            return KeyAction::None;
+        }
         match code {
             KeyCode::Enter => {
                 if !self.input_buffer.is_empty() {
@@ -320,97 +900,102 @@ impl TuiState {
                 self.expanded = !self.expanded;
             }
             KeyCode::Esc => {
-                if self.expanded {
+                if self.merged_view {
+                    self.merged_view = false;
+                } else if self.expanded {
                     self.expanded = false;
                 }
             }
+            KeyCode::Char('g') => {
+                self.merged_view = !self.merged_view;
+            }
+            KeyCode::Left | KeyCode::Char('[') if self.expanded => {
+                self.cycle_expanded_in_tab(available_height, false);
+            }
+            KeyCode::Right | KeyCode::Char(']') if self.expanded => {
+                self.cycle_expanded_in_tab(available_height, true);
+            }
+            KeyCode::Left if self.pans_horizontally(available_height) => {
+                self.panes[self.selected].pan(false);
+            }
+            KeyCode::Right if self.pans_horizontally(available_height) => {
+                self.panes[self.selected].pan(true);
+            }
             KeyCode::Left => {
-                if self.current_tab > 0 {
-                    let per_tab = self.max_panes_per_tab(available_height);
-                    self.current_tab -= 1;
-                    self.selected = self.current_tab * per_tab;
-                }
+                self.step_tab(available_height, false);
             }
             KeyCode::Right => {
-                let total = self.total_tabs(available_height);
-                if self.current_tab + 1 < total {
-                    let per_tab = self.max_panes_per_tab(available_height);
-                    self.current_tab += 1;
-                    self.selected = (self.current_tab * per_tab).min(self.panes.len().saturating_sub(1));
-                }
+                self.step_tab(available_height, true);
             }
             KeyCode::Tab | KeyCode::Char('j') => {
                 if !self.panes.is_empty() {
-                    self.selected = (self.selected + 1) % self.panes.len();
+                    self.selected = self.pane_nav().step(self.selected, self.panes.len(), true);
                     self.ensure_selected_visible(available_height);
                 }
             }
             KeyCode::BackTab | KeyCode::Char('k') => {
                 if !self.panes.is_empty() {
-                    self.selected = self
-                        .selected
-                        .checked_sub(1)
-                        .unwrap_or(self.panes.len() - 1);
+                    self.selected = self.pane_nav().step(self.selected, self.panes.len(), false);
                     self.ensure_selected_visible(available_height);
                 }
             }
             KeyCode::Up if ctrl_scroll => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_up_by(page_size, 1);
                 }
             }
             KeyCode::Down if ctrl_scroll => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_down_by(page_size, 1);
                 }
             }
             KeyCode::PageUp if ctrl_scroll => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_up_by(page_size, page_size);
                 }
             }
             KeyCode::PageDown if ctrl_scroll => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_down_by(page_size, page_size);
                 }
             }
             KeyCode::Home if ctrl_scroll => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_home();
                 }
             }
             KeyCode::End if ctrl_scroll => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_end();
                 }
             }
             KeyCode::Up => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_up_by(page_size, 1);
                 }
             }
             KeyCode::Down => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_down_by(page_size, 1);
                 }
             }
             KeyCode::PageUp => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_up_by(page_size, page_size);
                 }
             }
             KeyCode::PageDown => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_down_by(page_size, page_size);
                 }
             }
             KeyCode::Home => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_home();
                 }
             }
             KeyCode::End => {
-                if let Some(pane) = self.panes.get_mut(self.selected) {
+                if let Some(pane) = self.scroll_target() {
                     pane.scroll_end();
                 }
             }
@@ -422,8 +1007,47 @@ impl TuiState {
                     let _ = std::io::stdout().execute(DisableMouseCapture);
                 }
             }
-            KeyCode::Char('a') => {
+            KeyCode::Char('a') | KeyCode::Char('/') => {
+                self.input_mode = true;
+                self.input_kind = InputKind::AddPattern;
+            }
+            KeyCode::Char(':') => {
                 self.input_mode = true;
+                self.input_kind = InputKind::JumpToPod;
+                self.jump_choice = 0;
+            }
+            KeyCode::Char('e') => {
+                self.follow_errors = !self.follow_errors;
+            }
+            KeyCode::Char('o') => {
+                self.auto_focus = !self.auto_focus;
+            }
+            KeyCode::Char('E') => {
+                self.dashboard_open = true;
+                self.dashboard_row = 0;
+            }
+            KeyCode::Char('J') => {
+                self.json_logs = !self.json_logs;
+            }
+            KeyCode::Char('n') => {
+                self.show_node = !self.show_node;
+            }
+            KeyCode::Char('w') => {
+                if let Some(pane) = self.panes.get_mut(self.selected) {
+                    pane.wrap = !pane.wrap;
+                    pane.h_scroll = 0;
+                    self.wrap_new_panes = pane.wrap;
+                }
+            }
+            KeyCode::Char('y') => {
+                if let Some(pane) = self.panes.get(self.selected) {
+                    return KeyAction::Copy(pane.key.clone());
+                }
+            }
+            KeyCode::Char('s') => {
+                if self.selected < self.panes.len() {
+                    return KeyAction::SaveLog(self.selected);
+                }
             }
             KeyCode::Char('d') => {
                 if !self.panes.is_empty() {
@@ -530,6 +1154,21 @@ mod tests {
         assert_eq!(pane.lines.front().unwrap(), "line 1");
     }
 
+    #[test]
+    fn test_pod_pane_parses_lines_once_and_stays_in_sync() {
+        let mut pane = make_pane("ns/pod", 0);
+        for i in 0..MAX_LOG_LINES + 10 {
+            pane.push_line(format!("\x1b[31merr {i}\x1b[0m ok"));
+        }
+        assert_eq!(pane.parsed.len(), pane.lines.len());
+
+        let first: Vec<Line> = pane.styled_lines(0, 1).collect();
+        let spans: Vec<&str> = first[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(spans.concat(), "err 10 ok");
+        assert_eq!(first[0].spans[0].style.fg, Some(Color::Red));
+        assert_eq!(pane.styled_lines(MAX_LOG_LINES - 2, 5).count(), 2);
+    }
+
     #[test]
     fn test_pod_pane_scroll_offset_following() {
         let pane = make_pane("ns/pod", 100);
@@ -563,6 +1202,31 @@ mod tests {
         assert_eq!(pane.scroll_offset(20), 80);
     }
 
+    #[test]
+    fn test_pod_pane_scroll_anchor_survives_height_change() {
+        let mut pane = make_pane("ns/pod", 100);
+        pane.scroll_up_by(20, 15);
+        let top = pane.scroll_offset(20) as usize;
+        assert_eq!(top, 65);
+
+        for inner_height in [10, 40, 99] {
+            assert_eq!(pane.scroll_offset(inner_height) as usize, top);
+        }
+        assert_eq!(pane.lines[top], "line 65");
+    }
+
+    #[test]
+    fn test_pod_pane_scroll_anchor_follows_line_when_buffer_trims() {
+        let mut pane = make_pane("ns/pod", MAX_LOG_LINES);
+        pane.scroll_up = Some(100);
+        let anchored = pane.lines[100].clone();
+        pane.push_line("new 1".to_string());
+        pane.push_line("new 2".to_string());
+        let top = pane.scroll_offset(40) as usize;
+        assert_eq!(pane.lines[top], anchored);
+        assert_eq!(pane.scroll_offset(12) as usize, top);
+    }
+
     #[test]
     fn test_pod_pane_is_following() {
         let mut pane = make_pane("ns/pod", 10);
@@ -716,6 +1380,92 @@ mod tests {
         assert!(state.input_mode);
     }
 
+    #[test]
+    fn test_handle_key_slash_enters_input_mode() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["ns/a"], 0);
+
+        press_key(&mut state, KeyCode::Char('/'), &running, &closed);
+        assert!(state.input_mode);
+
+        // While typing, shortcut keys are pattern text rather than commands.
+        state.handle_input_key(KeyCode::Char('q'), 48);
+        state.handle_input_key(KeyCode::Char('/'), 48);
+        assert!(state.input_mode);
+        assert_eq!(state.input_buffer, "q/");
+    }
+
+    #[test]
+    fn test_jump_picker_selects_fuzzy_match_on_another_tab() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(
+            &["ns/api-0", "ns/api-1", "ns/web-0", "ns/db-0", "ns/worker-0"],
+            0,
+        );
+
+        press_key(&mut state, KeyCode::Char(':'), &running, &closed);
+        assert_eq!(state.input_kind, InputKind::JumpToPod);
+        assert_eq!(state.jump_candidates().len(), 5);
+        for c in "wkr".chars() {
+            state.handle_input_key(KeyCode::Char(c), 48);
+        }
+        assert_eq!(state.jump_candidates(), vec!["ns/worker-0"]);
+
+        state.handle_input_key(KeyCode::Enter, 48);
+        assert!(!state.input_mode);
+        assert_eq!(state.selected, 4);
+        assert_eq!(state.current_tab, 1);
+    }
+
+    #[test]
+    fn test_jump_picker_arrows_choose_and_esc_cancels() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["ns/api-0", "ns/api-1", "ns/web-0"], 0);
+
+        press_key(&mut state, KeyCode::Char(':'), &running, &closed);
+        state.handle_input_key(KeyCode::Char('q'), 48);
+        assert!(state.jump_candidates().is_empty());
+        state.handle_input_key(KeyCode::Enter, 48);
+        assert!(state.input_mode);
+        state.handle_input_key(KeyCode::Esc, 48);
+        assert!(!state.input_mode);
+        assert!(state.input_buffer.is_empty());
+
+        press_key(&mut state, KeyCode::Char(':'), &running, &closed);
+        state.handle_input_key(KeyCode::Down, 48);
+        state.handle_input_key(KeyCode::Down, 48);
+        state.handle_input_key(KeyCode::Down, 48);
+        assert_eq!(state.jump_choice, 2);
+        state.handle_input_key(KeyCode::Up, 48);
+        state.handle_input_key(KeyCode::Enter, 48);
+        assert_eq!(state.selected, 1);
+    }
+
+    #[test]
+    fn test_dashboard_sorts_and_jumps_to_pane() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["ns/a", "ns/b", "ns/c"], 0);
+        state.panes[1].error_count = 7;
+        state.panes[2].error_count = 2;
+
+        press_key(&mut state, KeyCode::Char('E'), &running, &closed);
+        assert!(state.dashboard_open);
+        assert_eq!(state.dashboard_rows(), vec![1, 2, 0]);
+
+        state.handle_dashboard_key(KeyCode::Down, 48);
+        state.handle_dashboard_key(KeyCode::Enter, 48);
+        assert!(!state.dashboard_open);
+        assert_eq!(state.selected, 2);
+
+        state.handle_dashboard_key(KeyCode::Char('o'), 48);
+        assert_eq!(state.dashboard_sort, DashboardSort::Name);
+        assert_eq!(state.dashboard_rows(), vec![0, 1, 2]);
+    }
+
     #[test]
     fn test_scroll_to_scrollbar_pos_outside_scrollbar_col() {
         let rect = Rect::new(0, 0, 80, 20);
@@ -771,6 +1521,82 @@ mod tests {
         assert_eq!(state.panes[1].scroll_up, Some(auto.saturating_sub(3)));
     }
 
+    fn mouse(
+        kind: MouseEventKind,
+        row: u16,
+        modifiers: KeyModifiers,
+    ) -> crossterm::event::MouseEvent {
+        crossterm::event::MouseEvent {
+            kind,
+            column: 10,
+            row,
+            modifiers,
+        }
+    }
+
+    #[test]
+    fn test_drag_border_resizes_stacked_panes() {
+        let mut state = make_state(&["ns/a", "ns/b"], 10);
+        state.pane_rects = vec![
+            (0, Rect::new(0, 0, 80, 20)),
+            (1, Rect::new(0, 20, 80, 20)),
+        ];
+        let left = MouseButton::Left;
+
+        state.handle_mouse(mouse(MouseEventKind::Down(left), 19, KeyModifiers::NONE));
+        state.handle_mouse(mouse(MouseEventKind::Drag(left), 29, KeyModifiers::NONE));
+        state.handle_mouse(mouse(MouseEventKind::Up(left), 29, KeyModifiers::NONE));
+        assert_eq!(state.pane_weight(0), 150);
+        assert_eq!(state.pane_weight(1), 50);
+        assert_eq!(state.selected, 0);
+
+        // Dragging past the end keeps the lower pane at its minimum height.
+        state.handle_mouse(mouse(MouseEventKind::Down(left), 20, KeyModifiers::NONE));
+        state.handle_mouse(mouse(MouseEventKind::Drag(left), 60, KeyModifiers::NONE));
+        assert_eq!(state.pane_weight(0), 185);
+        assert_eq!(state.pane_weight(1), 15);
+
+        // Weights follow the pane, not its position.
+        state.panes.swap(0, 1);
+        state.rebuild_index();
+        assert_eq!(state.pane_weight(0), 15);
+    }
+
+    #[test]
+    fn test_drag_inside_pane_does_not_resize() {
+        let mut state = make_state(&["ns/a", "ns/b"], 10);
+        state.pane_rects = vec![
+            (0, Rect::new(0, 0, 80, 20)),
+            (1, Rect::new(0, 20, 80, 20)),
+        ];
+        let left = MouseButton::Left;
+        state.handle_mouse(mouse(MouseEventKind::Down(left), 10, KeyModifiers::NONE));
+        state.handle_mouse(mouse(MouseEventKind::Drag(left), 30, KeyModifiers::NONE));
+        assert!(state.pane_weights.is_empty());
+    }
+
+    #[test]
+    fn test_shift_wheel_pans_unwrapped_pane() {
+        let mut state = make_state(&["ns/a", "ns/b"], 50);
+        state.pane_rects = vec![
+            (0, Rect::new(0, 0, 80, 20)),
+            (1, Rect::new(0, 20, 80, 20)),
+        ];
+
+        // Wrapping panes keep scrolling vertically.
+        state.handle_mouse(mouse(MouseEventKind::ScrollDown, 25, KeyModifiers::SHIFT));
+        assert_eq!(state.panes[1].h_scroll, 0);
+
+        state.panes[1].wrap = false;
+        state.handle_mouse(mouse(MouseEventKind::ScrollDown, 25, KeyModifiers::SHIFT));
+        state.handle_mouse(mouse(MouseEventKind::ScrollRight, 25, KeyModifiers::NONE));
+        assert_eq!(state.panes[1].h_scroll, 2 * H_SCROLL_STEP);
+        state.handle_mouse(mouse(MouseEventKind::ScrollUp, 25, KeyModifiers::SHIFT));
+        assert_eq!(state.panes[1].h_scroll, H_SCROLL_STEP);
+        assert_eq!(state.selected, 1);
+        assert!(state.panes[1].is_following());
+    }
+
     #[test]
     fn test_handle_mouse_click_selects_pane() {
         let mut state = make_state(&["ns/a", "ns/b"], 10);
@@ -799,6 +1625,46 @@ mod tests {
         assert_eq!(state.max_panes_per_tab(6), 1);
     }
 
+    #[test]
+    fn test_max_panes_per_tab_custom_min_height() {
+        let mut state = TuiState::new();
+        state.min_pane_height = 6;
+        assert_eq!(state.max_panes_per_tab(48), 8);
+        assert_eq!(state.max_panes_per_tab(24), 4);
+        assert_eq!(state.max_panes_per_tab(5), 1);
+        state.min_pane_height = 24;
+        assert_eq!(state.max_panes_per_tab(48), 2);
+        assert_eq!(state.max_panes_per_tab(12), 1);
+    }
+
+    #[test]
+    fn test_total_tabs_custom_min_height() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"], 0);
+        state.min_pane_height = 6;
+        assert_eq!(state.total_tabs(48), 2);
+        assert_eq!(state.visible_indices(48), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        state.current_tab = 1;
+        assert_eq!(state.visible_indices(48), vec![8, 9]);
+    }
+
+    #[test]
+    fn test_single_tab_keeps_all_panes_on_one_tab() {
+        let names: Vec<String> = (0..10).map(|i| format!("p{}", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut state = make_state(&names, 0);
+        state.single_tab = true;
+        assert_eq!(state.total_tabs(24), 1);
+        assert_eq!(state.visible_indices(48), (0..10).collect::<Vec<_>>());
+        assert_eq!(state.visible_indices(12), vec![0, 1, 2, 3]);
+
+        state.selected = 6;
+        assert_eq!(state.visible_indices(12), vec![3, 4, 5, 6]);
+        state.selected = 9;
+        assert_eq!(state.visible_indices(12), vec![6, 7, 8, 9]);
+        state.ensure_selected_visible(12);
+        assert_eq!(state.current_tab, 0);
+    }
+
     #[test]
     fn test_total_tabs() {
         let state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"], 0);
@@ -871,6 +1737,69 @@ mod tests {
         assert_eq!(state.current_tab, 0);
     }
 
+    #[test]
+    fn test_nav_wrap_at_boundaries() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h"], 0);
+        state.nav_mode = Some(NavMode::Wrap);
+
+        press_key(&mut state, KeyCode::BackTab, &running, &closed);
+        assert_eq!(state.selected, 7);
+        assert_eq!(state.current_tab, 1);
+
+        press_key(&mut state, KeyCode::Tab, &running, &closed);
+        assert_eq!(state.selected, 0);
+        assert_eq!(state.current_tab, 0);
+
+        press_key(&mut state, KeyCode::Left, &running, &closed);
+        assert_eq!(state.current_tab, 1);
+        assert_eq!(state.selected, 4);
+
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.current_tab, 0);
+        assert_eq!(state.selected, 0);
+    }
+
+    #[test]
+    fn test_nav_clamp_at_boundaries() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h"], 0);
+        state.nav_mode = Some(NavMode::Clamp);
+
+        press_key(&mut state, KeyCode::BackTab, &running, &closed);
+        assert_eq!(state.selected, 0);
+
+        press_key(&mut state, KeyCode::Left, &running, &closed);
+        assert_eq!(state.current_tab, 0);
+
+        state.selected = 7;
+        state.ensure_selected_visible(48);
+        press_key(&mut state, KeyCode::Tab, &running, &closed);
+        assert_eq!(state.selected, 7);
+        assert_eq!(state.current_tab, 1);
+
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.current_tab, 1);
+        assert_eq!(state.selected, 7);
+    }
+
+    #[test]
+    fn test_expanded_cycle_clamps() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d"], 0);
+        state.nav_mode = Some(NavMode::Clamp);
+        state.expanded = true;
+        state.selected = 3;
+
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.selected, 3);
+        press_key(&mut state, KeyCode::Left, &running, &closed);
+        assert_eq!(state.selected, 2);
+    }
+
     #[test]
     fn test_tab_auto_switch_on_cycle() {
         let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
@@ -883,6 +1812,347 @@ mod tests {
         assert_eq!(state.current_tab, 1);
     }
 
+    #[test]
+    fn test_expanded_cycle_stays_within_tab() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h"], 0);
+        state.current_tab = 1;
+        state.selected = 4;
+        state.expanded = true;
+
+        for expected in [5, 6, 7, 4, 5] {
+            press_key(&mut state, KeyCode::Right, &running, &closed);
+            assert_eq!(state.selected, expected);
+            assert_eq!(state.current_tab, 1);
+            assert!(state.expanded);
+        }
+
+        for expected in [4, 7, 6] {
+            press_key(&mut state, KeyCode::Char('['), &running, &closed);
+            assert_eq!(state.selected, expected);
+            assert_eq!(state.current_tab, 1);
+        }
+
+        press_key(&mut state, KeyCode::Char(']'), &running, &closed);
+        assert_eq!(state.selected, 7);
+        assert_eq!(state.visible_indices(48), vec![7]);
+    }
+
+    #[test]
+    fn test_expanded_cycle_partial_tab() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 0);
+        state.current_tab = 1;
+        state.selected = 5;
+        state.expanded = true;
+
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.selected, 4);
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.selected, 5);
+    }
+
+    #[test]
+    fn test_apply_initial_view_tab() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h"], 0);
+        let view = InitialView {
+            tab: Some(2),
+            select: None,
+        };
+        let warnings = state.apply_initial_view(&view, 48);
+        assert!(warnings.is_empty());
+        assert_eq!(state.current_tab, 1);
+        assert_eq!(state.selected, 4);
+    }
+
+    #[test]
+    fn test_apply_initial_view_tab_out_of_range_clamps() {
+        let mut state = make_state(&["a", "b", "c", "d", "e"], 0);
+        for (tab, expected_tab) in [(9, 1), (0, 0)] {
+            let view = InitialView {
+                tab: Some(tab),
+                select: None,
+            };
+            let warnings = state.apply_initial_view(&view, 48);
+            assert_eq!(warnings.len(), 1, "tab {tab}");
+            assert_eq!(state.current_tab, expected_tab);
+        }
+    }
+
+    #[test]
+    fn test_apply_initial_view_select() {
+        let mut state = make_state(&["ns/a", "ns/b", "ns/c", "ns/d", "ns/e", "ns/f"], 0);
+        let view = InitialView {
+            tab: None,
+            select: Some("ns/f".to_string()),
+        };
+        assert!(state.apply_initial_view(&view, 48).is_empty());
+        assert_eq!(state.selected, 5);
+        assert_eq!(state.current_tab, 1);
+
+        let view = InitialView {
+            tab: None,
+            select: Some("b".to_string()),
+        };
+        assert!(state.apply_initial_view(&view, 48).is_empty());
+        assert_eq!(state.selected, 1);
+        assert_eq!(state.current_tab, 0);
+    }
+
+    #[test]
+    fn test_apply_initial_view_unknown_select_warns() {
+        let mut state = make_state(&["ns/a", "ns/b"], 0);
+        let view = InitialView {
+            tab: None,
+            select: Some("ns/missing".to_string()),
+        };
+        let warnings = state.apply_initial_view(&view, 48);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(state.selected, 0);
+    }
+
+    #[test]
+    fn test_tab_activity_marks_and_clears() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f", "g", "h"], 0);
+        state.panes[5].mark_unseen(false);
+        state.panes[1].mark_unseen(true);
+        state.panes[1].mark_unseen(false);
+
+        assert_eq!(state.tab_activity(48), vec![Activity::Errors, Activity::Lines]);
+
+        state.mark_visible_seen(48);
+        assert_eq!(state.tab_activity(48), vec![Activity::None, Activity::Lines]);
+
+        state.current_tab = 1;
+        state.mark_visible_seen(48);
+        assert_eq!(state.tab_activity(48), vec![Activity::None, Activity::None]);
+    }
+
+    #[test]
+    fn test_follow_error_switches_tab() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 0);
+        state.follow_errors = true;
+        let now = Instant::now();
+
+        assert!(state.follow_error(5, now, 48));
+        assert_eq!(state.selected, 5);
+        assert_eq!(state.current_tab, 1);
+    }
+
+    #[test]
+    fn test_follow_error_disabled_by_default() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 0);
+        assert!(!state.follow_error(5, Instant::now(), 48));
+        assert_eq!(state.selected, 0);
+    }
+
+    #[test]
+    fn test_follow_error_debounced() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 0);
+        state.follow_errors = true;
+        let now = Instant::now();
+
+        assert!(state.follow_error(5, now, 48));
+        assert!(!state.follow_error(1, now + Duration::from_secs(1), 48));
+        assert_eq!(state.selected, 5);
+        assert!(state.follow_error(1, now + FOLLOW_ERRORS_DEBOUNCE, 48));
+        assert_eq!(state.selected, 1);
+    }
+
+    #[test]
+    fn test_follow_error_waits_for_idle_user() {
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 0);
+        state.follow_errors = true;
+        let now = Instant::now();
+        state.last_user_input = Some(now);
+
+        assert!(!state.follow_error(5, now + Duration::from_secs(1), 48));
+        assert!(state.follow_error(5, now + FOLLOW_ERRORS_IDLE, 48));
+    }
+
+    #[test]
+    fn test_auto_focus_selects_latest_pane_when_user_idle() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 0);
+        let now = Instant::now();
+        assert!(!state.auto_focus_on(5, now, 48));
+
+        press_key(&mut state, KeyCode::Char('o'), &running, &closed);
+        assert!(state.auto_focus);
+        assert!(state.auto_focus_on(5, now, 48));
+        assert_eq!(state.selected, 5);
+        assert_eq!(state.current_tab, 1);
+        assert!(state.auto_focus_on(1, now, 48));
+        assert_eq!(state.current_tab, 0);
+
+        state.last_user_input = Some(now);
+        assert!(!state.auto_focus_on(3, now + Duration::from_secs(1), 48));
+        assert_eq!(state.selected, 1);
+        assert!(state.auto_focus_on(3, now + FOLLOW_ERRORS_IDLE, 48));
+    }
+
+    #[test]
+    fn test_handle_key_toggles_follow_errors() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a"], 0);
+
+        press_key(&mut state, KeyCode::Char('e'), &running, &closed);
+        assert!(state.follow_errors);
+        press_key(&mut state, KeyCode::Char('e'), &running, &closed);
+        assert!(!state.follow_errors);
+    }
+
+    #[test]
+    fn test_pod_pane_log_export() {
+        let alive = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let mut pane = PodPane::new("ns/api-0".to_string(), Color::Green, alive);
+        pane.push_line("\x1b[31mERROR\x1b[0m boom".to_string());
+        pane.push_line("plain".to_string());
+
+        assert_eq!(pane.log_file_name(1760000000), "ns_api-0-1760000000.log");
+        assert_eq!(pane.plain_text(), "ERROR boom\nplain\n");
+    }
+
+    #[test]
+    fn test_handle_key_save_log_targets_selected_pane() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b"], 0);
+        state.selected = 1;
+
+        let action = press_key(&mut state, KeyCode::Char('s'), &running, &closed);
+        assert!(matches!(action, KeyAction::SaveLog(1)));
+
+        let mut empty = TuiState::new();
+        let action = press_key(&mut empty, KeyCode::Char('s'), &running, &closed);
+        assert!(matches!(action, KeyAction::None));
+    }
+
+    #[test]
+    fn test_handle_key_copy_selected_pod() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["ns/a", "ns/b"], 0);
+        state.selected = 1;
+
+        let action = press_key(&mut state, KeyCode::Char('y'), &running, &closed);
+        assert!(matches!(action, KeyAction::Copy(ref key) if key == "ns/b"));
+    }
+
+    #[test]
+    fn test_escape_control() {
+        assert_eq!(escape_control("\x1b[31merr\x1b[0m"), "^[[31merr^[[0m");
+        assert_eq!(escape_control("a\tb\rc\x7f"), "a\tb^Mc^?");
+        assert_eq!(escape_control("x\u{9b}y"), "x\\u{9b}y");
+        assert_eq!(escape_control("plain"), "plain");
+    }
+
+    #[test]
+    fn test_handle_key_toggles_json_logs() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a"], 0);
+
+        press_key(&mut state, KeyCode::Char('J'), &running, &closed);
+        assert!(state.json_logs);
+        press_key(&mut state, KeyCode::Char('J'), &running, &closed);
+        assert!(!state.json_logs);
+    }
+
+    #[test]
+    fn test_handle_key_toggles_wrap_and_pans() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b"], 0);
+        assert!(!state.pans_horizontally(48));
+
+        press_key(&mut state, KeyCode::Char('w'), &running, &closed);
+        assert!(!state.panes[0].wrap);
+        assert!(state.panes[1].wrap);
+        assert!(state.pans_horizontally(48));
+
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.panes[0].h_scroll, 2 * H_SCROLL_STEP);
+        press_key(&mut state, KeyCode::Left, &running, &closed);
+        assert_eq!(state.panes[0].h_scroll, H_SCROLL_STEP);
+        assert_eq!(state.current_tab, 0);
+
+        // New panes inherit the last choice.
+        state.add_pane(make_pane("c", 0));
+        assert!(!state.panes[2].wrap);
+
+        press_key(&mut state, KeyCode::Char('w'), &running, &closed);
+        assert!(state.panes[0].wrap);
+        assert_eq!(state.panes[0].h_scroll, 0);
+        assert!(state.wrap_new_panes);
+    }
+
+    #[test]
+    fn test_merged_view_scrolls_its_own_buffer() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b"], 0);
+        for i in 0..50 {
+            state.push_merged(if i % 2 == 0 { "a" } else { "b" }, format!("line {i}"));
+        }
+
+        press_key(&mut state, KeyCode::Char('g'), &running, &closed);
+        assert!(state.merged_view);
+        press_key(&mut state, KeyCode::Up, &running, &closed);
+        assert_eq!(state.merged.scroll_up, Some(29));
+        assert!(state.panes[0].is_following());
+
+        press_key(&mut state, KeyCode::Esc, &running, &closed);
+        assert!(!state.merged_view);
+    }
+
+    #[test]
+    fn test_merged_buffer_is_capped_with_its_sources() {
+        let mut state = TuiState::new();
+        for i in 0..MAX_LOG_LINES + 3 {
+            state.push_merged(&format!("pod-{i}"), format!("line {i}"));
+        }
+        assert_eq!(state.merged.lines.len(), MAX_LOG_LINES);
+        assert_eq!(state.merged_sources.len(), MAX_LOG_LINES);
+        assert_eq!(state.merged_sources[0], "pod-3");
+        assert_eq!(state.merged.lines[0], "line 3");
+    }
+
+    #[test]
+    fn test_arrows_switch_tabs_when_several_tabs_even_without_wrap() {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a", "b", "c", "d", "e"], 0);
+        press_key(&mut state, KeyCode::Char('w'), &running, &closed);
+        assert!(!state.pans_horizontally(48));
+
+        press_key(&mut state, KeyCode::Right, &running, &closed);
+        assert_eq!(state.current_tab, 1);
+        assert_eq!(state.panes[0].h_scroll, 0);
+    }
+
+    #[test]
+    fn test_title_label_toggles_node() {
+        let alive = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let mut pane = PodPane::new("ns/api-0".to_string(), Color::Green, alive);
+        assert_eq!(pane.title_label(true), "ns/api-0");
+        pane.node = Some("node-a".to_string());
+        assert_eq!(pane.title_label(true), "ns/api-0 @ node-a");
+        assert_eq!(pane.title_label(false), "ns/api-0");
+
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let closed = Arc::new(Mutex::new(HashSet::new()));
+        let mut state = make_state(&["a"], 0);
+        assert!(state.show_node);
+        press_key(&mut state, KeyCode::Char('n'), &running, &closed);
+        assert!(!state.show_node);
+    }
+
     #[test]
     fn test_few_panes_no_tabs() {
         let state = make_state(&["a", "b"], 0);
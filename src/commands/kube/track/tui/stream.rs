@@ -0,0 +1,134 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::super::{is_pod_gone, LogFilter, ReconnectBackoff};
+use super::event::TrackEvent;
+use super::traits::LogStreamSpawnOpts;
+
+pub(super) fn should_stop(running: &Arc<AtomicBool>, alive: &Arc<AtomicBool>) -> bool {
+    !running.load(Ordering::SeqCst) || !alive.load(Ordering::SeqCst)
+}
+
+/// Runs the command built by `make_cmd` and sends its output lines to the pane `key`,
+/// restarting it after it exits until the pane is closed, the TUI stops, or the command
+/// reports that the pod no longer exists. Its stderr is shown in the pane when
+/// `include_stderr` is set.
+pub fn follow_command<F>(key: String, opts: LogStreamSpawnOpts, include_stderr: bool, make_cmd: F)
+where
+    F: Fn() -> Command + Send + 'static,
+{
+    let LogStreamSpawnOpts {
+        filter,
+        running,
+        alive,
+        active_pods,
+        tx,
+    } = opts;
+
+    thread::spawn(move || {
+        let mut backoff = ReconnectBackoff::default();
+        loop {
+            if should_stop(&running, &alive) {
+                break;
+            }
+
+            let started = Instant::now();
+            let result = make_cmd()
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn();
+
+            let mut gone = false;
+            match result {
+                Ok(mut child) => {
+                    let stderr_reader = child.stderr.take().map(|stderr| {
+                        let forward = include_stderr.then(|| (filter.clone(), tx.clone()));
+                        read_stderr(stderr, key.clone(), forward)
+                    });
+                    if let Some(stdout) = child.stdout.take() {
+                        let reader = BufReader::new(stdout);
+                        let mut trace = filter.trace_state();
+
+                        for line in reader.lines() {
+                            if should_stop(&running, &alive) {
+                                let _ = child.kill();
+                                break;
+                            }
+
+                            match line {
+                                Ok(text) => {
+                                    if !filter.keeps(&text, &mut trace) {
+                                        continue;
+                                    }
+                                    if tx
+                                        .send(TrackEvent::LogLine {
+                                            pod_key: key.clone(),
+                                            text,
+                                        })
+                                        .is_err()
+                                    {
+                                        break;
+                                    }
+                                }
+                                Err(_) => break,
+                            }
+                        }
+                    }
+                    let _ = child.wait();
+                    if let Some(handle) = stderr_reader {
+                        gone = handle.join().unwrap_or(false);
+                    }
+                }
+                Err(_) => break,
+            }
+
+            if gone {
+                let _ = tx.send(TrackEvent::PodGone {
+                    pod_key: key.clone(),
+                });
+                break;
+            }
+
+            let resume_at = Instant::now() + backoff.next_delay(started.elapsed());
+            while Instant::now() < resume_at && !should_stop(&running, &alive) {
+                thread::sleep(Duration::from_millis(200));
+            }
+        }
+
+        active_pods.lock().unwrap().remove(&key);
+    });
+}
+
+/// Drains a follower's stderr, forwarding kept lines to the pane when `forward` is set.
+/// The thread yields whether any line said the pod is gone.
+fn read_stderr<R: Read + Send + 'static>(
+    reader: R,
+    key: String,
+    forward: Option<(LogFilter, mpsc::Sender<TrackEvent>)>,
+) -> thread::JoinHandle<bool> {
+    thread::spawn(move || {
+        let mut gone = false;
+        let mut trace = forward.as_ref().map(|(filter, _)| filter.trace_state());
+        for text in BufReader::new(reader).lines().map_while(|l| l.ok()) {
+            gone |= is_pod_gone(&text);
+            let (Some((filter, tx)), Some(trace)) = (&forward, trace.as_mut()) else {
+                continue;
+            };
+            if !filter.keeps(&text, trace) {
+                continue;
+            }
+            let event = TrackEvent::LogLine {
+                pod_key: key.clone(),
+                text,
+            };
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+        gone
+    })
+}
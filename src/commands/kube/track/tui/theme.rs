@@ -1,3 +1,4 @@
+use anyhow::{Context, Result};
 use ratatui::style::Color;
 
 const TUI_COLORS: &[Color] = &[
@@ -22,22 +23,99 @@ const TITLE_COLORS: &[Color] = &[
     Color::Rgb(0xff, 0x99, 0xff),
 ];
 
-#[derive(Clone)]
+/// Okabe-Ito palette, distinguishable with red-green color blindness.
+const DEUTERANOPIA_COLORS: &[Color] = &[
+    Color::Rgb(0xe6, 0x9f, 0x00),
+    Color::Rgb(0x56, 0xb4, 0xe9),
+    Color::Rgb(0x00, 0x9e, 0x73),
+    Color::Rgb(0xf0, 0xe4, 0x42),
+    Color::Rgb(0x00, 0x72, 0xb2),
+    Color::Rgb(0xd5, 0x5e, 0x00),
+    Color::Rgb(0xcc, 0x79, 0xa7),
+    Color::Rgb(0xbb, 0xbb, 0xbb),
+];
+
+const MONO_COLORS: &[Color] = &[
+    Color::Rgb(0xff, 0xff, 0xff),
+    Color::Rgb(0xe0, 0xe0, 0xe0),
+    Color::Rgb(0xc8, 0xc8, 0xc8),
+    Color::Rgb(0xb0, 0xb0, 0xb0),
+    Color::Rgb(0x98, 0x98, 0x98),
+    Color::Rgb(0x80, 0x80, 0x80),
+    Color::Rgb(0x68, 0x68, 0x68),
+    Color::Rgb(0x50, 0x50, 0x50),
+];
+
+const HIGH_CONTRAST_COLORS: &[Color] = &[
+    Color::Rgb(0xff, 0xff, 0xff),
+    Color::Rgb(0xff, 0xff, 0x00),
+    Color::Rgb(0x00, 0xff, 0xff),
+    Color::Rgb(0xff, 0x00, 0xff),
+    Color::Rgb(0x00, 0xff, 0x00),
+    Color::Rgb(0xff, 0x88, 0x00),
+    Color::Rgb(0x66, 0xaa, 0xff),
+    Color::Rgb(0xff, 0x66, 0x99),
+];
+
+/// The built-in palettes selectable with `kube track --theme`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Deuteranopia,
+    Mono,
+    HighContrast,
+}
+
+#[derive(Clone, Debug)]
 pub struct Theme {
-    pub pane_colors: &'static [Color],
-    pub title_colors: &'static [Color],
+    pub pane_colors: Vec<Color>,
+    pub title_colors: Vec<Color>,
 }
 
 impl Default for Theme {
     fn default() -> Self {
-        Self {
-            pane_colors: TUI_COLORS,
-            title_colors: TITLE_COLORS,
-        }
+        Self::named(ThemeName::Default)
     }
 }
 
 impl Theme {
+    pub fn named(name: ThemeName) -> Self {
+        let (pane_colors, title_colors) = match name {
+            ThemeName::Default => (TUI_COLORS, TITLE_COLORS),
+            ThemeName::Deuteranopia => (DEUTERANOPIA_COLORS, DEUTERANOPIA_COLORS),
+            ThemeName::Mono => (MONO_COLORS, MONO_COLORS),
+            ThemeName::HighContrast => (HIGH_CONTRAST_COLORS, HIGH_CONTRAST_COLORS),
+        };
+        Self {
+            pane_colors: pane_colors.to_vec(),
+            title_colors: title_colors.to_vec(),
+        }
+    }
+
+    /// The `--theme` given on the command line, else the config `palette`, else the default.
+    pub fn resolve(name: Option<ThemeName>, palette: &[String]) -> Result<Self> {
+        match name {
+            Some(name) => Ok(Self::named(name)),
+            None => Self::default().with_palette(palette),
+        }
+    }
+
+    /// Replaces both palettes with `palette` (hex colors like `#e69f00`), when it's non-empty.
+    pub fn with_palette(self, palette: &[String]) -> Result<Self> {
+        if palette.is_empty() {
+            return Ok(self);
+        }
+        let colors = palette
+            .iter()
+            .map(|hex| parse_hex_color(hex))
+            .collect::<Result<Vec<Color>>>()?;
+        Ok(Self {
+            pane_colors: colors.clone(),
+            title_colors: colors,
+        })
+    }
+
     pub fn pane_color(&self, index: usize) -> Color {
         self.pane_colors[index % self.pane_colors.len()]
     }
@@ -46,3 +124,78 @@ impl Theme {
         self.title_colors[index % self.title_colors.len()]
     }
 }
+
+/// Parses `#rrggbb` (the `#` is optional) into an RGB color.
+pub fn parse_hex_color(hex: &str) -> Result<Color> {
+    let digits = hex.trim().trim_start_matches('#');
+    let channel = |i: usize| {
+        digits
+            .get(i..i + 2)
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+    };
+    let rgb = (digits.len() == 6)
+        .then(|| Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?)))
+        .flatten();
+    rgb.with_context(|| format!("Invalid color {hex:?}; expected a hex color like #e69f00"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_each_theme_has_distinct_colors_for_first_eight_panes() {
+        for name in ThemeName::value_variants() {
+            let theme = Theme::named(*name);
+            let panes: HashSet<String> = (0..8)
+                .map(|i| format!("{:?}", theme.pane_color(i)))
+                .collect();
+            let titles: HashSet<String> = (0..8)
+                .map(|i| format!("{:?}", theme.title_color(i)))
+                .collect();
+            assert_eq!(panes.len(), 8, "{name:?} pane colors repeat");
+            assert_eq!(titles.len(), 8, "{name:?} title colors repeat");
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(
+            parse_hex_color("#e69f00").unwrap(),
+            Color::Rgb(0xe6, 0x9f, 0x00)
+        );
+        assert_eq!(
+            parse_hex_color("56B4E9").unwrap(),
+            Color::Rgb(0x56, 0xb4, 0xe9)
+        );
+        assert!(parse_hex_color("#fff").is_err());
+        assert!(parse_hex_color("#gg0000").is_err());
+        assert!(parse_hex_color("#ééé").is_err());
+    }
+
+    #[test]
+    fn test_with_palette_overrides_colors() {
+        let theme = Theme::named(ThemeName::Mono)
+            .with_palette(&["#ff0000".to_string(), "#00ff00".to_string()])
+            .unwrap();
+        assert_eq!(theme.pane_color(0), Color::Rgb(0xff, 0x00, 0x00));
+        assert_eq!(theme.pane_color(3), Color::Rgb(0x00, 0xff, 0x00));
+        assert_eq!(theme.title_color(1), Color::Rgb(0x00, 0xff, 0x00));
+
+        let unchanged = Theme::named(ThemeName::Mono).with_palette(&[]).unwrap();
+        assert_eq!(unchanged.pane_colors, MONO_COLORS);
+        assert!(Theme::default().with_palette(&["red".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_prefers_flag_over_palette() {
+        let palette = vec!["#ff0000".to_string()];
+        let flagged = Theme::resolve(Some(ThemeName::Deuteranopia), &palette).unwrap();
+        assert_eq!(flagged.pane_colors, DEUTERANOPIA_COLORS);
+        let configured = Theme::resolve(None, &palette).unwrap();
+        assert_eq!(configured.pane_color(0), Color::Rgb(0xff, 0x00, 0x00));
+        assert_eq!(Theme::resolve(None, &[]).unwrap().pane_colors, TUI_COLORS);
+    }
+}
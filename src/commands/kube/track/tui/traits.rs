@@ -3,16 +3,42 @@ use regex::Regex;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
 
 use super::event::TrackEvent;
-use super::super::PodInfo;
+use super::super::{match_pod, LogFilter, PodInfo};
 
 pub trait PodDiscovery: Send + Sync {
-    fn find_matching_pods(&self, regexes: &[Regex]) -> Result<Vec<PodInfo>>;
+    /// Every pod currently visible, before matching against any pattern.
+    fn list_pods(&self) -> Result<Vec<PodInfo>>;
+
+    /// A listing that bypasses any cache, so the poll loop always sees current pods.
+    fn refresh_pods(&self) -> Result<Vec<PodInfo>> {
+        self.list_pods()
+    }
+
+    /// Hands each pod to `on_pod` as it is discovered; sources that can list incrementally
+    /// override this so followers start before the whole listing is in.
+    fn stream_pods(&self, on_pod: &mut dyn FnMut(PodInfo)) -> Result<()> {
+        self.list_pods()?.into_iter().for_each(on_pod);
+        Ok(())
+    }
+
+    fn stream_matching_pods(
+        &self,
+        regexes: &[Regex],
+        on_pod: &mut dyn FnMut(PodInfo),
+    ) -> Result<()> {
+        self.stream_pods(&mut |pod| {
+            if let Some(pod) = match_pod(pod, regexes) {
+                on_pod(pod);
+            }
+        })
+    }
 }
 
 pub struct LogStreamSpawnOpts {
-    pub err_only: bool,
+    pub filter: LogFilter,
     pub running: Arc<AtomicBool>,
     pub alive: Arc<AtomicBool>,
     pub active_pods: Arc<Mutex<HashSet<String>>>,
@@ -24,5 +50,10 @@ pub trait LogStreamSpawner: Send + Sync {
 }
 
 pub trait PatternToRegex: Send + Sync {
-    fn build(&self, pattern: &str) -> Regex;
+    fn build(&self, pattern: &str) -> Result<Regex>;
+}
+
+/// Waits between poll-loop passes, so tests can drive the loop one pass at a time.
+pub trait Sleeper: Send + Sync {
+    fn sleep(&self, duration: Duration);
 }
@@ -1,3 +1,5 @@
+pub mod completions;
+pub mod config;
 pub mod docker;
 pub mod git;
 pub mod help;
@@ -5,6 +7,7 @@ pub mod kube;
 pub mod self_cmd;
 pub mod setup;
 pub mod show;
+pub mod tail;
 pub mod update;
 pub mod watch;
 
@@ -1,6 +1,7 @@
-use crate::utils::{colors::Colors, spinner};
+use crate::utils::log::CommandExt;
+use crate::utils::{colors::Colors, packages, spinner};
 use anyhow::{Context, Result};
-use regex::Regex;
+use std::cmp::Ordering;
 use std::env;
 use std::path::Path;
 use std::process::Command;
@@ -66,38 +67,75 @@ fn pip_has_bashers() -> bool {
         .is_some()
 }
 
-pub fn run() -> Result<()> {
+/// Which release `self update` installs.
+#[derive(Debug, Default)]
+pub struct UpdateOptions {
+    /// Install exactly this version instead of the latest release
+    pub version: Option<String>,
+    /// Consider pre-releases when looking up the latest version
+    pub pre: bool,
+    /// Allow installing a version older than (or equal to) the current one
+    pub force: bool,
+    /// Only report whether a newer version exists; never install
+    pub check: bool,
+    /// With `check`, exit non-zero when an update is available
+    pub exit_code: bool,
+}
+
+pub fn run(options: &UpdateOptions) -> Result<()> {
     let kind = detect_install_kind();
     let mut colors = Colors::new();
 
-    let latest_version = if spinner::should_show_spinner() {
-        let mut sp = spinner::create_spinner("Checking for updates...");
-        let result = match kind {
-            InstallKind::Cargo => get_latest_version_crates_io(),
-            InstallKind::Pip => get_latest_version_pypi(),
-        };
-        spinner::stop_spinner(sp.as_mut());
-        result?
-    } else {
-        colors.green()?;
-        colors.print("Checking for updates...")?;
-        colors.reset()?;
-        colors.println("")?;
-        match kind {
-            InstallKind::Cargo => get_latest_version_crates_io()?,
-            InstallKind::Pip => get_latest_version_pypi()?,
+    let target_version = match &options.version {
+        Some(version) => version.trim_start_matches('v').to_string(),
+        None if spinner::should_show_spinner() => {
+            let mut sp = spinner::create_spinner("Checking for updates...");
+            let result = latest_version(kind, options.pre);
+            spinner::stop_spinner(sp.as_mut());
+            result?
+        }
+        None => {
+            colors.green()?;
+            colors.print("Checking for updates...")?;
+            colors.reset()?;
+            colors.println("")?;
+            latest_version(kind, options.pre)?
         }
     };
 
-    if latest_version == CURRENT_VERSION {
-        colors.green()?;
-        colors.println(&format!("Already up to date (v{})", CURRENT_VERSION))?;
+    if options.check {
+        let (message, outdated) = check_message(&target_version, CURRENT_VERSION);
+        if outdated {
+            colors.yellow()?;
+        } else {
+            colors.green()?;
+        }
+        colors.println(&message)?;
         colors.reset()?;
+        if outdated && options.exit_code {
+            colors.flush()?;
+            std::process::exit(1);
+        }
         return Ok(());
     }
 
+    match install_decision(&target_version, CURRENT_VERSION, options.force) {
+        InstallDecision::UpToDate => {
+            colors.green()?;
+            colors.println(&format!("Already up to date (v{})", CURRENT_VERSION))?;
+            colors.reset()?;
+            return Ok(());
+        }
+        InstallDecision::RefuseDowngrade => anyhow::bail!(
+            "v{} is older than the installed v{}; pass --force to downgrade",
+            target_version,
+            CURRENT_VERSION
+        ),
+        InstallDecision::Install => {}
+    }
+
     colors.green()?;
-    colors.print(&format!("New version available: v{}", latest_version))?;
+    colors.print(&format!("Installing v{}", target_version))?;
     colors.reset()?;
     colors.println("")?;
     colors.print(&format!("Current version: v{}", CURRENT_VERSION))?;
@@ -110,8 +148,14 @@ pub fn run() -> Result<()> {
             colors.reset()?;
             colors.println("")?;
             let status = Command::new("cargo")
-                .args(["install", CRATES_IO_CRATE, "--force"])
-                .status()
+                .args([
+                    "install",
+                    CRATES_IO_CRATE,
+                    "--version",
+                    &target_version,
+                    "--force",
+                ])
+                .logged_status()
                 .context("Failed to run cargo install")?;
             ("cargo install", status.success())
         }
@@ -120,8 +164,8 @@ pub fn run() -> Result<()> {
             colors.print("Installing via pip...")?;
             colors.reset()?;
             colors.println("")?;
-            let status = run_pip_upgrade();
-            ("pip install --upgrade", status)
+            let status = run_pip_install(&target_version);
+            ("pip install", status)
         }
     };
 
@@ -130,77 +174,152 @@ pub fn run() -> Result<()> {
     }
 
     colors.green()?;
-    colors.println(&format!("Successfully updated to v{}", latest_version))?;
+    colors.println(&format!("Successfully installed v{}", target_version))?;
     colors.reset()?;
 
     Ok(())
 }
 
-fn run_pip_upgrade() -> bool {
-    let pip = Command::new("pip")
-        .args(["install", "--upgrade", PYPI_PACKAGE])
-        .status();
-    if let Ok(s) = pip {
-        return s.success();
-    }
-    Command::new("uv")
-        .args(["pip", "install", "--upgrade", PYPI_PACKAGE])
-        .status()
-        .map(|s| s.success())
-        .unwrap_or(false)
+/// Splits `0.9.0-rc.1` (Cargo) or `0.9.0rc1` (PyPI) into the release and the pre-release tag.
+fn split_pre_release(version: &str) -> (&str, Option<&str>) {
+    let version = version.trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or(version);
+    let end = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let (release, pre) = version.split_at(end);
+    let pre = pre.trim_start_matches(['-', '.']);
+    (
+        release.trim_end_matches('.'),
+        (!pre.is_empty()).then_some(pre),
+    )
 }
 
-fn get_latest_version_crates_io() -> Result<String> {
-    let output = Command::new("curl")
-        .args([
-            "-s",
-            &format!("https://crates.io/api/v1/crates/{}", CRATES_IO_CRATE),
-        ])
-        .output()
-        .context("Failed to fetch latest version from crates.io")?;
+/// Orders pre-release identifiers like `rc.1` or `rc10`: numbers numerically, otherwise by
+/// their text and then any trailing number.
+fn cmp_pre_release(a: &str, b: &str) -> Ordering {
+    let key = |id: &str| {
+        let digits = id.len() - id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let (text, number) = id.split_at(id.len() - digits);
+        (text.to_string(), number.parse::<u64>().unwrap_or(0))
+    };
+    let ids_a = a.split('.').map(key);
+    let ids_b = b.split('.').map(key);
+    ids_a.cmp(ids_b)
+}
 
-    if !output.status.success() {
-        anyhow::bail!("Failed to fetch latest version");
+/// Version precedence where a pre-release sorts below its release, as in semver.
+fn cmp_release(a: &str, b: &str) -> Ordering {
+    let (release_a, pre_a) = split_pre_release(a);
+    let (release_b, pre_b) = split_pre_release(b);
+    packages::cmp_version(release_a, release_b).then_with(|| match (pre_a, pre_b) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(a), Some(b)) => cmp_pre_release(a, b),
+    })
+}
+
+/// The `--check` report, and whether `latest` is newer than `current`.
+fn check_message(latest: &str, current: &str) -> (String, bool) {
+    if cmp_release(latest, current) == Ordering::Greater {
+        (
+            format!("v{} available (current v{})", latest, current),
+            true,
+        )
+    } else {
+        (format!("Up to date (v{})", current), false)
     }
+}
 
-    let response = String::from_utf8(output.stdout)?;
-    let re = Regex::new(r#""newest_version"\s*:\s*"([^"]+)""#).context("Failed to create regex")?;
+#[derive(Debug, PartialEq, Eq)]
+enum InstallDecision {
+    Install,
+    UpToDate,
+    RefuseDowngrade,
+}
 
-    let version = re
-        .captures(&response)
-        .and_then(|caps| caps.get(1))
-        .map(|m| m.as_str().to_string())
-        .context("No newest_version in crates.io API response")?;
+/// Whether to install `target` over `current`; `force` reinstalls or downgrades anyway.
+fn install_decision(target: &str, current: &str, force: bool) -> InstallDecision {
+    match cmp_release(target, current) {
+        Ordering::Greater => InstallDecision::Install,
+        _ if force => InstallDecision::Install,
+        Ordering::Equal => InstallDecision::UpToDate,
+        Ordering::Less => InstallDecision::RefuseDowngrade,
+    }
+}
 
-    Ok(version)
+fn latest_version(kind: InstallKind, pre: bool) -> Result<String> {
+    match (kind, pre) {
+        (InstallKind::Cargo, false) => get_latest_version_crates_io(),
+        (InstallKind::Pip, false) => get_latest_version_pypi(),
+        (InstallKind::Cargo, true) => latest_version_crates_io_pre(&fetch_json(&format!(
+            "https://crates.io/api/v1/crates/{}",
+            CRATES_IO_CRATE
+        ))?)
+        .context("No max_version in crates.io API response"),
+        (InstallKind::Pip, true) => latest_version_pypi_pre(&fetch_json(&format!(
+            "https://pypi.org/pypi/{}/json",
+            PYPI_PACKAGE
+        ))?)
+        .context("No releases in PyPI API response"),
+    }
 }
 
-fn get_latest_version_pypi() -> Result<String> {
+fn fetch_json(url: &str) -> Result<serde_json::Value> {
     let output = Command::new("curl")
-        .args([
-            "-s",
-            &format!("https://pypi.org/pypi/{}/json", PYPI_PACKAGE),
-        ])
-        .output()
-        .context("Failed to fetch latest version from PyPI")?;
-
+        .args(["-s", url])
+        .logged_output()
+        .with_context(|| format!("Failed to fetch {}", url))?;
     if !output.status.success() {
-        anyhow::bail!("Failed to fetch latest version from PyPI");
+        anyhow::bail!("Failed to fetch {}", url);
+    }
+    serde_json::from_slice(&output.stdout).with_context(|| format!("Invalid JSON from {}", url))
+}
+
+/// crates.io's `max_version` is the highest version including pre-releases.
+fn latest_version_crates_io_pre(response: &serde_json::Value) -> Option<String> {
+    response["crate"]["max_version"]
+        .as_str()
+        .map(str::to_string)
+}
+
+/// PyPI's `info.version` skips pre-releases, so pick the highest key of `releases` instead.
+fn latest_version_pypi_pre(response: &serde_json::Value) -> Option<String> {
+    response["releases"]
+        .as_object()?
+        .keys()
+        .max_by(|a, b| cmp_release(a, b))
+        .cloned()
+}
+
+fn run_pip_install(version: &str) -> bool {
+    let requirement = format!("{}=={}", PYPI_PACKAGE, version);
+    let pip = Command::new("pip")
+        .args(["install", &requirement])
+        .logged_status();
+    if let Ok(s) = pip {
+        return s.success();
     }
+    Command::new("uv")
+        .args(["pip", "install", &requirement])
+        .logged_status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
 
-    let response = String::from_utf8(output.stdout)?;
-    let re = Regex::new(r#""version"\s*:\s*"([^"]+)""#).context("Failed to create regex")?;
-    let version = re
-        .captures(&response)
-        .and_then(|caps| caps.get(1))
-        .map(|m| m.as_str().to_string())
-        .context("No version in PyPI API response (package may not exist)")?;
-    Ok(version)
+fn get_latest_version_crates_io() -> Result<String> {
+    packages::latest_version_crates_io(CRATES_IO_CRATE)
+}
+
+fn get_latest_version_pypi() -> Result<String> {
+    packages::latest_version_pypi(PYPI_PACKAGE)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use regex::Regex;
 
     #[test]
     fn test_parse_version_from_crates_io_api() {
@@ -235,6 +354,91 @@ mod tests {
         assert_eq!(version, None);
     }
 
+    #[test]
+    fn test_install_decision() {
+        assert_eq!(
+            install_decision("0.9.0", "0.8.8", false),
+            InstallDecision::Install
+        );
+        assert_eq!(
+            install_decision("0.8.8", "0.8.8", false),
+            InstallDecision::UpToDate
+        );
+        assert_eq!(
+            install_decision("v0.8.1", "0.8.8", false),
+            InstallDecision::RefuseDowngrade
+        );
+        assert_eq!(
+            install_decision("0.8.1", "0.8.8", true),
+            InstallDecision::Install
+        );
+        assert_eq!(
+            install_decision("0.8.8", "0.8.8", true),
+            InstallDecision::Install
+        );
+    }
+
+    #[test]
+    fn test_pre_release_sorts_below_its_release() {
+        assert_eq!(cmp_release("0.9.0-rc.1", "0.9.0"), Ordering::Less);
+        assert_eq!(cmp_release("0.9.0rc1", "0.9.0"), Ordering::Less);
+        assert_eq!(cmp_release("0.9.0-rc.1", "0.8.8"), Ordering::Greater);
+        assert_eq!(cmp_release("0.9.0-rc.2", "0.9.0-rc.10"), Ordering::Less);
+        assert_eq!(cmp_release("0.9.0a1", "0.9.0rc1"), Ordering::Less);
+        assert_eq!(cmp_release("v0.9.0", "0.9.0+build.5"), Ordering::Equal);
+
+        assert_eq!(
+            install_decision("0.9.0", "0.9.0-rc.1", false),
+            InstallDecision::Install
+        );
+        assert_eq!(
+            install_decision("0.9.0-rc.1", "0.9.0", false),
+            InstallDecision::RefuseDowngrade
+        );
+        assert_eq!(
+            check_message("0.9.0", "0.9.0-rc.1"),
+            ("v0.9.0 available (current v0.9.0-rc.1)".to_string(), true)
+        );
+        let pypi = serde_json::json!({
+            "releases": {"0.9.0rc1": [], "0.9.0": [], "0.8.8": []}
+        });
+        assert_eq!(latest_version_pypi_pre(&pypi), Some("0.9.0".to_string()));
+    }
+
+    #[test]
+    fn test_check_message() {
+        assert_eq!(
+            check_message("0.9.0", "0.8.8"),
+            ("v0.9.0 available (current v0.8.8)".to_string(), true)
+        );
+        assert_eq!(
+            check_message("0.8.8", "0.8.8"),
+            ("Up to date (v0.8.8)".to_string(), false)
+        );
+        assert_eq!(
+            check_message("0.8.1", "0.8.8"),
+            ("Up to date (v0.8.8)".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_latest_pre_release_versions() {
+        let crates_io = serde_json::json!({
+            "crate": {"max_version": "0.9.0-rc.1", "max_stable_version": "0.8.8"}
+        });
+        assert_eq!(
+            latest_version_crates_io_pre(&crates_io),
+            Some("0.9.0-rc.1".to_string())
+        );
+
+        let pypi = serde_json::json!({
+            "info": {"version": "0.8.8"},
+            "releases": {"0.8.8": [], "0.10.0": [], "0.9.2": []}
+        });
+        assert_eq!(latest_version_pypi_pre(&pypi), Some("0.10.0".to_string()));
+        assert_eq!(latest_version_pypi_pre(&serde_json::json!({})), None);
+    }
+
     #[test]
     fn test_version_constant() {
         assert_eq!(env!("CARGO_PKG_VERSION"), CURRENT_VERSION);
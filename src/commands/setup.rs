@@ -1,10 +1,27 @@
-use crate::utils::{project, spinner};
+use crate::utils::project::{self, PYTHON};
+use crate::utils::spinner;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
-pub fn run(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
+pub fn run(
+    frozen: bool,
+    rm: bool,
+    dry_run: bool,
+    list: bool,
+    project: Option<project::ProjectType>,
+) -> Result<()> {
+    let project_type = project::enter(project)?;
+
+    if list {
+        println!("Setup plan for this {} project:", project_type.tool());
+        for (i, step) in plan(project_type, frozen, rm).iter().enumerate() {
+            println!("  {}. {}", i + 1, step);
+        }
+        return Ok(());
+    }
+
     if rm {
         if dry_run {
             println!("rm -rf .venv");
@@ -13,8 +30,6 @@ pub fn run(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
         }
     }
 
-    let project_type = project::detect()?.context("No uv/poetry/cargo project found")?;
-
     match project_type {
         project::ProjectType::Uv => {
             setup_uv(frozen, rm, dry_run)?;
@@ -25,21 +40,53 @@ pub fn run(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
         project::ProjectType::Cargo => {
             setup_cargo(frozen, rm, dry_run)?;
         }
+        project::ProjectType::Pip => {
+            setup_pip(frozen, rm, dry_run)?;
+        }
     }
 
     Ok(())
 }
 
-fn setup_uv(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
-    let mut args = vec!["sync", "--all-extras"];
-
-    if frozen {
-        args.push("--frozen");
-    }
-
+/// The ordered, human-readable steps `run` performs for `project_type`.
+fn plan(project_type: project::ProjectType, frozen: bool, rm: bool) -> Vec<String> {
+    let mut steps = Vec::new();
     if rm {
-        args.push("--no-cache");
+        steps.push("Remove .venv".to_string());
     }
+    let tool = project_type.tool();
+    let mut program = tool.to_string();
+    let (action, args) = match project_type {
+        project::ProjectType::Uv => ("Install dependencies", build_uv_args(frozen, rm)),
+        project::ProjectType::Poetry => ("Install dependencies", build_poetry_args(frozen, rm)),
+        project::ProjectType::Cargo => {
+            if rm {
+                steps.push("Remove target".to_string());
+            }
+            ("Build", build_cargo_args(frozen))
+        }
+        project::ProjectType::Pip => {
+            let pip = project::venv_pip(Path::new(project::VENV_DIR));
+            if rm || !pip.is_file() {
+                steps.push(format!(
+                    "Create {} with {PYTHON}: {PYTHON} {}",
+                    project::VENV_DIR,
+                    build_venv_args().join(" ")
+                ));
+            }
+            program = pip.display().to_string();
+            ("Install dependencies", build_pip_args(frozen))
+        }
+    };
+    steps.push(format!(
+        "{action} with {tool}: {program} {}",
+        args.join(" ")
+    ));
+    steps
+}
+
+fn setup_uv(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
+    let args = build_uv_args(frozen, rm);
 
     if dry_run {
         println!("uv {}", args.join(" "));
@@ -62,15 +109,7 @@ fn setup_uv(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
 }
 
 fn setup_poetry(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
-    let mut args = vec!["install", "--all-extras"];
-
-    if frozen {
-        args.push("--sync");
-    }
-
-    if rm {
-        args.push("--no-cache");
-    }
+    let args = build_poetry_args(frozen, rm);
 
     if dry_run {
         println!("poetry {}", args.join(" "));
@@ -101,12 +140,7 @@ fn setup_cargo(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
         }
     }
 
-    let mut args = vec!["build"];
-
-    if frozen {
-        // For Cargo, --frozen means don't update Cargo.lock
-        args.push("--frozen");
-    }
+    let args = build_cargo_args(frozen);
 
     if dry_run {
         println!("cargo {}", args.join(" "));
@@ -128,8 +162,51 @@ fn setup_cargo(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-// Helper function for testing argument building
-#[cfg(test)]
+/// Installs `requirements.txt` into the project's `.venv`, creating it first when it's missing
+/// (or was just removed by `--rm`), never into the interpreter `pip` happens to resolve to.
+fn setup_pip(frozen: bool, rm: bool, dry_run: bool) -> Result<()> {
+    let pip = project::venv_pip(Path::new(project::VENV_DIR));
+    let create_venv = rm || !pip.is_file();
+    let venv_args = build_venv_args();
+    let args = build_pip_args(frozen);
+
+    if dry_run {
+        if create_venv {
+            println!("{PYTHON} {}", venv_args.join(" "));
+        }
+        println!("{} {}", pip.display(), args.join(" "));
+        return Ok(());
+    }
+
+    if create_venv {
+        let mut cmd = Command::new(PYTHON);
+        cmd.args(&venv_args);
+        let status = spinner::run_with_spinner_and_message(
+            "Creating .venv...",
+            &mut cmd,
+            Some("Created .venv"),
+        )?;
+
+        if !status.success() {
+            anyhow::bail!("{PYTHON} -m venv failed");
+        }
+    }
+
+    let mut cmd = Command::new(&pip);
+    cmd.args(&args);
+    let status = spinner::run_with_spinner_and_message(
+        "Installing dependencies with pip...",
+        &mut cmd,
+        Some("Installation complete"),
+    )?;
+
+    if !status.success() {
+        anyhow::bail!("pip install failed");
+    }
+
+    Ok(())
+}
+
 fn build_uv_args(frozen: bool, rm: bool) -> Vec<&'static str> {
     let mut args = vec!["sync", "--all-extras"];
     if frozen {
@@ -141,7 +218,6 @@ fn build_uv_args(frozen: bool, rm: bool) -> Vec<&'static str> {
     args
 }
 
-#[cfg(test)]
 fn build_poetry_args(frozen: bool, rm: bool) -> Vec<&'static str> {
     let mut args = vec!["install", "--all-extras"];
     if frozen {
@@ -153,10 +229,23 @@ fn build_poetry_args(frozen: bool, rm: bool) -> Vec<&'static str> {
     args
 }
 
-#[cfg(test)]
+fn build_venv_args() -> Vec<&'static str> {
+    vec!["-m", "venv", project::VENV_DIR]
+}
+
+fn build_pip_args(frozen: bool) -> Vec<&'static str> {
+    let mut args = vec!["install", "-r", "requirements.txt"];
+    if frozen {
+        // Install exactly the pinned requirements, without resolving anything else
+        args.push("--no-deps");
+    }
+    args
+}
+
 fn build_cargo_args(frozen: bool) -> Vec<&'static str> {
     let mut args = vec!["build"];
     if frozen {
+        // For Cargo, --frozen means don't update Cargo.lock
         args.push("--frozen");
     }
     args
@@ -281,11 +370,64 @@ mod tests {
         assert_eq!(build_cargo_args(true), vec!["build", "--frozen"]);
     }
 
+    #[test]
+    fn test_build_pip_args() {
+        assert_eq!(
+            build_pip_args(false),
+            vec!["install", "-r", "requirements.txt"]
+        );
+        assert_eq!(
+            build_pip_args(true),
+            vec!["install", "-r", "requirements.txt", "--no-deps"]
+        );
+    }
+
+    #[test]
+    fn test_setup_pip_dry_run_rm() {
+        let result = setup_pip(true, true, true);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_plan() {
+        assert_eq!(
+            plan(project::ProjectType::Uv, true, true),
+            vec![
+                "Remove .venv",
+                "Install dependencies with uv: uv sync --all-extras --frozen --no-cache"
+            ]
+        );
+        assert_eq!(
+            plan(project::ProjectType::Poetry, false, false),
+            vec!["Install dependencies with poetry: poetry install --all-extras"]
+        );
+        assert_eq!(
+            plan(project::ProjectType::Cargo, true, true),
+            vec![
+                "Remove .venv",
+                "Remove target",
+                "Build with cargo: cargo build --frozen"
+            ]
+        );
+        let pip = project::venv_pip(Path::new(".venv"));
+        assert_eq!(
+            plan(project::ProjectType::Pip, true, true),
+            vec![
+                "Remove .venv".to_string(),
+                format!("Create .venv with {PYTHON}: {PYTHON} -m venv .venv"),
+                format!(
+                    "Install dependencies with pip: {} install -r requirements.txt --no-deps",
+                    pip.display()
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_setup_rm_dry_run() {
         // Test that rm flag with dry_run prints the correct command
         // We can't easily test println, but we can verify the function succeeds
-        let result = run(false, true, true);
+        let result = run(false, true, true, false, None);
         // This will fail if no project is detected, which is expected in test environment
         // But the rm logic should still execute
         let _ = result;
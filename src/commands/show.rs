@@ -1,119 +1,309 @@
-use crate::utils::project;
+use crate::utils::colors;
+use crate::utils::output;
+use crate::utils::packages::{self, cmp_version};
+use crate::utils::project::{self, ProjectType};
+use crate::utils::spinner;
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::cmp::Ordering;
 use std::process::{Command, Stdio};
 
-pub fn run(patterns: &[String]) -> Result<()> {
-    let project_type = project::detect()?.context("No uv/poetry/cargo project found")?;
+const LATEST_LOOKUP_WORKERS: usize = 8;
 
-    match project_type {
-        project::ProjectType::Uv => {
-            show_uv(patterns)?;
-        }
-        project::ProjectType::Poetry => {
-            show_poetry(patterns)?;
-        }
-        project::ProjectType::Cargo => {
-            show_cargo(patterns)?;
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    Name,
+    Version,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShowOptions {
+    pub json: bool,
+    pub depth: Option<usize>,
+    pub sort: Option<SortKey>,
+    pub outdated: bool,
+    pub min_score: Option<i64>,
+    pub project: Option<ProjectType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyInfo {
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<String>,
+}
+
+impl DependencyInfo {
+    pub fn is_outdated(&self) -> bool {
+        self.latest
+            .as_deref()
+            .is_some_and(|latest| cmp_version(&self.version, latest) == Ordering::Less)
     }
+}
 
-    Ok(())
+#[derive(Serialize)]
+struct ShowJson {
+    project_type: ProjectType,
+    dependencies: Vec<DependencyInfo>,
 }
 
-fn show_uv(patterns: &[String]) -> Result<()> {
-    let mut cmd = Command::new("uv");
-    cmd.args(["pip", "list"]);
+pub fn run(patterns: &[String], options: &ShowOptions) -> Result<()> {
+    let project_type = project::enter(options.project)?;
 
-    if patterns.is_empty() {
-        let status = cmd.status().context("Failed to run uv pip list")?;
-        std::process::exit(status.code().unwrap_or(1));
-    } else {
-        let output = cmd
-            .stdout(Stdio::piped())
-            .output()
-            .context("Failed to run uv pip list")?;
-
-        if !output.status.success() {
-            anyhow::bail!("uv pip list failed");
+    if options.depth.is_some() && project_type != ProjectType::Cargo {
+        spinner::print_warning_message("--depth is only supported for cargo projects; ignoring");
+    }
+
+    if options.json {
+        let report = ShowJson {
+            project_type,
+            dependencies: collect_dependencies(project_type, patterns, options)?,
+        };
+        return output::emit(&report);
+    }
+
+    if options.sort.is_some() || options.outdated {
+        let deps = collect_dependencies(project_type, patterns, options)?;
+        let use_color = colors::use_color(atty::Stream::Stdout);
+        for line in format_table(&deps, use_color) {
+            println!("{}", line);
         }
+        return Ok(());
+    }
 
-        let stdout = String::from_utf8(output.stdout)?;
-        let pattern = patterns.join("|");
+    show_filtered(project_type, patterns, options)
+}
 
-        for line in stdout.lines() {
-            if regex_match_case_insensitive(line, &pattern) {
-                println!("{}", line);
+fn dependency_command(
+    project_type: ProjectType,
+    depth: Option<usize>,
+) -> Result<(Command, &'static str)> {
+    Ok(match project_type {
+        ProjectType::Uv => {
+            let mut cmd = Command::new("uv");
+            cmd.args(["pip", "list"]);
+            (cmd, "uv pip list")
+        }
+        ProjectType::Poetry => {
+            let mut cmd = Command::new("poetry");
+            cmd.arg("show");
+            (cmd, "poetry show")
+        }
+        ProjectType::Pip => {
+            let mut cmd = project::pip_command()?;
+            cmd.arg("list");
+            (cmd, "pip list")
+        }
+        ProjectType::Cargo => {
+            let mut cmd = Command::new("cargo");
+            cmd.arg("tree");
+            if let Some(depth) = depth {
+                cmd.args(["--depth", &depth.to_string()]);
             }
+            (cmd, "cargo tree")
         }
+    })
+}
+
+fn get_dependency_output(project_type: ProjectType, depth: Option<usize>) -> Result<String> {
+    let (mut cmd, label) = dependency_command(project_type, depth)?;
+
+    let output = cmd
+        .stdout(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run {}", label))?;
+
+    if !output.status.success() {
+        anyhow::bail!("{} failed", label);
     }
 
-    Ok(())
+    Ok(String::from_utf8(output.stdout)?)
 }
 
-fn show_poetry(patterns: &[String]) -> Result<()> {
-    let mut cmd = Command::new("poetry");
-    cmd.arg("show");
-
+fn show_filtered(
+    project_type: ProjectType,
+    patterns: &[String],
+    options: &ShowOptions,
+) -> Result<()> {
     if patterns.is_empty() {
-        let status = cmd.status().context("Failed to run poetry show")?;
+        let (mut cmd, label) = dependency_command(project_type, options.depth)?;
+        let status = cmd
+            .status()
+            .with_context(|| format!("Failed to run {}", label))?;
         std::process::exit(status.code().unwrap_or(1));
-    } else {
-        let output = cmd
-            .stdout(Stdio::piped())
-            .output()
-            .context("Failed to run poetry show")?;
-
-        if !output.status.success() {
-            anyhow::bail!("poetry show failed");
-        }
-
-        let stdout = String::from_utf8(output.stdout)?;
-        let pattern = patterns.join("|");
+    }
 
-        for line in stdout.lines() {
-            if regex_match_case_insensitive(line, &pattern) {
-                println!("{}", line);
-            }
-        }
+    let stdout = get_dependency_output(project_type, options.depth)?;
+    for line in matching_lines(project_type, &stdout, patterns, options.min_score) {
+        println!("{}", line);
     }
 
     Ok(())
 }
 
-fn show_cargo(patterns: &[String]) -> Result<()> {
-    let mut cmd = Command::new("cargo");
-    cmd.args(["tree"]);
+fn collect_dependencies(
+    project_type: ProjectType,
+    patterns: &[String],
+    options: &ShowOptions,
+) -> Result<Vec<DependencyInfo>> {
+    let stdout = get_dependency_output(project_type, options.depth)?;
+    let mut deps = parse_dependency_lines(
+        project_type,
+        matching_lines(project_type, &stdout, patterns, options.min_score),
+    );
+    if let Some(key) = options.sort {
+        sort_dependencies(&mut deps, key);
+    }
+    if options.outdated {
+        let mut sp = spinner::create_spinner("Checking latest versions...");
+        fill_latest_versions(project_type, &mut deps);
+        spinner::stop_spinner(sp.as_mut());
+    }
+    Ok(deps)
+}
 
-    if patterns.is_empty() {
-        let status = cmd.status().context("Failed to run cargo tree")?;
-        std::process::exit(status.code().unwrap_or(1));
-    } else {
-        let output = cmd
-            .stdout(Stdio::piped())
-            .output()
-            .context("Failed to run cargo tree")?;
-
-        if !output.status.success() {
-            anyhow::bail!("cargo tree failed");
+/// Looks up the latest release of every dependency. Failed lookups leave `latest` unset
+/// so one unreachable package doesn't abort the whole listing.
+fn fill_latest_versions(project_type: ProjectType, deps: &mut [DependencyInfo]) {
+    let chunk_size = deps.len().div_ceil(LATEST_LOOKUP_WORKERS).max(1);
+    std::thread::scope(|scope| {
+        for chunk in deps.chunks_mut(chunk_size) {
+            scope.spawn(move || {
+                for dep in chunk {
+                    dep.latest = packages::latest_version(project_type, &dep.name).ok();
+                }
+            });
         }
+    });
+}
 
-        let stdout = String::from_utf8(output.stdout)?;
-        let pattern = patterns.join("|");
+fn compare_dependencies(a: &DependencyInfo, b: &DependencyInfo, key: SortKey) -> Ordering {
+    let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    match key {
+        SortKey::Name => by_name().then_with(|| cmp_version(&a.version, &b.version)),
+        SortKey::Version => cmp_version(&a.version, &b.version).then_with(by_name),
+    }
+}
 
-        for line in stdout.lines() {
-            if regex_match_case_insensitive(line, &pattern) {
-                println!("{}", line);
+fn sort_dependencies(deps: &mut [DependencyInfo], key: SortKey) {
+    deps.sort_by(|a, b| compare_dependencies(a, b, key));
+}
+
+fn format_table(deps: &[DependencyInfo], use_color: bool) -> Vec<String> {
+    let width = deps
+        .iter()
+        .map(|d| d.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    deps.iter()
+        .map(|d| {
+            let row = format!("{:<width$}  {}", d.name, d.version, width = width);
+            match d.latest.as_deref() {
+                Some(latest) if d.is_outdated() => {
+                    let latest = latest.trim_start_matches('v');
+                    if use_color {
+                        format!(
+                            "{row} {}(latest v{latest}){}",
+                            colors::ANSI_YELLOW,
+                            colors::ANSI_RESET
+                        )
+                    } else {
+                        format!("{row} (latest v{latest})")
+                    }
+                }
+                _ => row,
             }
-        }
+        })
+        .collect()
+}
+
+/// [`filter_lines`], additionally dropping lines whose dependency name fuzzy-matches no
+/// pattern with at least `min_score`.
+fn matching_lines<'a>(
+    project_type: ProjectType,
+    output: &'a str,
+    patterns: &[String],
+    min_score: Option<i64>,
+) -> Vec<&'a str> {
+    let lines = filter_lines(output, patterns);
+    let Some(min_score) = min_score else {
+        return lines;
+    };
+    lines
+        .into_iter()
+        .filter(|line| {
+            parse_dependency_lines(project_type, [*line])
+                .first()
+                .is_some_and(|dep| {
+                    patterns.iter().any(|p| {
+                        packages::fuzzy_score(&dep.name, p).is_some_and(|score| score >= min_score)
+                    })
+                })
+        })
+        .collect()
+}
+
+fn filter_lines<'a>(output: &'a str, patterns: &[String]) -> Vec<&'a str> {
+    if patterns.is_empty() {
+        return output.lines().collect();
     }
+    let pattern = patterns.join("|");
+    output
+        .lines()
+        .filter(|line| regex_match_case_insensitive(line, &pattern))
+        .collect()
+}
 
-    Ok(())
+pub fn parse_dependency_lines<'a>(
+    project_type: ProjectType,
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Vec<DependencyInfo> {
+    lines
+        .into_iter()
+        .filter_map(|line| match project_type {
+            ProjectType::Uv | ProjectType::Poetry | ProjectType::Pip => parse_list_line(line),
+            ProjectType::Cargo => parse_cargo_tree_line(line),
+        })
+        .collect()
+}
+
+fn parse_list_line(line: &str) -> Option<DependencyInfo> {
+    let mut parts = line.split_whitespace().filter(|p| *p != "(!)");
+    let name = parts.next()?;
+    let version = parts.next()?;
+    if name == "Package" || name.starts_with('-') {
+        return None;
+    }
+    Some(DependencyInfo {
+        name: name.to_string(),
+        version: version.to_string(),
+        latest: None,
+    })
+}
+
+fn parse_cargo_tree_line(line: &str) -> Option<DependencyInfo> {
+    let stripped = line.trim_start_matches(['│', '├', '└', '─', ' ']);
+    if stripped.len() == line.len() {
+        // The root crate has no tree prefix; it is the project itself, not a dependency.
+        return None;
+    }
+    if stripped.ends_with("(*)") {
+        // Already listed earlier in the tree.
+        return None;
+    }
+    let mut parts = stripped.split_whitespace();
+    let name = parts.next()?;
+    let version = parts.next()?.trim_start_matches('v');
+    Some(DependencyInfo {
+        name: name.to_string(),
+        version: version.to_string(),
+        latest: None,
+    })
 }
 
 fn regex_match_case_insensitive(text: &str, pattern: &str) -> bool {
-    use regex::Regex;
-    let escaped = regex::escape(pattern);
-    match Regex::new(&format!("(?i){}", escaped)) {
+    match crate::utils::pattern::build_regex(&regex::escape(pattern), true) {
         Ok(re) => re.is_match(text),
         Err(_) => text.to_lowercase().contains(&pattern.to_lowercase()),
     }
@@ -269,6 +459,208 @@ mod tests {
         assert!(regex_match_case_insensitive("anyhow 1.0.0", "1.0"));
     }
 
+    #[test]
+    fn test_parse_dependency_lines_uv() {
+        let output = "Package    Version\n---------- -------\nanyio      4.2.0\nhttpx      0.27.0";
+        let deps = parse_dependency_lines(ProjectType::Uv, output.lines());
+        assert_eq!(
+            deps,
+            vec![
+                DependencyInfo {
+                    name: "anyio".to_string(),
+                    version: "4.2.0".to_string(),
+                    latest: None,
+                },
+                DependencyInfo {
+                    name: "httpx".to_string(),
+                    version: "0.27.0".to_string(),
+                    latest: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dependency_lines_poetry() {
+        let output = "anyio  4.2.0  High level compatibility layer\nhttpx (!) 0.27.0 The next generation HTTP client.";
+        let deps = parse_dependency_lines(ProjectType::Poetry, output.lines());
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[1].name, "httpx");
+        assert_eq!(deps[1].version, "0.27.0");
+    }
+
+    #[test]
+    fn test_parse_dependency_lines_cargo_skips_root() {
+        let output = "bashers v0.8.8 (/home/user/bashers)\n├── anyhow v1.0.100\n│   └── subpkg v0.1.0\n└── clap v4.5.54";
+        let deps = parse_dependency_lines(ProjectType::Cargo, output.lines());
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["anyhow", "subpkg", "clap"]);
+        assert_eq!(deps[2].version, "4.5.54");
+    }
+
+    #[test]
+    fn test_parse_dependency_lines_cargo_skips_repeats() {
+        let output = "app v0.1.0\n├── a v1.0.0\n│   └── shared v2.0.0\n└── b v1.0.0\n    └── shared v2.0.0 (*)";
+        let deps = parse_dependency_lines(ProjectType::Cargo, output.lines());
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "shared", "b"]);
+    }
+
+    fn command_args(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_dependency_command_cargo_depth() {
+        let (cmd, _) = dependency_command(ProjectType::Cargo, None).unwrap();
+        assert_eq!(command_args(&cmd), vec!["tree"]);
+        let (cmd, _) = dependency_command(ProjectType::Cargo, Some(2)).unwrap();
+        assert_eq!(command_args(&cmd), vec!["tree", "--depth", "2"]);
+    }
+
+    #[test]
+    fn test_dependency_command_ignores_depth_for_python() {
+        let (cmd, label) = dependency_command(ProjectType::Uv, Some(2)).unwrap();
+        assert_eq!(command_args(&cmd), vec!["pip", "list"]);
+        assert_eq!(label, "uv pip list");
+        let (cmd, _) = dependency_command(ProjectType::Poetry, Some(2)).unwrap();
+        assert_eq!(command_args(&cmd), vec!["show"]);
+        // Pip needs a virtualenv, so it's only checked where the test run has one
+        match dependency_command(ProjectType::Pip, Some(2)) {
+            Ok((cmd, label)) => {
+                assert_eq!(command_args(&cmd), vec!["list"]);
+                assert_eq!(label, "pip list");
+            }
+            Err(e) => assert!(e.to_string().contains("No virtualenv found")),
+        }
+    }
+
+    #[test]
+    fn test_matching_lines_min_score() {
+        let output = "├── clap v4.5.54\n├── libclapx v0.1.0\n└── anyhow v1.0.100";
+        let patterns = vec!["clap".to_string()];
+        assert_eq!(
+            matching_lines(ProjectType::Cargo, output, &patterns, None).len(),
+            2
+        );
+        let best = packages::fuzzy_score("clap", "clap").unwrap();
+        assert_eq!(
+            matching_lines(ProjectType::Cargo, output, &patterns, Some(best)),
+            vec!["├── clap v4.5.54"]
+        );
+    }
+
+    #[test]
+    fn test_filter_lines_then_parse() {
+        let output = "├── anyhow v1.0.100\n└── clap v4.5.54";
+        let lines = filter_lines(output, &["CLAP".to_string()]);
+        let deps = parse_dependency_lines(ProjectType::Cargo, lines);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "clap");
+    }
+
+    fn dep(name: &str, version: &str) -> DependencyInfo {
+        DependencyInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            latest: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_dependencies_by_version_mixed_prefix() {
+        let cases = vec![
+            (dep("a", "v1.2.0"), dep("b", "1.10.0"), Ordering::Less),
+            (dep("a", "1.10.0"), dep("b", "v1.2.0"), Ordering::Greater),
+            (dep("a", "v2.0.0"), dep("b", "2.0.0"), Ordering::Less),
+            (dep("b", "2.0.0"), dep("a", "v2.0.0"), Ordering::Greater),
+            (dep("a", "0.9"), dep("b", "v0.9.1"), Ordering::Less),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(
+                compare_dependencies(&a, &b, SortKey::Version),
+                expected,
+                "{a:?} vs {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compare_dependencies_by_name_case_insensitive() {
+        assert_eq!(
+            compare_dependencies(&dep("Clap", "1.0"), &dep("anyhow", "1.0"), SortKey::Name),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_dependencies(&dep("clap", "v4.0"), &dep("clap", "3.0"), SortKey::Name),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_sort_dependencies_version() {
+        let mut deps = vec![dep("c", "v1.10.0"), dep("a", "1.2.0"), dep("b", "v1.9")];
+        sort_dependencies(&mut deps, SortKey::Version);
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_format_table_pads_names() {
+        let deps = vec![dep("clap", "4.5.54"), dep("serde_json", "1.0.0")];
+        assert_eq!(
+            format_table(&deps, false),
+            vec!["clap        4.5.54", "serde_json  1.0.0"]
+        );
+    }
+
+    #[test]
+    fn test_format_table_outdated_annotation() {
+        let mut old = dep("clap", "4.5.0");
+        old.latest = Some("4.5.54".to_string());
+        let mut current = dep("anyhow", "1.0.100");
+        current.latest = Some("1.0.100".to_string());
+        let unknown = dep("regex", "1.0.0");
+
+        assert!(old.is_outdated());
+        assert!(!current.is_outdated());
+        assert!(!unknown.is_outdated());
+        assert_eq!(
+            format_table(&[old, current, unknown], false),
+            vec![
+                "clap    4.5.0 (latest v4.5.54)",
+                "anyhow  1.0.100",
+                "regex   1.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_show_json_shape() {
+        let report = ShowJson {
+            project_type: ProjectType::Cargo,
+            dependencies: vec![
+                DependencyInfo {
+                    name: "clap".to_string(),
+                    version: "4.5.54".to_string(),
+                    latest: None,
+                },
+                DependencyInfo {
+                    name: "regex".to_string(),
+                    version: "1.0.0".to_string(),
+                    latest: Some("1.12.2".to_string()),
+                },
+            ],
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            json,
+            r#"{"project_type":"cargo","dependencies":[{"name":"clap","version":"4.5.54"},{"name":"regex","version":"1.0.0","latest":"1.12.2"}]}"#
+        );
+    }
+
     #[test]
     fn test_regex_match_hyphens() {
         // Test matching package names with hyphens
@@ -0,0 +1,24 @@
+use crate::commands::kube::track::tui::{self, file};
+use crate::commands::kube::track::{print_warning, LogFilter, TuiOptions};
+use crate::utils::colors;
+use anyhow::Result;
+
+pub fn run(paths: &[String], err_only: bool) -> Result<()> {
+    let files = file::expand_paths(paths)?;
+
+    let use_color = colors::use_color(atty::Stream::Stderr);
+    for (i, path) in paths.iter().enumerate() {
+        if !files.iter().any(|f| f.pattern_idx == i) {
+            print_warning(&format!("No files matching: \"{path}\""), use_color);
+        }
+    }
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let filter = LogFilter {
+        err_only,
+        ..LogFilter::default()
+    };
+    tui::run_files(files, paths, filter, TuiOptions::default())
+}
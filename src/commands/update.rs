@@ -1,8 +1,12 @@
+use crate::utils::log::CommandExt;
+use crate::utils::output::{self, OutputFormat};
+use crate::utils::packages::cmp_version;
 use crate::utils::{colors, multi_progress, packages, project, spinner};
 use anyhow::{Context, Result};
+use serde::Serialize;
 use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use std::process::Command;
 
@@ -22,45 +26,26 @@ fn version_change(before: &str, after: &str) -> colors::VersionChange {
     }
 }
 
-fn cmp_version(a: &str, b: &str) -> Ordering {
-    let a = a.trim_start_matches('v');
-    let b = b.trim_start_matches('v');
-    let parts_a: Vec<u64> = a
-        .split('.')
-        .filter_map(|s| s.split('-').next())
-        .filter_map(|s| s.parse().ok())
-        .collect();
-    let parts_b: Vec<u64> = b
-        .split('.')
-        .filter_map(|s| s.split('-').next())
-        .filter_map(|s| s.parse().ok())
-        .collect();
-    for (pa, pb) in parts_a.iter().zip(parts_b.iter()) {
-        match pa.cmp(pb) {
-            Ordering::Equal => continue,
-            o => return o,
-        }
-    }
-    parts_a.len().cmp(&parts_b.len())
-}
-
 pub fn run(
     package_patterns: &[String],
     dry_run: bool,
     auto_select: bool,
+    min_score: Option<i64>,
     verbose: bool,
+    output: OutputFormat,
+    project: Option<project::ProjectType>,
 ) -> Result<()> {
-    let project_type = project::detect()?.context("No uv/poetry/cargo project found")?;
+    let project_type = project::enter(project)?;
 
     if package_patterns.is_empty() {
-        update_all(project_type, dry_run, verbose)?;
+        update_all(project_type, dry_run, verbose, output)?;
         return Ok(());
     }
 
     let all_packages = packages::list(project_type)?;
     let mut combined: Vec<String> = Vec::new();
     for pattern in package_patterns {
-        let matches = packages::fuzzy_match(&all_packages, pattern)?;
+        let matches = packages::fuzzy_match_min_score(&all_packages, pattern, min_score);
         for m in matches {
             if !combined.contains(&m) {
                 combined.push(m);
@@ -91,7 +76,7 @@ pub fn run(
         many
     };
 
-    update_packages(project_type, &selected, dry_run, verbose)?;
+    update_packages(project_type, &selected, dry_run, verbose, output)?;
 
     Ok(())
 }
@@ -101,6 +86,7 @@ fn update_packages(
     packages: &[String],
     dry_run: bool,
     verbose: bool,
+    output: OutputFormat,
 ) -> Result<()> {
     if packages.is_empty() {
         return Ok(());
@@ -125,6 +111,15 @@ fn update_packages(
                     .collect();
                 println!("cargo update {}", args.join(" "));
             }
+            project::ProjectType::Pip => {
+                let args: Vec<&str> = packages.iter().map(String::as_str).collect();
+                let pip = project::pip_command()?;
+                println!(
+                    "{} install --upgrade {}",
+                    pip.get_program().to_string_lossy(),
+                    args.join(" ")
+                );
+            }
         }
         return Ok(());
     }
@@ -146,14 +141,14 @@ fn update_packages(
                 for p in packages {
                     lock.args(["--upgrade-package", p]);
                 }
-                let out1 = lock.output().context("Failed to run uv lock")?;
+                let out1 = lock.logged_output().context("Failed to run uv lock")?;
                 forward(&out1);
                 if !out1.status.success() {
                     anyhow::bail!("uv lock failed");
                 }
                 let out2 = Command::new("uv")
                     .args(["sync", "--all-extras"])
-                    .output()
+                    .logged_output()
                     .context("Failed to run uv sync")?;
                 forward(&out2);
                 if !out2.status.success() {
@@ -165,7 +160,7 @@ fn update_packages(
                 let out = Command::new("poetry")
                     .arg("update")
                     .args(packages)
-                    .output()
+                    .logged_output()
                     .context("Failed to run poetry update")?;
                 forward(&out);
                 if !out.status.success() {
@@ -179,34 +174,41 @@ fn update_packages(
                 for p in packages {
                     cmd.args(["-p", p]);
                 }
-                let out = cmd.output().context("Failed to run cargo update")?;
+                let out = cmd.logged_output().context("Failed to run cargo update")?;
                 forward(&out);
                 if !out.status.success() {
                     anyhow::bail!("cargo update failed");
                 }
                 Ok(())
             }
+            project::ProjectType::Pip => {
+                let out = project::pip_command()?
+                    .args(["install", "--upgrade"])
+                    .args(packages)
+                    .logged_output()
+                    .context("Failed to run pip install")?;
+                forward(&out);
+                if !out.status.success() {
+                    anyhow::bail!("pip install --upgrade failed");
+                }
+                Ok(())
+            }
         }
     };
 
-    let result = if spinner::should_show_spinner() {
-        let before_versions: HashMap<String, Option<String>> = packages
-            .iter()
-            .map(|p| {
-                (
-                    p.clone(),
-                    packages::get_installed_version(project_type, p)
-                        .ok()
-                        .flatten(),
-                )
-            })
-            .collect();
+    let show_spinner = !output.is_json() && spinner::should_show_spinner();
+    let before_versions = if output.is_json() || show_spinner {
+        installed_versions(project_type, packages)
+    } else {
+        HashMap::new()
+    };
+    let result = if show_spinner {
         let multi = multi_progress::multi_progress_stderr();
         multi_progress::run_spinners_then_single_op(
             &multi,
             packages,
             |one_indexed, total, pkg| {
-                if atty::is(atty::Stream::Stderr) {
+                if colors::use_color(atty::Stream::Stderr) {
                     format!(
                         "[{}/{}] {}[{}]{} ",
                         one_indexed,
@@ -236,7 +238,7 @@ fn update_packages(
                     colors::format_bumped_message_colored(&before, &after, change)
                 } else {
                     let failed = "Failed";
-                    if atty::is(atty::Stream::Stderr) {
+                    if colors::use_color(atty::Stream::Stderr) {
                         format!("{}{}{}", colors::ANSI_RED, failed, colors::ANSI_RESET)
                     } else {
                         failed.to_string()
@@ -248,17 +250,22 @@ fn update_packages(
         run_update()
     };
 
-    if verbose {
-        let _ = std::io::stdout().write_all(&stdout_buf.borrow());
-        let _ = std::io::stderr().write_all(&stderr_buf.borrow());
-        let _ = std::io::stdout().flush();
-        let _ = std::io::stderr().flush();
-    }
+    flush_verbose(verbose, output, &stdout_buf.borrow(), &stderr_buf.borrow());
+    result?;
 
-    result
+    if output.is_json() {
+        let after_versions = installed_versions(project_type, packages);
+        output::emit(&version_report(&before_versions, &after_versions, false))?;
+    }
+    Ok(())
 }
 
-fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool) -> Result<()> {
+fn update_all(
+    project_type: project::ProjectType,
+    dry_run: bool,
+    verbose: bool,
+    output: OutputFormat,
+) -> Result<()> {
     if dry_run {
         match project_type {
             project::ProjectType::Uv => {
@@ -271,6 +278,13 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
             project::ProjectType::Cargo => {
                 println!("cargo update");
             }
+            project::ProjectType::Pip => {
+                let pip = project::pip_command()?;
+                println!(
+                    "{} install --upgrade -r requirements.txt",
+                    pip.get_program().to_string_lossy()
+                );
+            }
         }
         return Ok(());
     }
@@ -290,7 +304,7 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
             project::ProjectType::Uv => {
                 let out1 = Command::new("uv")
                     .args(["lock", "--upgrade"])
-                    .output()
+                    .logged_output()
                     .context("Failed to run uv lock")?;
                 forward(&out1);
                 if !out1.status.success() {
@@ -298,7 +312,7 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
                 }
                 let out2 = Command::new("uv")
                     .args(["sync", "--all-extras"])
-                    .output()
+                    .logged_output()
                     .context("Failed to run uv sync")?;
                 forward(&out2);
                 if !out2.status.success() {
@@ -309,7 +323,7 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
             project::ProjectType::Poetry => {
                 let out = Command::new("poetry")
                     .arg("update")
-                    .output()
+                    .logged_output()
                     .context("Failed to run poetry update")?;
                 forward(&out);
                 if !out.status.success() {
@@ -320,7 +334,7 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
             project::ProjectType::Cargo => {
                 let out = Command::new("cargo")
                     .arg("update")
-                    .output()
+                    .logged_output()
                     .context("Failed to run cargo update")?;
                 forward(&out);
                 if !out.status.success() {
@@ -328,10 +342,26 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
                 }
                 Ok(())
             }
+            project::ProjectType::Pip => {
+                let out = project::pip_command()?
+                    .args(["install", "--upgrade", "-r", "requirements.txt"])
+                    .logged_output()
+                    .context("Failed to run pip install")?;
+                forward(&out);
+                if !out.status.success() {
+                    anyhow::bail!("pip install --upgrade failed");
+                }
+                Ok(())
+            }
         }
     };
 
-    let result = if spinner::should_show_spinner() {
+    let before_versions = if output.is_json() {
+        installed_versions(project_type, &packages::list(project_type)?)
+    } else {
+        HashMap::new()
+    };
+    let result = if !output.is_json() && spinner::should_show_spinner() {
         let multi = multi_progress::multi_progress_stderr();
         multi_progress::run_header_spinner(
             &multi,
@@ -344,14 +374,72 @@ fn update_all(project_type: project::ProjectType, dry_run: bool, verbose: bool)
         run_all()
     };
 
-    if verbose {
-        let _ = std::io::stdout().write_all(&stdout_buf.borrow());
-        let _ = std::io::stderr().write_all(&stderr_buf.borrow());
-        let _ = std::io::stdout().flush();
-        let _ = std::io::stderr().flush();
+    flush_verbose(verbose, output, &stdout_buf.borrow(), &stderr_buf.borrow());
+    result?;
+
+    if output.is_json() {
+        let after_versions = installed_versions(project_type, &packages::list(project_type)?);
+        output::emit(&version_report(&before_versions, &after_versions, true))?;
     }
+    Ok(())
+}
+
+/// In JSON mode the tools' stdout goes to stderr so stdout stays parseable.
+fn flush_verbose(verbose: bool, output: OutputFormat, stdout_buf: &[u8], stderr_buf: &[u8]) {
+    if !verbose {
+        return;
+    }
+    if output.is_json() {
+        let _ = std::io::stderr().write_all(stdout_buf);
+    } else {
+        let _ = std::io::stdout().write_all(stdout_buf);
+    }
+    let _ = std::io::stderr().write_all(stderr_buf);
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+}
+
+fn installed_versions(
+    project_type: project::ProjectType,
+    packages: &[String],
+) -> HashMap<String, Option<String>> {
+    packages
+        .iter()
+        .map(|p| {
+            (
+                p.clone(),
+                packages::get_installed_version(project_type, p)
+                    .ok()
+                    .flatten(),
+            )
+        })
+        .collect()
+}
 
-    result
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct VersionUpdate {
+    before: Option<String>,
+    after: Option<String>,
+}
+
+/// Before/after versions keyed by package; with `changed_only`, unchanged packages are left out.
+fn version_report(
+    before: &HashMap<String, Option<String>>,
+    after: &HashMap<String, Option<String>>,
+    changed_only: bool,
+) -> BTreeMap<String, VersionUpdate> {
+    before
+        .keys()
+        .chain(after.keys())
+        .map(|name| {
+            let update = VersionUpdate {
+                before: before.get(name).cloned().flatten(),
+                after: after.get(name).cloned().flatten(),
+            };
+            (name.clone(), update)
+        })
+        .filter(|(_, update)| !changed_only || update.before != update.after)
+        .collect()
 }
 
 #[cfg(test)]
@@ -409,28 +497,76 @@ mod tests {
 
     #[test]
     fn test_update_packages_dry_run_uv() {
-        let result = update_packages(ProjectType::Uv, &["test-package".into()], true, false);
+        let result = update_packages(
+            ProjectType::Uv,
+            &["test-package".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_dry_run_poetry() {
-        let result = update_packages(ProjectType::Poetry, &["test-package".into()], true, false);
+        let result = update_packages(
+            ProjectType::Poetry,
+            &["test-package".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_dry_run_cargo() {
-        let result = update_packages(ProjectType::Cargo, &["test-package".into()], true, false);
+        let result = update_packages(
+            ProjectType::Cargo,
+            &["test-package".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_dry_run_empty() {
-        let result = update_packages(ProjectType::Cargo, &[], true, false);
+        let result = update_packages(ProjectType::Cargo, &[], true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_version_report() {
+        let before = HashMap::from([
+            ("a".to_string(), Some("1.0.0".to_string())),
+            ("b".to_string(), Some("2.0.0".to_string())),
+        ]);
+        let after = HashMap::from([
+            ("a".to_string(), Some("1.1.0".to_string())),
+            ("b".to_string(), Some("2.0.0".to_string())),
+            ("c".to_string(), Some("0.1.0".to_string())),
+        ]);
+        let all = version_report(&before, &after, false);
+        assert_eq!(all.len(), 3);
+        assert_eq!(
+            all["a"],
+            VersionUpdate {
+                before: Some("1.0.0".to_string()),
+                after: Some("1.1.0".to_string()),
+            }
+        );
+        assert_eq!(all["c"].before, None);
+
+        let changed = version_report(&before, &after, true);
+        assert_eq!(changed.keys().collect::<Vec<_>>(), vec!["a", "c"]);
+        assert_eq!(
+            serde_json::to_string(&changed["a"]).unwrap(),
+            r#"{"before":"1.0.0","after":"1.1.0"}"#
+        );
+    }
+
     #[test]
     fn test_update_packages_dry_run_special_chars() {
         let result = update_packages(
@@ -438,6 +574,7 @@ mod tests {
             &["test-package_v1.0".into()],
             true,
             false,
+            OutputFormat::Text,
         );
         assert!(result.is_ok());
     }
@@ -449,37 +586,44 @@ mod tests {
             &["pkg-a".into(), "pkg-b".into()],
             true,
             false,
+            OutputFormat::Text,
         );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_all_dry_run_uv() {
-        let result = update_all(ProjectType::Uv, true, false);
+        let result = update_all(ProjectType::Uv, true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_all_dry_run_poetry() {
-        let result = update_all(ProjectType::Poetry, true, false);
+        let result = update_all(ProjectType::Poetry, true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_all_dry_run_cargo() {
-        let result = update_all(ProjectType::Cargo, true, false);
+        let result = update_all(ProjectType::Cargo, true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_output_format() {
-        let result = update_packages(ProjectType::Cargo, &["test".into()], true, false);
+        let result = update_packages(
+            ProjectType::Cargo,
+            &["test".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_all_output_format() {
-        let result = update_all(ProjectType::Cargo, true, false);
+        let result = update_all(ProjectType::Cargo, true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
@@ -490,37 +634,56 @@ mod tests {
             &["test-package_v1.0".into()],
             true,
             false,
+            OutputFormat::Text,
         );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_all_uv() {
-        let result = update_all(ProjectType::Uv, true, false);
+        let result = update_all(ProjectType::Uv, true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_all_poetry() {
-        let result = update_all(ProjectType::Poetry, true, false);
+        let result = update_all(ProjectType::Poetry, true, false, OutputFormat::Text);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_uv() {
-        let result = update_packages(ProjectType::Uv, &["test-package".into()], true, false);
+        let result = update_packages(
+            ProjectType::Uv,
+            &["test-package".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_poetry() {
-        let result = update_packages(ProjectType::Poetry, &["test-package".into()], true, false);
+        let result = update_packages(
+            ProjectType::Poetry,
+            &["test-package".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_update_packages_cargo() {
-        let result = update_packages(ProjectType::Cargo, &["test-package".into()], true, false);
+        let result = update_packages(
+            ProjectType::Cargo,
+            &["test-package".into()],
+            true,
+            false,
+            OutputFormat::Text,
+        );
         assert!(result.is_ok());
     }
 }
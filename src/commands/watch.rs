@@ -1,114 +1,391 @@
 use anyhow::{Context, Result};
-use std::process::Command;
-use std::time::Duration;
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use time::{OffsetDateTime, UtcOffset};
 
 use crate::utils::colors::Colors;
+use crate::utils::shell;
 use diff;
 
-pub fn run(command: &[String], interval_secs: u64, no_diff: bool) -> Result<()> {
+/// Which output streams of the watched command are captured and diffed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputStreams {
+    #[default]
+    Both,
+    Stdout,
+    Stderr,
+}
+
+impl OutputStreams {
+    pub fn from_flags(stdout_only: bool, stderr_only: bool) -> Self {
+        match (stdout_only, stderr_only) {
+            (true, _) => OutputStreams::Stdout,
+            (false, true) => OutputStreams::Stderr,
+            (false, false) => OutputStreams::Both,
+        }
+    }
+}
+
+/// When `watch` stops on its own instead of looping until Ctrl+C.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StopConditions {
+    /// Stop after this many runs
+    pub max_runs: Option<u64>,
+    /// Stop (successfully) the first time the output changes
+    pub until_change: bool,
+    /// Stop with an error as soon as the command exits non-zero
+    pub exit_on_error: bool,
+}
+
+impl StopConditions {
+    /// Returns the final result once watching should stop, or `None` to keep going.
+    fn after_run(&self, run_count: u64, changed: bool, status: ExitStatus) -> Option<Result<()>> {
+        if self.exit_on_error && !status.success() {
+            return Some(Err(anyhow::anyhow!(
+                "command failed ({})",
+                exit_label(status)
+            )));
+        }
+        if self.until_change && changed {
+            return Some(Ok(()));
+        }
+        if self.max_runs.is_some_and(|max| run_count >= max) {
+            if self.until_change {
+                return Some(Err(anyhow::anyhow!(
+                    "output did not change within {} runs",
+                    run_count
+                )));
+            }
+            return Some(Ok(()));
+        }
+        None
+    }
+}
+
+/// How to get the user's attention when the output changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeNotify {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Shell command to run, e.g. `notify-send "watch: output changed"`
+    pub command: Option<String>,
+}
+
+impl ChangeNotify {
+    fn fire(&self) {
+        if self.bell {
+            print!("\x07");
+        }
+        if let Some(cmd) = &self.command {
+            // Fire and forget: a slow notifier must not delay the next run.
+            let _ = Command::new("sh")
+                .args(["-c", cmd])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+        }
+    }
+}
+
+/// Lets an event through at most once per `min_gap`.
+struct Debounce {
+    min_gap: Duration,
+    last: Option<Instant>,
+}
+
+impl Debounce {
+    fn ready(&mut self, now: Instant) -> bool {
+        if self
+            .last
+            .is_some_and(|last| now.duration_since(last) < self.min_gap)
+        {
+            return false;
+        }
+        self.last = Some(now);
+        true
+    }
+}
+
+/// Turns the CLI arguments into the argv to run. With `use_shell` a single argument is handed
+/// to `sh -c` as written and several are quoted back into one command line; otherwise a single quoted argument such as `"kubectl get pods"` is
+/// split into words without shell interpretation.
+pub fn resolve_command(command: &[String], use_shell: bool) -> Result<Vec<String>> {
+    if use_shell {
+        let script = match command {
+            [single] => single.clone(),
+            _ => command
+                .iter()
+                .map(|arg| shell::quote(arg))
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        return Ok(vec!["sh".to_string(), "-c".to_string(), script]);
+    }
+    match command {
+        [single] if single.contains(char::is_whitespace) => shell::split(single),
+        _ => Ok(command.to_vec()),
+    }
+}
+
+pub fn run(
+    command: &[String],
+    interval_secs: u64,
+    no_diff: bool,
+    diff_options: DiffOptions,
+    streams: OutputStreams,
+    stop: StopConditions,
+    notify: ChangeNotify,
+) -> Result<()> {
     if command.is_empty() {
         anyhow::bail!("command cannot be empty");
     }
     let program = &command[0];
     let args = &command[1..];
 
+    // The local offset can only be read while single-threaded, so resolve it before the
+    // Ctrl+C handler spawns its thread.
+    let local_offset = atty::is(atty::Stream::Stdout)
+        .then(|| UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC));
+
     ctrlc::set_handler(move || std::process::exit(0)).context("setting Ctrl+C handler")?;
 
     let mut colors = Colors::new();
     let mut previous: Option<String> = None;
+    let mut run_count: u64 = 0;
+    let mut notify_debounce = Debounce {
+        min_gap: Duration::from_secs(interval_secs.max(1)),
+        last: None,
+    };
 
     loop {
-        let output = run_cmd(program, args)?;
+        let (output, status) = run_cmd(program, args, streams)?;
+        run_count += 1;
         clear_screen();
         let show_diff = !no_diff && previous.is_some();
-        print_header(interval_secs, command, &mut colors, show_diff)?;
+        let timestamp = local_offset.map(|offset| {
+            let now = OffsetDateTime::now_utc().to_offset(offset);
+            format!("{:02}:{:02}:{:02}", now.hour(), now.minute(), now.second())
+        });
+        print_header(
+            &header_prefix(interval_secs, run_count, timestamp.as_deref()),
+            command,
+            status,
+            &mut colors,
+            show_diff,
+            diff_options.show_removed,
+        )?;
 
-        if no_diff {
-            let _ = colors.reset();
-            let _ = colors.println(&output);
-        } else if let Some(ref prev) = previous {
-            print_diff(prev, &output, &mut colors)?;
-        } else {
-            let _ = colors.reset();
-            let _ = colors.println(&output);
-        }
+        let changed = match previous.as_deref() {
+            Some(prev) if !no_diff => print_diff(prev, &output, diff_options, &mut colors)?,
+            _ => {
+                let _ = colors.reset();
+                let _ = colors.println(&output);
+                previous.as_deref().is_some_and(|prev| prev != output)
+            }
+        };
         previous = Some(output);
 
         let _ = colors.reset();
+        if changed && notify_debounce.ready(Instant::now()) {
+            notify.fire();
+        }
         let _ = colors.flush();
+
+        if let Some(result) = stop.after_run(run_count, changed, status) {
+            return result;
+        }
         std::thread::sleep(Duration::from_secs(interval_secs));
     }
 }
 
-fn run_cmd(program: &str, args: &[String]) -> Result<String> {
+fn run_cmd(program: &str, args: &[String], streams: OutputStreams) -> Result<(String, ExitStatus)> {
     let out = Command::new(program)
         .args(args)
         .output()
         .with_context(|| format!("running {} {}", program, args.join(" ")))?;
-    let mut s = String::from_utf8_lossy(&out.stdout).into_owned();
-    if !out.stderr.is_empty() {
+    let mut s = match streams {
+        OutputStreams::Both | OutputStreams::Stdout => {
+            String::from_utf8_lossy(&out.stdout).into_owned()
+        }
+        OutputStreams::Stderr => String::from_utf8_lossy(&out.stderr).into_owned(),
+    };
+    if streams == OutputStreams::Both && !out.stderr.is_empty() {
         s.push_str(&String::from_utf8_lossy(&out.stderr));
     }
     if s.ends_with('\n') {
         s.pop();
     }
-    Ok(s)
+    Ok((s, out.status))
+}
+
+fn exit_label(status: ExitStatus) -> String {
+    match status.code() {
+        Some(code) => format!("exit {}", code),
+        None => "killed by signal".to_string(),
+    }
 }
 
 fn clear_screen() {
     print!("\x1b[2J\x1b[H");
 }
 
+fn header_prefix(interval_secs: u64, run_count: u64, timestamp: Option<&str>) -> String {
+    match timestamp {
+        Some(time) => format!("Every {}s (#{}, {}): ", interval_secs, run_count, time),
+        None => format!("Every {}s (#{}): ", interval_secs, run_count),
+    }
+}
+
 fn print_header(
-    interval_secs: u64,
+    prefix: &str,
     command: &[String],
+    status: ExitStatus,
     colors: &mut Colors,
     show_diff_hint: bool,
+    show_removed_hint: bool,
 ) -> std::io::Result<()> {
     let _ = colors.cyan();
     let _ = colors.bold();
-    let _ = colors.print(&format!("Every {}s: ", interval_secs));
+    let _ = colors.print(prefix);
     let _ = colors.reset();
-    let _ = colors.println(&command.join(" "));
+    let _ = colors.print(&command.join(" "));
+    let _ = colors.print("  ");
+    if status.success() {
+        let _ = colors.green();
+    } else {
+        let _ = colors.red();
+    }
+    let _ = colors.print(&format!("[{}]", exit_label(status)));
+    let _ = colors.reset();
+    let _ = colors.println("");
     if show_diff_hint {
         let _ = colors.green();
         let _ = colors.print("green");
         let _ = colors.reset();
-        let _ = colors.println(" = changed since last run");
+        let _ = colors.print(" = changed since last run");
+        if show_removed_hint {
+            let _ = colors.print(", ");
+            let _ = colors.red();
+            let _ = colors.print("red");
+            let _ = colors.reset();
+            let _ = colors.print(" = removed");
+        }
+        let _ = colors.println("");
     }
     let _ = colors.println("");
     Ok(())
 }
 
-fn print_diff(prev: &str, curr: &str, colors: &mut Colors) -> std::io::Result<()> {
-    let results = diff::lines(prev, curr);
+/// How a changed line is highlighted against its previous counterpart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffMode {
+    /// Highlight only the characters that changed
+    #[default]
+    Char,
+    /// Highlight the whole line when anything in it changed
+    Line,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    pub mode: DiffMode,
+    /// Show lines that disappeared since the last run, in red with a `-` prefix
+    pub show_removed: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DiffResult {
+    lines: Vec<Vec<Segment>>,
+    /// Whether any line was added, changed or removed, whether or not it is displayed
+    changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Plain(String),
+    Changed(String),
+    Removed(String),
+}
+
+fn print_diff(
+    prev: &str,
+    curr: &str,
+    options: DiffOptions,
+    colors: &mut Colors,
+) -> std::io::Result<bool> {
+    let diff = compute_diff_lines(prev, curr, options);
+    for line in diff.lines {
+        for segment in line {
+            match segment {
+                Segment::Plain(text) => {
+                    let _ = colors.reset();
+                    let _ = colors.print(&text);
+                }
+                Segment::Changed(text) => {
+                    let _ = colors.green();
+                    let _ = colors.print(&text);
+                }
+                Segment::Removed(text) => {
+                    let _ = colors.red();
+                    let _ = colors.print(&format!("- {}", text));
+                }
+            }
+        }
+        let _ = colors.reset();
+        let _ = colors.println("");
+    }
+    Ok(diff.changed)
+}
+
+/// Pairs each current line with its previous counterpart and renders it per `options.mode`.
+/// Lines with no counterpart are entirely new and always fully highlighted; previous lines
+/// with no counterpart are dropped unless `options.show_removed` is set.
+fn compute_diff_lines(prev: &str, curr: &str, options: DiffOptions) -> DiffResult {
+    let mut lines = Vec::new();
+    let mut changed = false;
     let mut pending_lefts: Vec<&str> = Vec::new();
-    for r in results {
+    let flush_removed = |pending: &mut Vec<&str>, lines: &mut Vec<Vec<Segment>>| {
+        if options.show_removed {
+            for line in pending.drain(..) {
+                lines.push(vec![Segment::Removed(line.to_string())]);
+            }
+        }
+    };
+    for r in diff::lines(prev, curr) {
         match r {
             diff::Result::Left(line) => {
+                changed = true;
                 pending_lefts.push(line);
             }
             diff::Result::Both(prev_line, curr_line) => {
-                print_line_char_diff(prev_line, curr_line, colors)?;
+                flush_removed(&mut pending_lefts, &mut lines);
+                lines.push(diff_pair(prev_line, curr_line, options.mode));
             }
             diff::Result::Right(curr_line) => {
+                changed = true;
                 if let Some(prev_line) = pending_lefts.pop() {
-                    print_line_char_diff(prev_line, curr_line, colors)?;
+                    lines.push(diff_pair(prev_line, curr_line, options.mode));
                 } else {
-                    let _ = colors.green();
-                    let _ = colors.println(curr_line);
+                    lines.push(vec![Segment::Changed(curr_line.to_string())]);
                 }
             }
         }
     }
-    Ok(())
+    flush_removed(&mut pending_lefts, &mut lines);
+    DiffResult { lines, changed }
 }
 
-fn print_line_char_diff(
-    prev_line: &str,
-    curr_line: &str,
-    colors: &mut Colors,
-) -> std::io::Result<()> {
+fn diff_pair(prev_line: &str, curr_line: &str, mode: DiffMode) -> Vec<Segment> {
+    match mode {
+        DiffMode::Char => compute_char_diff(prev_line, curr_line),
+        DiffMode::Line if prev_line == curr_line => vec![Segment::Plain(curr_line.to_string())],
+        DiffMode::Line => vec![Segment::Changed(curr_line.to_string())],
+    }
+}
+
+fn compute_char_diff(prev_line: &str, curr_line: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
     let mut normal = String::new();
     let mut green = String::new();
     for r in diff::chars(prev_line, curr_line) {
@@ -116,39 +393,272 @@ fn print_line_char_diff(
             diff::Result::Left(_) => {}
             diff::Result::Both(c, _) => {
                 if !green.is_empty() {
-                    let _ = colors.green();
-                    let _ = colors.print(&green);
-                    let _ = colors.reset();
-                    green.clear();
+                    segments.push(Segment::Changed(std::mem::take(&mut green)));
                 }
                 normal.push(c);
             }
             diff::Result::Right(c) => {
                 if !normal.is_empty() {
-                    let _ = colors.reset();
-                    let _ = colors.print(&normal);
-                    normal.clear();
+                    segments.push(Segment::Plain(std::mem::take(&mut normal)));
                 }
                 green.push(c);
             }
         }
     }
-    let _ = colors.reset();
-    let _ = colors.print(&normal);
-    let _ = colors.green();
-    let _ = colors.print(&green);
-    let _ = colors.reset();
-    let _ = colors.println("");
-    Ok(())
+    if !normal.is_empty() {
+        segments.push(Segment::Plain(normal));
+    }
+    if !green.is_empty() {
+        segments.push(Segment::Changed(green));
+    }
+    segments
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_command() {
+        assert_eq!(
+            resolve_command(&argv(&["ls", "-la"]), false).unwrap(),
+            argv(&["ls", "-la"])
+        );
+        assert_eq!(
+            resolve_command(&argv(&["kubectl get pods -l 'app=web'"]), false).unwrap(),
+            argv(&["kubectl", "get", "pods", "-l", "app=web"])
+        );
+        assert_eq!(
+            resolve_command(&argv(&["ps aux | grep x"]), true).unwrap(),
+            argv(&["sh", "-c", "ps aux | grep x"])
+        );
+        assert!(resolve_command(&argv(&["echo 'oops"]), false).is_err());
+    }
+
+    #[test]
+    fn test_resolve_command_shell_quotes_multiple_args() {
+        assert_eq!(
+            resolve_command(&argv(&["grep", "a b", "f"]), true).unwrap(),
+            argv(&["sh", "-c", "grep 'a b' f"])
+        );
+    }
+
     #[test]
     fn test_run_empty_command_errors() {
-        let err = run(&[], 1, false).unwrap_err();
+        let err = run(
+            &[],
+            1,
+            false,
+            DiffOptions::default(),
+            OutputStreams::Both,
+            StopConditions::default(),
+            ChangeNotify::default(),
+        )
+        .unwrap_err();
         assert!(err.to_string().contains("empty"));
     }
+
+    #[test]
+    fn test_debounce_limits_rate() {
+        let start = Instant::now();
+        let mut debounce = Debounce {
+            min_gap: Duration::from_secs(2),
+            last: None,
+        };
+        assert!(debounce.ready(start));
+        assert!(!debounce.ready(start + Duration::from_secs(1)));
+        assert!(debounce.ready(start + Duration::from_secs(2)));
+        assert!(!debounce.ready(start + Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_header_prefix() {
+        assert_eq!(
+            header_prefix(2, 7, Some("14:03:22")),
+            "Every 2s (#7, 14:03:22): "
+        );
+        assert_eq!(header_prefix(5, 1, None), "Every 5s (#1): ");
+    }
+
+    fn char_mode() -> DiffOptions {
+        DiffOptions::default()
+    }
+
+    fn line_mode() -> DiffOptions {
+        DiffOptions {
+            mode: DiffMode::Line,
+            ..DiffOptions::default()
+        }
+    }
+
+    fn plain(text: &str) -> Segment {
+        Segment::Plain(text.to_string())
+    }
+
+    fn changed(text: &str) -> Segment {
+        Segment::Changed(text.to_string())
+    }
+
+    #[test]
+    fn test_char_diff_highlights_changed_characters() {
+        assert_eq!(
+            compute_diff_lines("count: 1\nsame", "count: 2\nsame", char_mode()).lines,
+            vec![vec![plain("count: "), changed("2")], vec![plain("same")]]
+        );
+    }
+
+    #[test]
+    fn test_line_diff_highlights_whole_changed_line() {
+        assert_eq!(
+            compute_diff_lines("count: 1\nsame", "count: 2\nsame", line_mode()).lines,
+            vec![vec![changed("count: 2")], vec![plain("same")]]
+        );
+    }
+
+    #[test]
+    fn test_diff_new_lines_fully_highlighted_in_both_modes() {
+        for options in [char_mode(), line_mode()] {
+            assert_eq!(
+                compute_diff_lines("a", "a\nb", options).lines,
+                vec![vec![plain("a")], vec![changed("b")]],
+                "{options:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_changes() {
+        assert!(!compute_diff_lines("a\nb", "a\nb", char_mode()).changed);
+        assert!(compute_diff_lines("a", "a\nb", char_mode()).changed);
+        assert!(compute_diff_lines("a", "b", line_mode()).changed);
+        // Removed lines count as a change even when they are not displayed.
+        let removed = compute_diff_lines("a\ngone", "a", char_mode());
+        assert!(removed.changed);
+        assert_eq!(removed.lines, vec![vec![plain("a")]]);
+    }
+
+    fn status_of(program: &str) -> ExitStatus {
+        Command::new(program).status().unwrap()
+    }
+
+    #[test]
+    fn test_stop_conditions_after_run() {
+        let ok = status_of("true");
+        let unbounded = StopConditions::default();
+        assert!(unbounded.after_run(100, true, ok).is_none());
+
+        let max_runs = StopConditions {
+            max_runs: Some(2),
+            ..StopConditions::default()
+        };
+        assert!(max_runs.after_run(1, true, ok).is_none());
+        assert!(max_runs.after_run(2, false, ok).unwrap().is_ok());
+
+        let until_change = StopConditions {
+            max_runs: Some(3),
+            until_change: true,
+            ..StopConditions::default()
+        };
+        assert!(until_change.after_run(1, false, ok).is_none());
+        assert!(until_change.after_run(2, true, ok).unwrap().is_ok());
+        let err = until_change.after_run(3, false, ok).unwrap().unwrap_err();
+        assert!(err.to_string().contains("did not change"));
+    }
+
+    #[test]
+    fn test_stop_conditions_exit_on_error() {
+        let failed = status_of("false");
+        assert!(StopConditions::default()
+            .after_run(1, false, failed)
+            .is_none());
+
+        let exit_on_error = StopConditions {
+            exit_on_error: true,
+            ..StopConditions::default()
+        };
+        assert!(exit_on_error
+            .after_run(1, false, status_of("true"))
+            .is_none());
+        let err = exit_on_error
+            .after_run(1, false, failed)
+            .unwrap()
+            .unwrap_err();
+        assert!(err.to_string().contains("exit 1"));
+    }
+
+    #[test]
+    fn test_run_cmd_returns_exit_status() {
+        let args = vec!["-c".to_string(), "echo oops; exit 3".to_string()];
+        let (output, status) = run_cmd("sh", &args, OutputStreams::Both).unwrap();
+        assert_eq!(output, "oops");
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(exit_label(status), "exit 3");
+    }
+
+    #[test]
+    fn test_diff_drops_removed_lines_by_default() {
+        assert_eq!(
+            compute_diff_lines("a\ngone\nb", "a\nb", char_mode()).lines,
+            vec![vec![plain("a")], vec![plain("b")]]
+        );
+    }
+
+    #[test]
+    fn test_diff_show_removed_emits_removed_lines() {
+        let options = DiffOptions {
+            show_removed: true,
+            ..char_mode()
+        };
+        assert_eq!(
+            compute_diff_lines("a\ngone\nb", "a\nb", options).lines,
+            vec![
+                vec![plain("a")],
+                vec![Segment::Removed("gone".to_string())],
+                vec![plain("b")],
+            ]
+        );
+        assert_eq!(
+            compute_diff_lines("a\ntail", "a", options).lines,
+            vec![vec![plain("a")], vec![Segment::Removed("tail".to_string())]]
+        );
+    }
+
+    fn both_streams_args() -> Vec<String> {
+        vec!["-c".to_string(), "printf out; printf err >&2".to_string()]
+    }
+
+    #[test]
+    fn test_run_cmd_combines_streams_by_default() {
+        let (output, _) = run_cmd("sh", &both_streams_args(), OutputStreams::Both).unwrap();
+        assert_eq!(output, "outerr");
+    }
+
+    #[test]
+    fn test_run_cmd_stdout_only() {
+        let (output, _) = run_cmd("sh", &both_streams_args(), OutputStreams::Stdout).unwrap();
+        assert_eq!(output, "out");
+    }
+
+    #[test]
+    fn test_run_cmd_stderr_only() {
+        let (output, _) = run_cmd("sh", &both_streams_args(), OutputStreams::Stderr).unwrap();
+        assert_eq!(output, "err");
+    }
+
+    #[test]
+    fn test_output_streams_from_flags() {
+        assert_eq!(OutputStreams::from_flags(false, false), OutputStreams::Both);
+        assert_eq!(
+            OutputStreams::from_flags(true, false),
+            OutputStreams::Stdout
+        );
+        assert_eq!(
+            OutputStreams::from_flags(false, true),
+            OutputStreams::Stderr
+        );
+    }
 }
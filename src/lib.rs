@@ -23,32 +23,170 @@ pub fn run(args: Vec<String>) -> Result<()> {
         }
     }
     let app = BashersApp::parse_from(args);
+    utils::colors::set_no_color(app.no_color);
+    utils::log::set_verbosity(app.verbose);
+    let output = app.output;
+    // Loaded only by the commands that read it, so a broken config file can't keep
+    // `bashers config`, `version` or `self update` from running.
+    let load_config = utils::config::Config::load;
 
     match app.command {
         Some(cli::Commands::Update {
             packages,
             dry_run,
             auto_select,
-            verbose,
-        }) => commands::update::run(&packages, dry_run, auto_select, verbose)?,
+            min_score,
+            project,
+        }) => commands::update::run(
+            &packages,
+            dry_run,
+            auto_select,
+            min_score,
+            app.verbose > 0,
+            output,
+            project,
+        )?,
         Some(cli::Commands::Setup {
             frozen,
             rm,
             dry_run,
-        }) => commands::setup::run(frozen, rm, dry_run)?,
-        Some(cli::Commands::Show { patterns }) => commands::show::run(&patterns)?,
+            list,
+            project,
+        }) => commands::setup::run(frozen, rm, dry_run, list, project)?,
+        Some(cli::Commands::Show {
+            patterns,
+            json,
+            depth,
+            sort,
+            outdated,
+            min_score,
+            project,
+        }) => commands::show::run(
+            &patterns,
+            &commands::show::ShowOptions {
+                json: json || output.is_json(),
+                depth,
+                sort,
+                outdated,
+                min_score,
+                project,
+            },
+        )?,
         Some(cli::Commands::Git { command }) => match command {
-            cli::GitCommands::Sync { current, dry_run } => {
-                commands::git::sync::run(current, dry_run)?
-            }
+            cli::GitCommands::Sync {
+                current,
+                rebase,
+                all_branches,
+                dry_run,
+            } => commands::git::sync::run(current, rebase, all_branches, dry_run)?,
         },
         Some(cli::Commands::Kube { command }) => match command {
-            cli::KubeCommands::Kmg { patterns } => commands::kube::kmg::run(&patterns)?,
+            cli::KubeCommands::Ctx { name } => commands::kube::ctx::run(name.as_deref(), output)?,
+            cli::KubeCommands::Ns { name } => commands::kube::ns::run(name.as_deref(), output)?,
+            cli::KubeCommands::Kmg { patterns, format } => commands::kube::kmg::run(
+                &patterns,
+                if output.is_json() {
+                    commands::kube::kmg::KmgFormat::Json
+                } else {
+                    format
+                },
+            )?,
+            cli::KubeCommands::Pods {
+                patterns,
+                context,
+                namespace,
+            } => {
+                let config = load_config()?;
+                commands::kube::pods::run(
+                    &patterns,
+                    &commands::kube::track::KubeTarget {
+                        context: context.or(config.kube.context),
+                        namespace: namespace.or(config.kube.namespace),
+                        ..Default::default()
+                    }
+                    .with_kubectl()?,
+                    output,
+                )?
+            }
             cli::KubeCommands::Track {
                 patterns,
                 err_only,
+                trace_style,
+                block_start,
+                block_continue,
                 simple,
-            } => commands::kube::track::run(&patterns, err_only, simple)?,
+                context,
+                namespace,
+                initial_tab,
+                select,
+                nav,
+                follow_errors,
+                json_logs,
+                raw,
+                min_pane_height,
+                single_tab,
+                columns,
+                node,
+                timeout,
+                retry,
+                since_restart,
+                pod_cache_ttl,
+                poll_interval,
+                theme,
+            } => {
+                let config = load_config()?;
+                let target = commands::kube::track::KubeTarget {
+                    context: context.or(config.kube.context),
+                    namespace: namespace.or(config.kube.namespace),
+                    columns,
+                    node,
+                    timeout: timeout.map(std::time::Duration::from_secs),
+                    retry,
+                    since_restart,
+                    kubectl: None,
+                }
+                .with_kubectl()?;
+                if output.is_json() {
+                    commands::kube::pods::run(&patterns, &target, output)?
+                } else {
+                    commands::kube::track::run(
+                        &patterns,
+                        commands::kube::track::LogFilter {
+                            err_only,
+                            trace_style,
+                            block: block_start
+                                .as_deref()
+                                .map(|start| {
+                                    commands::kube::track::BlockPattern::new(
+                                        start,
+                                        block_continue.as_deref(),
+                                    )
+                                })
+                                .transpose()?,
+                        },
+                        simple,
+                        json_logs,
+                        target,
+                        commands::kube::track::TuiOptions {
+                            initial_view: commands::kube::track::InitialView {
+                                tab: initial_tab,
+                                select,
+                            },
+                            nav_mode: nav,
+                            follow_errors,
+                            raw,
+                            min_pane_height,
+                            single_tab,
+                            pod_cache_ttl: pod_cache_ttl.map(std::time::Duration::from_secs),
+                            poll_interval: poll_interval.map(std::time::Duration::from_secs),
+                            theme: commands::kube::track::Theme::resolve(
+                                theme,
+                                config.kube.palette.as_deref().unwrap_or_default(),
+                            )?,
+                        },
+                    )?
+                }
+            }
         },
         Some(cli::Commands::Docker { command }) => match command {
             cli::DockerCommands::Build {
@@ -56,21 +194,93 @@ pub fn run(args: Vec<String>) -> Result<()> {
                 tag,
                 no_cache,
                 context,
-            } => commands::docker::build::run(
-                dockerfile.as_deref(),
-                tag.as_deref(),
+                build_args,
+                platform,
+                push,
+                tag_from_git,
+                latest,
+                progress,
+                quiet,
+                dry_run,
+            } => commands::docker::build::run(&commands::docker::build::BuildOptions {
+                dockerfile,
+                tag,
                 no_cache,
-                context.as_deref(),
-            )?,
+                context,
+                build_args,
+                platform: match platform {
+                    Some(platform) => Some(platform),
+                    None => load_config()?.docker.platform,
+                },
+                push,
+                tag_from_git,
+                latest,
+                progress,
+                quiet,
+                dry_run,
+            })?,
+            cli::DockerCommands::Logs { patterns, err_only } => {
+                commands::docker::logs::run(&patterns, err_only)?
+            }
         },
+        Some(cli::Commands::Tail { paths, err_only }) => commands::tail::run(&paths, err_only)?,
         Some(cli::Commands::Version) => println!("v{}", env!("CARGO_PKG_VERSION")),
+        Some(cli::Commands::Completions { shell }) => commands::completions::run(shell)?,
+        Some(cli::Commands::Config { init }) => commands::config::run(init)?,
         Some(cli::Commands::Watch {
             command,
             interval,
             no_diff,
-        }) => commands::watch::run(&command, interval, no_diff)?,
+            max_runs,
+            until_change,
+            exit_on_error,
+            bell,
+            notify_cmd,
+            line_diff,
+            show_removed,
+            stdout_only,
+            stderr_only,
+            shell,
+        }) => commands::watch::run(
+            &commands::watch::resolve_command(&command, shell)?,
+            match interval {
+                Some(interval) => interval,
+                None => load_config()?.watch.interval.unwrap_or(2),
+            },
+            no_diff,
+            commands::watch::DiffOptions {
+                mode: if line_diff {
+                    commands::watch::DiffMode::Line
+                } else {
+                    commands::watch::DiffMode::Char
+                },
+                show_removed,
+            },
+            commands::watch::OutputStreams::from_flags(stdout_only, stderr_only),
+            commands::watch::StopConditions {
+                max_runs,
+                until_change,
+                exit_on_error,
+            },
+            commands::watch::ChangeNotify {
+                bell,
+                command: notify_cmd,
+            },
+        )?,
         Some(cli::Commands::SelfCmd { command }) => match command {
-            cli::SelfCommands::Update => commands::self_cmd::update::run()?,
+            cli::SelfCommands::Update {
+                version,
+                pre,
+                force,
+                check,
+                exit_code,
+            } => commands::self_cmd::update::run(&commands::self_cmd::update::UpdateOptions {
+                version,
+                pre,
+                force,
+                check,
+                exit_code,
+            })?,
         },
         None => commands::help::run()?,
     }
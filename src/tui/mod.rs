@@ -1,10 +1,26 @@
 use anyhow::Result;
+use crossterm::clipboard::CopyToClipboard;
 use crossterm::event::{self as crossterm_event, DisableMouseCapture, EnableMouseCapture, Event};
 use crossterm::ExecutableCommand;
-use ratatui::layout::Rect;
+use ratatui::layout::{Rect, Size};
+use ratatui::widgets::{Paragraph, Wrap};
 use ratatui::{DefaultTerminal, Frame};
+use std::panic::{self, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::utils::log;
+
+type PanicHook = dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static;
+
+/// Below this size panes collapse to nothing, so a notice is drawn instead.
+const MIN_TERMINAL_WIDTH: u16 = 30;
+const MIN_TERMINAL_HEIGHT: u16 = 6;
+
+/// Whether the terminal is in raw mode with the alternate screen and mouse capture on.
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
 pub trait TuiApp {
     fn update_layout(&mut self, term_size: Rect, available_height: u16);
     fn render(&self, frame: &mut Frame);
@@ -14,14 +30,47 @@ pub trait TuiApp {
 }
 
 pub fn run<T: TuiApp>(mut app: T) -> Result<()> {
+    // Debug lines on stderr would tear the alternate screen, so `-v` is muted while it is up.
+    let verbosity = log::verbosity();
+    log::set_verbosity(0);
     let mut terminal = ratatui::init();
-    std::io::stdout().execute(EnableMouseCapture)?;
-    let result = run_loop(&mut terminal, &mut app);
+    TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+    let previous_hook = install_panic_hook();
+    let result = std::io::stdout()
+        .execute(EnableMouseCapture)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| run_loop(&mut terminal, &mut app));
     teardown();
+    panic::set_hook(Box::new(move |info| previous_hook(info)));
+    log::set_verbosity(verbosity);
     result
 }
 
+/// Restores the terminal before the panic message is printed, so it isn't lost on the
+/// alternate screen. Returns the hook it replaced.
+fn install_panic_hook() -> Arc<PanicHook> {
+    let previous: Arc<PanicHook> = Arc::from(panic::take_hook());
+    let chained = Arc::clone(&previous);
+    panic::set_hook(Box::new(move |info| {
+        teardown();
+        chained(info);
+    }));
+    previous
+}
+
+/// Copies `text` to the system clipboard through the terminal (OSC 52), which also works
+/// over SSH. Terminals without OSC 52 support silently ignore it.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    std::io::stdout().execute(CopyToClipboard::to_clipboard_from(text))?;
+    Ok(())
+}
+
+/// Leaves raw mode, the alternate screen and mouse capture. Only the first call after
+/// `run` starts does anything, so the panic hook and the normal exit path can both call it.
 pub(crate) fn teardown() {
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
     let _ = std::io::stdout().execute(DisableMouseCapture);
     ratatui::restore();
 }
@@ -30,24 +79,83 @@ fn run_loop<T: TuiApp>(terminal: &mut DefaultTerminal, app: &mut T) -> Result<()
     loop {
         app.process_background();
         let term_size = terminal.size()?;
-        let available_height = term_size.height.saturating_sub(1);
-        app.update_layout(term_size.into(), available_height);
-        terminal.draw(|frame| app.render(frame))?;
-        if crossterm_event::poll(app.poll_interval())?
-            && app.handle_event(crossterm_event::read()?)?
-        {
-            break;
+        if is_too_small(term_size) {
+            terminal.draw(|frame| render_too_small(frame, term_size))?;
+        } else {
+            let available_height = term_size.height.saturating_sub(1);
+            app.update_layout(term_size.into(), available_height);
+            terminal.draw(|frame| app.render(frame))?;
+        }
+        if !crossterm_event::poll(app.poll_interval())? {
+            continue;
+        }
+        match crossterm_event::read()? {
+            // Redraw at the new size right away instead of after the next poll interval.
+            Event::Resize(_, _) => terminal.autoresize()?,
+            event => {
+                if app.handle_event(event)? {
+                    break;
+                }
+            }
         }
     }
     Ok(())
 }
 
+fn is_too_small(size: Size) -> bool {
+    size.width < MIN_TERMINAL_WIDTH || size.height < MIN_TERMINAL_HEIGHT
+}
+
+fn render_too_small(frame: &mut Frame, size: Size) {
+    let message = format!(
+        "Terminal too small ({}x{}), need at least {}x{}",
+        size.width, size.height, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+    );
+    frame.render_widget(
+        Paragraph::new(message).wrap(Wrap { trim: true }),
+        frame.area(),
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
 
     #[test]
     fn test_teardown_disables_mouse_capture() {
         teardown();
     }
+
+    #[test]
+    fn test_too_small_terminal_renders_notice() {
+        assert!(is_too_small(Size::new(29, 40)));
+        assert!(is_too_small(Size::new(120, 5)));
+        assert!(!is_too_small(Size::new(30, 6)));
+
+        let size = Size::new(20, 4);
+        let mut terminal = Terminal::new(TestBackend::new(size.width, size.height)).unwrap();
+        terminal
+            .draw(|frame| render_too_small(frame, size))
+            .unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(text.contains("Terminal too"), "{text:?}");
+        assert!(text.contains("(20x4)"), "{text:?}");
+    }
+
+    #[test]
+    fn test_teardown_is_idempotent() {
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        teardown();
+        assert!(!TERMINAL_ACTIVE.load(Ordering::SeqCst));
+        teardown();
+        assert!(!TERMINAL_ACTIVE.load(Ordering::SeqCst));
+    }
 }
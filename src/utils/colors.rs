@@ -1,4 +1,5 @@
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 pub const ANSI_CYAN_BOLD: &str = "\x1b[36m\x1b[1m";
@@ -8,6 +9,49 @@ pub const ANSI_YELLOW: &str = "\x1b[33m";
 pub const ANSI_DIM: &str = "\x1b[2m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+static NO_COLOR_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Turns color off for the rest of the process (the global `--no-color` flag).
+pub fn set_no_color(no_color: bool) {
+    NO_COLOR_FLAG.store(no_color, Ordering::Relaxed);
+}
+
+/// Whether output written to `stream` should be colored. `--no-color` and `NO_COLOR` turn
+/// color off, `FORCE_COLOR` turns it on even when piped; otherwise only terminals get color.
+pub fn use_color(stream: atty::Stream) -> bool {
+    color_policy(
+        NO_COLOR_FLAG.load(Ordering::Relaxed),
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("FORCE_COLOR").ok().as_deref(),
+        || atty::is(stream),
+    )
+}
+
+/// The `termcolor` choice matching [`use_color`].
+pub fn color_choice(stream: atty::Stream) -> ColorChoice {
+    if use_color(stream) {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    }
+}
+
+fn color_policy(
+    no_color_flag: bool,
+    no_color_env: Option<&str>,
+    force_color_env: Option<&str>,
+    is_tty: impl FnOnce() -> bool,
+) -> bool {
+    if no_color_flag || no_color_env.is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    match force_color_env {
+        Some("0") | Some("false") => false,
+        Some(v) if !v.is_empty() => true,
+        _ => is_tty(),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VersionChange {
     Upgraded,
@@ -16,7 +60,16 @@ pub enum VersionChange {
 }
 
 pub fn format_bumped_message_colored(before: &str, after: &str, change: VersionChange) -> String {
-    if atty::is(atty::Stream::Stderr) {
+    format_bumped_message(before, after, change, use_color(atty::Stream::Stderr))
+}
+
+fn format_bumped_message(
+    before: &str,
+    after: &str,
+    change: VersionChange,
+    use_color: bool,
+) -> String {
+    if use_color {
         let after_color = match change {
             VersionChange::Upgraded => ANSI_GREEN,
             VersionChange::Unchanged => ANSI_DIM,
@@ -31,6 +84,16 @@ pub fn format_bumped_message_colored(before: &str, after: &str, change: VersionC
     }
 }
 
+/// Removes ANSI escape sequences (colors, cursor movement) from `text`.
+pub fn strip_ansi(text: &str) -> String {
+    static ANSI_ESCAPE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = ANSI_ESCAPE.get_or_init(|| {
+        regex::Regex::new(r"\x1b(\[[0-9;?]*[ -/]*[@-~]|\][^\x07]*\x07|[@-Z\\-_])")
+            .expect("ANSI escape pattern must be valid")
+    });
+    re.replace_all(text, "").into_owned()
+}
+
 pub struct Colors {
     stdout: StandardStream,
 }
@@ -43,13 +106,8 @@ impl Default for Colors {
 
 impl Colors {
     pub fn new() -> Self {
-        let choice = if atty::is(atty::Stream::Stdout) {
-            ColorChoice::Auto
-        } else {
-            ColorChoice::Never
-        };
         Self {
-            stdout: StandardStream::stdout(choice),
+            stdout: StandardStream::stdout(color_choice(atty::Stream::Stdout)),
         }
     }
 
@@ -204,6 +262,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi(&format!("{}ok{} done", ANSI_CYAN_BOLD, ANSI_RESET)),
+            "ok done"
+        );
+        assert_eq!(strip_ansi("\x1b[38;5;208mwarn\x1b[0m"), "warn");
+        assert_eq!(strip_ansi("\x1b]0;title\x07plain"), "plain");
+        assert_eq!(strip_ansi("no escapes"), "no escapes");
+    }
+
+    #[test]
+    fn test_color_policy() {
+        let tty = || true;
+        let pipe = || false;
+        assert!(color_policy(false, None, None, tty));
+        assert!(!color_policy(false, None, None, pipe));
+        assert!(!color_policy(true, None, Some("1"), tty));
+        assert!(!color_policy(false, Some("1"), Some("1"), tty));
+        assert!(color_policy(false, Some(""), None, tty));
+        assert!(color_policy(false, None, Some("1"), pipe));
+        assert!(!color_policy(false, None, Some("0"), tty));
+        assert!(!color_policy(false, None, Some(""), pipe));
+    }
+
+    #[test]
+    fn test_format_bumped_message_without_color_has_no_escapes() {
+        let s = format_bumped_message("v1.0.0", "v2.0.0", VersionChange::Upgraded, false);
+        assert_eq!(s, "bumped from v1.0.0 -> v2.0.0");
+        let s = format_bumped_message("v1.0.0", "v2.0.0", VersionChange::Upgraded, true);
+        assert!(s.contains(ANSI_GREEN));
+    }
+
     #[test]
     fn test_version_change_equality() {
         assert_eq!(VersionChange::Upgraded, VersionChange::Upgraded);
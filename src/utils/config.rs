@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+pub const LOCAL_CONFIG_FILE: &str = ".bashers.toml";
+
+/// Defaults for command flags, read from `~/.config/bashers/config.toml` and `.bashers.toml`.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub kube: KubeConfig,
+    pub watch: WatchConfig,
+    pub docker: DockerConfig,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KubeConfig {
+    pub context: Option<String>,
+    pub namespace: Option<String>,
+    /// Hex colors (e.g. `"#e69f00"`) for `kube track` panes, replacing the theme's palette.
+    pub palette: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchConfig {
+    pub interval: Option<u64>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DockerConfig {
+    pub platform: Option<String>,
+}
+
+impl Config {
+    /// Loads the global config, then the nearest `.bashers.toml`; missing files are skipped.
+    pub fn load() -> Result<Self> {
+        let mut config = Config::default();
+        for path in config_paths() {
+            if path.is_file() {
+                config = config.merge(read(&path)?);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Field-wise merge where values set in `other` win.
+    fn merge(self, other: Config) -> Config {
+        Config {
+            kube: KubeConfig {
+                context: other.kube.context.or(self.kube.context),
+                namespace: other.kube.namespace.or(self.kube.namespace),
+                palette: other.kube.palette.or(self.kube.palette),
+            },
+            watch: WatchConfig {
+                interval: other.watch.interval.or(self.watch.interval),
+            },
+            docker: DockerConfig {
+                platform: other.docker.platform.or(self.docker.platform),
+            },
+        }
+    }
+}
+
+/// Parses a single config file.
+pub fn read(path: &Path) -> Result<Config> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config {}", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("Invalid config {}", path.display()))
+}
+
+/// Config files in load order: the global file first, then the repo-local one.
+pub fn config_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(global) = global_config_file() {
+        paths.push(global);
+    }
+    if let Some(local) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_local_config(&cwd))
+    {
+        paths.push(local);
+    }
+    paths
+}
+
+/// `$XDG_CONFIG_HOME/bashers/config.toml`, falling back to `~/.config/bashers/config.toml`.
+pub fn global_config_file() -> Option<PathBuf> {
+    global_config_path(
+        std::env::var("XDG_CONFIG_HOME").ok().as_deref(),
+        std::env::var("HOME").ok().as_deref(),
+    )
+}
+
+fn global_config_path(xdg_config_home: Option<&str>, home: Option<&str>) -> Option<PathBuf> {
+    let base = match xdg_config_home.filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(home.filter(|dir| !dir.is_empty())?).join(".config"),
+    };
+    Some(base.join("bashers").join("config.toml"))
+}
+
+fn find_local_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(LOCAL_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = toml::from_str(
+            "[kube]\nnamespace = \"payments\"\n[watch]\ninterval = 5\n[docker]\nplatform = \"linux/arm64\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.kube.namespace.as_deref(), Some("payments"));
+        assert_eq!(config.kube.context, None);
+        assert_eq!(config.watch.interval, Some(5));
+        assert_eq!(config.docker.platform.as_deref(), Some("linux/arm64"));
+
+        assert!(toml::from_str::<Config>("[kube]\nnamspace = \"typo\"\n").is_err());
+    }
+
+    #[test]
+    fn test_merge_prefers_later_values() {
+        let global: Config =
+            toml::from_str("[kube]\ncontext = \"prod\"\nnamespace = \"default\"\n").unwrap();
+        let local: Config = toml::from_str("[kube]\nnamespace = \"payments\"\n").unwrap();
+        let merged = global.merge(local);
+        assert_eq!(merged.kube.context.as_deref(), Some("prod"));
+        assert_eq!(merged.kube.namespace.as_deref(), Some("payments"));
+    }
+
+    #[test]
+    fn test_parse_kube_palette() {
+        let config: Config =
+            toml::from_str("[kube]\npalette = [\"#e69f00\", \"#56b4e9\"]\n").unwrap();
+        assert_eq!(
+            config.kube.palette,
+            Some(vec!["#e69f00".to_string(), "#56b4e9".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_global_config_path() {
+        assert_eq!(
+            global_config_path(Some("/xdg"), Some("/home/me")),
+            Some(PathBuf::from("/xdg/bashers/config.toml"))
+        );
+        assert_eq!(
+            global_config_path(Some(""), Some("/home/me")),
+            Some(PathBuf::from("/home/me/.config/bashers/config.toml"))
+        );
+        assert_eq!(global_config_path(None, None), None);
+    }
+}
@@ -0,0 +1,88 @@
+use std::io;
+use std::process::{Child, Command, ExitStatus, Output};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::utils::shell;
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the global `-v` count: 1 logs external commands, 2 also logs how long they took.
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+pub fn debug(message: &str) {
+    if verbosity() >= 1 {
+        eprintln!("[debug] {message}");
+    }
+}
+
+fn describe(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| shell::quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn timing_line(cmd: &Command, elapsed: Duration) -> String {
+    format!("{} took {:.2}s", describe(cmd), elapsed.as_secs_f64())
+}
+
+fn timed<T>(cmd: &mut Command, run: impl FnOnce(&mut Command) -> T) -> T {
+    debug(&format!("+ {}", describe(cmd)));
+    let start = Instant::now();
+    let result = run(cmd);
+    if verbosity() >= 2 {
+        debug(&timing_line(cmd, start.elapsed()));
+    }
+    result
+}
+
+/// `output`/`status`/`spawn` that first log the command line when running with `-v`.
+pub trait CommandExt {
+    fn logged_output(&mut self) -> io::Result<Output>;
+    fn logged_status(&mut self) -> io::Result<ExitStatus>;
+    fn logged_spawn(&mut self) -> io::Result<Child>;
+}
+
+impl CommandExt for Command {
+    fn logged_output(&mut self) -> io::Result<Output> {
+        timed(self, Command::output)
+    }
+
+    fn logged_status(&mut self) -> io::Result<ExitStatus> {
+        timed(self, Command::status)
+    }
+
+    fn logged_spawn(&mut self) -> io::Result<Child> {
+        debug(&format!("+ {}", describe(self)));
+        self.spawn()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_quotes_args() {
+        let mut cmd = Command::new("kubectl");
+        cmd.args(["get", "pods", "-l", "app=my api"]);
+        assert_eq!(describe(&cmd), "kubectl get pods -l 'app=my api'");
+    }
+
+    #[test]
+    fn test_timing_line() {
+        let cmd = Command::new("git");
+        assert_eq!(
+            timing_line(&cmd, Duration::from_millis(1500)),
+            "git took 1.50s"
+        );
+    }
+}
@@ -1,5 +1,11 @@
 pub mod colors;
+pub mod config;
+pub mod git;
+pub mod log;
 pub mod multi_progress;
+pub mod output;
 pub mod packages;
+pub mod pattern;
 pub mod project;
+pub mod shell;
 pub mod spinner;
@@ -1,3 +1,4 @@
+use crate::utils::colors;
 use anyhow::Result;
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::thread;
@@ -17,6 +18,19 @@ pub fn multi_progress_stderr() -> MultiProgress {
     MultiProgress::with_draw_target(draw_target)
 }
 
+/// A spinner style for `template`, without its `.dim` styling when color is off.
+fn spinner_style(template: &str) -> ProgressStyle {
+    let template = if colors::use_color(atty::Stream::Stderr) {
+        template.to_string()
+    } else {
+        template.replace(":.dim", "")
+    };
+    ProgressStyle::default_spinner()
+        .template(&template)
+        .unwrap()
+        .tick_strings(SPINNER_TICKS)
+}
+
 pub fn run_header_spinner<F, T>(
     multi: &MultiProgress,
     loading_msg: &str,
@@ -27,10 +41,7 @@ pub fn run_header_spinner<F, T>(
 where
     F: FnOnce() -> Result<T>,
 {
-    let style = ProgressStyle::default_spinner()
-        .template("{spinner:.dim}{msg}")
-        .unwrap()
-        .tick_strings(SPINNER_TICKS);
+    let style = spinner_style("{spinner:.dim}{msg}");
     let pb = multi.add(
         ProgressBar::new_spinner()
             .with_style(style)
@@ -67,10 +78,7 @@ where
         return Vec::new();
     }
 
-    let style = ProgressStyle::default_spinner()
-        .template("{prefix}{spinner:.dim}{msg}")
-        .unwrap()
-        .tick_strings(SPINNER_TICKS);
+    let style = spinner_style("{prefix}{spinner:.dim}{msg}");
 
     thread::scope(|s| {
         let per_item_ref = &per_item;
@@ -119,10 +127,7 @@ where
         return op();
     }
 
-    let style = ProgressStyle::default_spinner()
-        .template("{prefix}{spinner:.dim}{msg}")
-        .unwrap()
-        .tick_strings(SPINNER_TICKS);
+    let style = spinner_style("{prefix}{spinner:.dim}{msg}");
 
     let total = items.len();
     let mut bars = Vec::with_capacity(total);
@@ -167,10 +172,7 @@ where
         .template("{msg}")
         .unwrap()
         .tick_strings(SECTION_TICKS);
-    let item_style = ProgressStyle::default_spinner()
-        .template("{prefix}{spinner:.dim}{msg}")
-        .unwrap()
-        .tick_strings(SPINNER_TICKS);
+    let item_style = spinner_style("{prefix}{spinner:.dim}{msg}");
 
     let mut bars_and_items: Vec<(ProgressBar, Item)> = Vec::new();
     let section_count = sections.len();
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use serde::Serialize;
+
+/// Output style chosen with the global `--output` flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == OutputFormat::Json
+    }
+}
+
+/// Prints `value` to stdout as pretty JSON, the shape shared by every `--output json` command.
+pub fn emit<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", to_json(value)?);
+    Ok(())
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Sample {
+        name: &'static str,
+        count: u32,
+    }
+
+    #[test]
+    fn test_to_json_is_pretty() {
+        let json = to_json(&[Sample {
+            name: "a",
+            count: 1,
+        }])
+        .unwrap();
+        assert_eq!(
+            json,
+            "[\n  {\n    \"name\": \"a\",\n    \"count\": 1\n  }\n]"
+        );
+    }
+
+    #[test]
+    fn test_is_json() {
+        assert!(OutputFormat::Json.is_json());
+        assert!(!OutputFormat::default().is_json());
+    }
+}
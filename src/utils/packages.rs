@@ -1,5 +1,8 @@
-use crate::utils::project::ProjectType;
+use crate::utils::log::CommandExt;
+use crate::utils::project::{self, ProjectType};
 use anyhow::{Context, Result};
+use regex::Regex;
+use std::cmp::Ordering;
 use std::process::Command;
 
 pub fn list(project_type: ProjectType) -> Result<Vec<String>> {
@@ -7,13 +10,14 @@ pub fn list(project_type: ProjectType) -> Result<Vec<String>> {
         ProjectType::Uv => list_uv(),
         ProjectType::Poetry => list_poetry(),
         ProjectType::Cargo => list_cargo(),
+        ProjectType::Pip => list_pip(),
     }
 }
 
 fn list_uv() -> Result<Vec<String>> {
     let output = Command::new("uv")
         .args(["pip", "list"])
-        .output()
+        .logged_output()
         .context("Failed to run uv pip list")?;
 
     if !output.status.success() {
@@ -30,10 +34,71 @@ fn list_uv() -> Result<Vec<String>> {
     Ok(packages)
 }
 
+/// Packages named in `requirements.txt`, or everything `pip list` reports when there is no
+/// readable requirements file.
+fn list_pip() -> Result<Vec<String>> {
+    if let Ok(content) = std::fs::read_to_string("requirements.txt") {
+        return Ok(content
+            .lines()
+            .filter_map(parse_requirement)
+            .map(|(name, _)| name.to_string())
+            .collect());
+    }
+
+    let output = project::pip_command()?
+        .arg("list")
+        .logged_output()
+        .context("Failed to run pip list")?;
+
+    if !output.status.success() {
+        anyhow::bail!("pip list failed");
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let packages: Vec<String> = stdout
+        .lines()
+        .skip(2)
+        .filter_map(|line| line.split_whitespace().next().map(|s| s.to_string()))
+        .collect();
+
+    Ok(packages)
+}
+
+/// Parses one `requirements.txt` line into the package name and, when pinned with `==`, its
+/// version. Comments, blank lines and pip options (`-r`, `-e`, `--index-url`, ...) yield `None`.
+pub fn parse_requirement(line: &str) -> Option<(&str, Option<&str>)> {
+    let line = line.split(" #").next()?.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+        return None;
+    }
+    let spec = line.split(';').next()?.trim();
+    let name_end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(spec.len());
+    let name = &spec[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+    let rest = spec[name_end..].trim_start();
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => extras
+            .split_once(']')
+            .map_or("", |(_, after)| after)
+            .trim_start(),
+        None => rest,
+    };
+    let version = rest
+        .strip_prefix("==")
+        .filter(|v| !v.starts_with('='))
+        .map(|v| v.split(',').next().unwrap_or(v).trim())
+        .filter(|v| !v.is_empty());
+    Some((name, version))
+}
+
 fn list_poetry() -> Result<Vec<String>> {
     let output = Command::new("poetry")
         .arg("show")
-        .output()
+        .logged_output()
         .context("Failed to run poetry show")?;
 
     if !output.status.success() {
@@ -58,7 +123,7 @@ fn list_poetry() -> Result<Vec<String>> {
 fn list_cargo() -> Result<Vec<String>> {
     let output = Command::new("cargo")
         .args(["tree", "--depth", "1", "--format", "{p}"])
-        .output()
+        .logged_output()
         .context("Failed to run cargo tree")?;
 
     if !output.status.success() {
@@ -96,14 +161,26 @@ pub fn get_installed_version(project_type: ProjectType, package: &str) -> Result
         ProjectType::Uv => get_version_uv(package),
         ProjectType::Poetry => get_version_poetry(package),
         ProjectType::Cargo => get_version_cargo(package),
+        ProjectType::Pip => get_version_pip(package),
     }
 }
 
 fn get_version_uv(package: &str) -> Result<Option<String>> {
-    let output = Command::new("uv")
-        .args(["pip", "show", package])
-        .output()
-        .context("Failed to run uv pip show")?;
+    let mut cmd = Command::new("uv");
+    cmd.args(["pip", "show", package]);
+    get_version_pip_show(cmd, "uv pip show")
+}
+
+fn get_version_pip(package: &str) -> Result<Option<String>> {
+    let mut cmd = project::pip_command()?;
+    cmd.args(["show", package]);
+    get_version_pip_show(cmd, "pip show")
+}
+
+fn get_version_pip_show(mut cmd: Command, label: &str) -> Result<Option<String>> {
+    let output = cmd
+        .logged_output()
+        .with_context(|| format!("Failed to run {}", label))?;
     if !output.status.success() {
         return Ok(None);
     }
@@ -119,7 +196,7 @@ fn get_version_uv(package: &str) -> Result<Option<String>> {
 fn get_version_poetry(package: &str) -> Result<Option<String>> {
     let output = Command::new("poetry")
         .args(["show", package])
-        .output()
+        .logged_output()
         .context("Failed to run poetry show")?;
     if !output.status.success() {
         return Ok(None);
@@ -139,7 +216,7 @@ fn get_version_poetry(package: &str) -> Result<Option<String>> {
 fn get_version_cargo(package: &str) -> Result<Option<String>> {
     let output = Command::new("cargo")
         .args(["tree", "-p", package, "--depth", "0"])
-        .output()
+        .logged_output()
         .context("Failed to run cargo tree")?;
     if !output.status.success() {
         return Ok(None);
@@ -163,7 +240,89 @@ fn get_version_cargo(package: &str) -> Result<Option<String>> {
     Ok(None)
 }
 
+pub fn latest_version(project_type: ProjectType, package: &str) -> Result<String> {
+    match project_type {
+        ProjectType::Cargo => latest_version_crates_io(package),
+        ProjectType::Uv | ProjectType::Poetry | ProjectType::Pip => latest_version_pypi(package),
+    }
+}
+
+pub fn latest_version_crates_io(package: &str) -> Result<String> {
+    let output = Command::new("curl")
+        .args([
+            "-s",
+            &format!("https://crates.io/api/v1/crates/{}", package),
+        ])
+        .logged_output()
+        .context("Failed to fetch latest version from crates.io")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to fetch latest version");
+    }
+
+    let response = String::from_utf8(output.stdout)?;
+    let re = Regex::new(r#""newest_version"\s*:\s*"([^"]+)""#).context("Failed to create regex")?;
+
+    let version = re
+        .captures(&response)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+        .context("No newest_version in crates.io API response")?;
+
+    Ok(version)
+}
+
+pub fn latest_version_pypi(package: &str) -> Result<String> {
+    let output = Command::new("curl")
+        .args(["-s", &format!("https://pypi.org/pypi/{}/json", package)])
+        .logged_output()
+        .context("Failed to fetch latest version from PyPI")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to fetch latest version from PyPI");
+    }
+
+    let response = String::from_utf8(output.stdout)?;
+    let re = Regex::new(r#""version"\s*:\s*"([^"]+)""#).context("Failed to create regex")?;
+    let version = re
+        .captures(&response)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+        .context("No version in PyPI API response (package may not exist)")?;
+    Ok(version)
+}
+
+pub fn cmp_version(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('v');
+    let b = b.trim_start_matches('v');
+    let parts_a: Vec<u64> = a
+        .split('.')
+        .filter_map(|s| s.split('-').next())
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    let parts_b: Vec<u64> = b
+        .split('.')
+        .filter_map(|s| s.split('-').next())
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    for (pa, pb) in parts_a.iter().zip(parts_b.iter()) {
+        match pa.cmp(pb) {
+            Ordering::Equal => continue,
+            o => return o,
+        }
+    }
+    parts_a.len().cmp(&parts_b.len())
+}
+
 pub fn fuzzy_match(packages: &[String], pattern: &str) -> Result<Vec<String>> {
+    Ok(fuzzy_match_scored(packages, pattern)
+        .into_iter()
+        .map(|(_, pkg)| pkg)
+        .collect())
+}
+
+/// Packages matching `pattern` with their skim scores, best match first.
+pub fn fuzzy_match_scored(packages: &[String], pattern: &str) -> Vec<(i64, String)> {
     use fuzzy_matcher::skim::SkimMatcherV2;
     use fuzzy_matcher::FuzzyMatcher;
 
@@ -178,7 +337,28 @@ pub fn fuzzy_match(packages: &[String], pattern: &str) -> Result<Vec<String>> {
         .collect();
 
     matches.sort_by(|a, b| b.0.cmp(&a.0));
-    Ok(matches.into_iter().map(|(_, pkg)| pkg).collect())
+    matches
+}
+
+/// Skim score of `pattern` against `candidate`, or `None` when it doesn't match at all.
+pub fn fuzzy_score(candidate: &str, pattern: &str) -> Option<i64> {
+    use fuzzy_matcher::skim::SkimMatcherV2;
+    use fuzzy_matcher::FuzzyMatcher;
+
+    SkimMatcherV2::default().fuzzy_match(candidate, pattern)
+}
+
+/// Like [`fuzzy_match`], but drops matches scoring below `min_score` (when set).
+pub fn fuzzy_match_min_score(
+    packages: &[String],
+    pattern: &str,
+    min_score: Option<i64>,
+) -> Vec<String> {
+    fuzzy_match_scored(packages, pattern)
+        .into_iter()
+        .filter(|(score, _)| min_score.is_none_or(|min| *score >= min))
+        .map(|(_, pkg)| pkg)
+        .collect()
 }
 
 pub fn select_one(matches: Vec<String>) -> Result<String> {
@@ -233,12 +413,18 @@ pub fn select_one_with_auto_select(matches: Vec<String>, auto_select: bool) -> R
 }
 
 fn select_with_inquire(matches: &[String]) -> Result<String> {
+    prompt_select("Select a package:", matches.to_vec(), 0)
+}
+
+/// Interactive single-choice picker with the cursor starting on `starting`.
+pub fn prompt_select(message: &str, options: Vec<String>, starting: usize) -> Result<String> {
     use inquire::Select;
 
-    let selected = Select::new("Select a package:", matches.to_vec())
+    let selected = Select::new(message, options)
         .with_page_size(10)
+        .with_starting_cursor(starting)
         .prompt()
-        .context("Failed to select package")?;
+        .context("Failed to read selection")?;
 
     Ok(selected)
 }
@@ -411,6 +597,45 @@ mod tests {
         assert!(matches.contains(&"clap".to_string()));
     }
 
+    #[test]
+    fn test_fuzzy_match_scored_orders_by_score() {
+        let packages = vec![
+            "clap-utils".to_string(),
+            "anyhow".to_string(),
+            "clap".to_string(),
+            "cargo-lambda-plugin".to_string(),
+        ];
+        let matches = fuzzy_match_scored(&packages, "clap");
+        assert_eq!(matches.len(), 3);
+        assert!(matches.windows(2).all(|w| w[0].0 >= w[1].0));
+        assert_eq!(matches[2].1, "cargo-lambda-plugin");
+        assert!(matches[2].0 < matches[0].0);
+        assert!(!matches.iter().any(|(_, pkg)| pkg == "anyhow"));
+    }
+
+    #[test]
+    fn test_fuzzy_match_min_score_drops_weak_matches() {
+        let packages = vec![
+            "clap".to_string(),
+            "cargo-lambda-plugin".to_string(),
+            "clap-derive".to_string(),
+        ];
+        let scored = fuzzy_match_scored(&packages, "clap");
+        let best = scored[0].0;
+        let weakest = scored.last().unwrap().0;
+        assert!(weakest < best);
+
+        assert_eq!(
+            fuzzy_match_min_score(&packages, "clap", None).len(),
+            scored.len()
+        );
+        assert_eq!(
+            fuzzy_match_min_score(&packages, "clap", Some(best)),
+            vec!["clap", "clap-derive"]
+        );
+        assert!(fuzzy_match_min_score(&packages, "clap", Some(best + 1)).is_empty());
+    }
+
     #[test]
     fn test_fuzzy_match_multiple_words() {
         let packages = vec!["clap-derive".to_string(), "clap-utils".to_string()];
@@ -604,6 +829,43 @@ mod tests {
         let _ = list(ProjectType::Uv);
         let _ = list(ProjectType::Poetry);
         let _ = list(ProjectType::Cargo);
+        let _ = list(ProjectType::Pip);
+    }
+
+    #[test]
+    fn test_parse_requirement_pinned() {
+        assert_eq!(
+            parse_requirement("requests==2.31.0"),
+            Some(("requests", Some("2.31.0")))
+        );
+        assert_eq!(
+            parse_requirement("uvicorn[standard] == 0.29.0 ; python_version >= \"3.8\""),
+            Some(("uvicorn", Some("0.29.0")))
+        );
+        assert_eq!(
+            parse_requirement("flask==3.0.2  # web"),
+            Some(("flask", Some("3.0.2")))
+        );
+    }
+
+    #[test]
+    fn test_parse_requirement_unpinned() {
+        assert_eq!(parse_requirement("numpy"), Some(("numpy", None)));
+        assert_eq!(parse_requirement("django>=4.2,<5"), Some(("django", None)));
+        assert_eq!(parse_requirement("attrs~=23.1"), Some(("attrs", None)));
+        assert_eq!(parse_requirement("pkg===1.0"), Some(("pkg", None)));
+    }
+
+    #[test]
+    fn test_parse_requirement_skips_non_packages() {
+        assert_eq!(parse_requirement(""), None);
+        assert_eq!(parse_requirement("   # pinned for CI"), None);
+        assert_eq!(parse_requirement("-r base.txt"), None);
+        assert_eq!(
+            parse_requirement("--index-url https://example.com/simple"),
+            None
+        );
+        assert_eq!(parse_requirement("-e ."), None);
     }
 
     #[test]
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+
+/// Compile-size caps for user-supplied patterns. The regex crate matches in linear time, but
+/// large counted repetitions can blow up the compiled program; those are rejected up front.
+pub const REGEX_SIZE_LIMIT: usize = 1 << 20;
+pub const REGEX_DFA_SIZE_LIMIT: usize = 2 << 20;
+
+pub fn build_regex(pattern: &str, case_insensitive: bool) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(REGEX_DFA_SIZE_LIMIT)
+        .build()
+}
+
+/// A pod/container name pattern: a regex when it parses, otherwise a case-insensitive literal.
+/// Fails only when the pattern is too large to compile within the limits.
+pub fn name_pattern_regex(pattern: &str) -> Result<Regex> {
+    match build_regex(pattern, false) {
+        Ok(re) => Ok(re),
+        Err(regex::Error::CompiledTooBig(limit)) => anyhow::bail!(
+            "Pattern \"{}\" is too complex (compiled size exceeds {} KiB); simplify it",
+            pattern,
+            limit / 1024
+        ),
+        Err(_) => build_regex(&regex::escape(pattern), true)
+            .map_err(|e| anyhow::anyhow!("Pattern \"{}\" could not be compiled: {}", pattern, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversized_pattern_is_rejected() {
+        let err = name_pattern_regex(r"\w{1000}\w{1000}\w{1000}").unwrap_err();
+        assert!(err.to_string().contains("too complex"), "{err}");
+        assert!(build_regex(r"\w{1000}\w{1000}\w{1000}", true).is_err());
+    }
+}
@@ -1,64 +1,161 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use which::which;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
 pub enum ProjectType {
     Uv,
     Poetry,
     Cargo,
+    Pip,
 }
 
-pub fn detect() -> Result<Option<ProjectType>> {
-    let detection_rules: Vec<(bool, &str, ProjectType)> = vec![
-        (
-            Path::new("Cargo.toml").exists(),
-            "cargo",
-            ProjectType::Cargo,
-        ),
-        (
-            Path::new("uv.lock").exists() || has_project_section(),
-            "uv",
-            ProjectType::Uv,
-        ),
-        (
-            Path::new("poetry.lock").exists() || has_poetry_section(),
-            "poetry",
-            ProjectType::Poetry,
-        ),
-    ];
+/// The project types found in the nearest directory with a manifest, and that directory.
+/// A monorepo root can hold several (e.g. a `Cargo.toml` next to a `pyproject.toml`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub kinds: Vec<ProjectType>,
+    pub root: PathBuf,
+}
 
-    for (condition, tool, project_type) in detection_rules {
-        if condition {
-            which(tool).with_context(|| format!("{} not found on PATH", tool))?;
-            return Ok(Some(project_type));
-        }
+impl Project {
+    /// Picks `requested`, or the only detected type; fails when the choice is ambiguous.
+    /// The chosen type's tool must be on PATH.
+    pub fn select(&self, requested: Option<ProjectType>) -> Result<ProjectType> {
+        let kind = match (requested, self.kinds.as_slice()) {
+            (Some(kind), kinds) if kinds.contains(&kind) => kind,
+            (Some(kind), _) => anyhow::bail!(
+                "No {} project found in {} (found: {})",
+                kind.tool(),
+                self.root.display(),
+                self.kind_list()
+            ),
+            (None, [kind]) => *kind,
+            (None, _) => anyhow::bail!(
+                "Multiple project types found in {} ({}); pass --project to pick one",
+                self.root.display(),
+                self.kind_list()
+            ),
+        };
+        let program = kind.required_program();
+        which(program).with_context(|| format!("{program} not found on PATH"))?;
+        Ok(kind)
     }
 
-    Ok(None)
+    fn kind_list(&self) -> String {
+        self.kinds
+            .iter()
+            .map(|kind| kind.tool())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub fn detect() -> Result<Option<Project>> {
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    Ok(detect_from(&cwd))
 }
 
-fn has_project_section() -> bool {
-    if Path::new("pyproject.toml").exists() {
-        {
-            std::fs::read_to_string("pyproject.toml")
-                .map(|content| content.contains("[project]"))
-                .unwrap_or(false)
+/// Detects the project in `start` or its nearest ancestor that has a manifest, stopping at a
+/// `.git` boundary so a repository never picks up a project that merely contains it.
+pub fn detect_from(start: &Path) -> Option<Project> {
+    for dir in start.ancestors() {
+        let kinds = detect_in(dir);
+        if !kinds.is_empty() {
+            return Some(Project {
+                kinds,
+                root: dir.to_path_buf(),
+            });
+        }
+        if dir.join(".git").exists() {
+            break;
         }
-    } else {
-        false
     }
+    None
 }
 
-fn has_poetry_section() -> bool {
-    if Path::new("pyproject.toml").exists() {
-        {
-            std::fs::read_to_string("pyproject.toml")
-                .map(|content| content.contains("[tool.poetry]"))
-                .unwrap_or(false)
-        }
+/// Detects the project, picks its type (see [`Project::select`]) and makes its root the
+/// working directory, so relative paths and the tools run afterwards act on that project
+/// even when invoked from a subdirectory.
+pub fn enter(requested: Option<ProjectType>) -> Result<ProjectType> {
+    let project = detect()?.context("No uv/poetry/pip/cargo project found")?;
+    let kind = project.select(requested)?;
+    std::env::set_current_dir(&project.root)
+        .with_context(|| format!("Failed to change to {}", project.root.display()))?;
+    Ok(kind)
+}
+
+/// The project types in `dir`. A `pyproject.toml` counts once: uv when `uv.lock` exists,
+/// otherwise poetry when it has a poetry lock or section, otherwise uv for a `[project]`.
+/// A bare `requirements.txt` is pip only when none of those markers is present.
+fn detect_in(dir: &Path) -> Vec<ProjectType> {
+    let mut kinds = Vec::new();
+    if dir.join("Cargo.toml").exists() {
+        kinds.push(ProjectType::Cargo);
+    }
+    if dir.join("uv.lock").exists() {
+        kinds.push(ProjectType::Uv);
+    } else if dir.join("poetry.lock").exists() || has_poetry_section(dir) {
+        kinds.push(ProjectType::Poetry);
+    } else if has_project_section(dir) {
+        kinds.push(ProjectType::Uv);
+    } else if dir.join("requirements.txt").exists() {
+        kinds.push(ProjectType::Pip);
+    }
+    kinds
+}
+
+fn has_project_section(dir: &Path) -> bool {
+    pyproject_contains(dir, "[project]")
+}
+
+fn has_poetry_section(dir: &Path) -> bool {
+    pyproject_contains(dir, "[tool.poetry]")
+}
+
+fn pyproject_contains(dir: &Path, section: &str) -> bool {
+    std::fs::read_to_string(dir.join("pyproject.toml"))
+        .map(|content| content.contains(section))
+        .unwrap_or(false)
+}
+
+/// The virtualenv `bashers setup` creates for pip projects.
+pub const VENV_DIR: &str = ".venv";
+
+/// The interpreter pip projects' virtualenvs are created with.
+pub const PYTHON: &str = if cfg!(windows) { "python" } else { "python3" };
+
+/// The `pip` executable inside the virtualenv at `venv`.
+pub fn venv_pip(venv: &Path) -> PathBuf {
+    if cfg!(windows) {
+        venv.join("Scripts").join("pip.exe")
     } else {
-        false
+        venv.join("bin").join("pip")
+    }
+}
+
+/// A `pip` command for the project's `.venv`, or for the active virtualenv when there is none,
+/// so packages never land in the global or user interpreter.
+pub fn pip_command() -> Result<Command> {
+    let venv_dir = std::env::var_os("VIRTUAL_ENV");
+    let pip = pip_program(Path::new(VENV_DIR), venv_dir.as_deref())?;
+    Ok(Command::new(pip))
+}
+
+fn pip_program(venv: &Path, active: Option<&OsStr>) -> Result<PathBuf> {
+    let project_pip = venv_pip(venv);
+    if project_pip.is_file() {
+        return Ok(project_pip);
+    }
+    match active {
+        Some(dir) if !dir.is_empty() => Ok(venv_pip(Path::new(dir))),
+        _ => anyhow::bail!(
+            "No virtualenv found: run `bashers setup` to create {VENV_DIR}, or activate one"
+        ),
     }
 }
 
@@ -74,6 +171,29 @@ impl ProjectType {
     pub fn is_cargo(&self) -> bool {
         matches!(self, ProjectType::Cargo)
     }
+
+    pub fn is_pip(&self) -> bool {
+        matches!(self, ProjectType::Pip)
+    }
+
+    /// The program that has to be on PATH. Pip projects only need Python: `setup` creates
+    /// the venv and installs with the venv's own pip.
+    pub fn required_program(&self) -> &'static str {
+        match self {
+            ProjectType::Pip => PYTHON,
+            _ => self.tool(),
+        }
+    }
+
+    /// The command-line tool that manages this kind of project.
+    pub fn tool(&self) -> &'static str {
+        match self {
+            ProjectType::Uv => "uv",
+            ProjectType::Poetry => "poetry",
+            ProjectType::Cargo => "cargo",
+            ProjectType::Pip => "pip",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -99,6 +219,93 @@ mod tests {
         assert!(ProjectType::Cargo.is_cargo());
         assert!(!ProjectType::Cargo.is_uv());
         assert!(!ProjectType::Cargo.is_poetry());
+
+        assert!(ProjectType::Pip.is_pip());
+        assert!(!ProjectType::Pip.is_uv());
+        assert!(!ProjectType::Cargo.is_pip());
+    }
+
+    fn temp_tree(name: &str) -> std::path::PathBuf {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("SystemTime before UNIX_EPOCH")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("bashers_{}_{}", name, timestamp));
+        fs::create_dir_all(root.join("a/b")).expect("Failed to create test directory");
+        root
+    }
+
+    #[test]
+    fn test_detect_from_finds_manifest_two_levels_up() {
+        let root = temp_tree("detect_parent");
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"test\"")
+            .expect("Failed to write Cargo.toml");
+
+        let project = detect_from(&root.join("a/b")).unwrap();
+        assert_eq!(project.kinds, vec![ProjectType::Cargo]);
+        assert_eq!(project.root, root);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_detect_from_stops_at_git_boundary() {
+        let root = temp_tree("detect_git");
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"test\"")
+            .expect("Failed to write Cargo.toml");
+        fs::create_dir_all(root.join("a/.git")).expect("Failed to create .git");
+
+        assert_eq!(detect_from(&root.join("a/b")), None);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_detect_from_monorepo_lists_every_type() {
+        let root = temp_tree("detect_monorepo");
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"test\"")
+            .expect("Failed to write Cargo.toml");
+        fs::write(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"test\"\n\n[tool.poetry]\nname = \"test\"",
+        )
+        .expect("Failed to write pyproject.toml");
+
+        let project = detect_from(&root).unwrap();
+        assert_eq!(project.kinds, vec![ProjectType::Cargo, ProjectType::Poetry]);
+        let err = project.select(None).unwrap_err().to_string();
+        assert!(
+            err.contains("cargo, poetry") && err.contains("--project"),
+            "{err}"
+        );
+        assert_eq!(
+            project.select(Some(ProjectType::Cargo)).unwrap(),
+            ProjectType::Cargo
+        );
+        assert!(project.select(Some(ProjectType::Uv)).is_err());
+
+        fs::write(root.join("uv.lock"), "").expect("Failed to write uv.lock");
+        let project = detect_from(&root).unwrap();
+        assert_eq!(project.kinds, vec![ProjectType::Cargo, ProjectType::Uv]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_detect_from_requirements_only_without_pyproject_markers() {
+        let root = temp_tree("detect_pip");
+        fs::write(root.join("requirements.txt"), "requests==2.31.0\n")
+            .expect("Failed to write requirements.txt");
+
+        let project = detect_from(&root).unwrap();
+        assert_eq!(project.kinds, vec![ProjectType::Pip]);
+
+        fs::write(root.join("poetry.lock"), "").expect("Failed to write poetry.lock");
+        let project = detect_from(&root).unwrap();
+        assert_eq!(project.kinds, vec![ProjectType::Poetry]);
+
+        fs::remove_dir_all(&root).ok();
     }
 
     #[test]
@@ -134,7 +341,7 @@ mod tests {
             "Failed to change to test directory"
         );
 
-        let result = has_project_section();
+        let result = has_project_section(Path::new("."));
         assert!(
             result,
             "has_project_section() should return true when [project] section exists"
@@ -166,7 +373,7 @@ mod tests {
         let original_dir = std::env::current_dir().expect("Failed to get current directory");
         std::env::set_current_dir(test_dir).expect("Failed to change to test directory");
 
-        let result = has_project_section();
+        let result = has_project_section(Path::new("."));
         assert!(!result);
 
         std::env::set_current_dir(original_dir).expect("Failed to restore original directory");
@@ -192,7 +399,7 @@ mod tests {
         let original_dir = std::env::current_dir().expect("Failed to get current directory");
         std::env::set_current_dir(test_dir).expect("Failed to change to test directory");
 
-        let result = has_project_section();
+        let result = has_project_section(Path::new("."));
         assert!(!result);
 
         std::env::set_current_dir(original_dir).expect("Failed to restore original directory");
@@ -218,7 +425,7 @@ mod tests {
         let original_dir = std::env::current_dir().expect("Failed to get current directory");
         std::env::set_current_dir(test_dir).expect("Failed to change to test directory");
 
-        let result = has_poetry_section();
+        let result = has_poetry_section(Path::new("."));
         assert!(result);
 
         std::env::set_current_dir(original_dir).expect("Failed to restore original directory");
@@ -240,7 +447,7 @@ mod tests {
         let original_dir = std::env::current_dir().expect("Failed to get current directory");
         std::env::set_current_dir(test_dir).expect("Failed to change to test directory");
 
-        let result = has_poetry_section();
+        let result = has_poetry_section(Path::new("."));
         assert!(!result);
 
         std::env::set_current_dir(original_dir).expect("Failed to restore original directory");
@@ -266,13 +473,41 @@ mod tests {
         let original_dir = std::env::current_dir().expect("Failed to get current directory");
         std::env::set_current_dir(test_dir).expect("Failed to change to test directory");
 
-        let result = has_poetry_section();
+        let result = has_poetry_section(Path::new("."));
         assert!(!result);
 
         std::env::set_current_dir(original_dir).expect("Failed to restore original directory");
         fs::remove_dir_all(test_dir).ok();
     }
 
+    #[test]
+    fn test_pip_program_prefers_project_venv() {
+        let root = temp_tree("pip_program");
+        let venv = root.join(VENV_DIR);
+        let active = OsStr::new("/opt/active-venv");
+
+        assert!(pip_program(&venv, None).is_err());
+        assert!(pip_program(&venv, Some(OsStr::new(""))).is_err());
+        assert_eq!(
+            pip_program(&venv, Some(active)).unwrap(),
+            venv_pip(Path::new("/opt/active-venv"))
+        );
+
+        let pip = venv_pip(&venv);
+        fs::create_dir_all(pip.parent().unwrap()).unwrap();
+        fs::write(&pip, "").unwrap();
+        assert_eq!(pip_program(&venv, Some(active)).unwrap(), pip);
+        assert_eq!(pip_program(&venv, None).unwrap(), pip);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_required_program() {
+        assert_eq!(ProjectType::Pip.required_program(), PYTHON);
+        assert_eq!(ProjectType::Uv.required_program(), "uv");
+        assert_eq!(ProjectType::Cargo.required_program(), "cargo");
+    }
+
     #[test]
     fn test_project_type_equality() {
         assert_eq!(ProjectType::Uv, ProjectType::Uv);
@@ -0,0 +1,130 @@
+use anyhow::Result;
+
+/// Splits a command string into argv the way a POSIX shell would tokenize it (quotes and
+/// backslash escapes), without expanding variables, globs or running anything.
+pub fn split(input: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("unterminated single quote in: {}", input),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => anyhow::bail!("unterminated double quote in: {}", input),
+                        },
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("unterminated double quote in: {}", input),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some('\n') => {}
+                    Some(c) => current.push(c),
+                    None => anyhow::bail!("trailing backslash in: {}", input),
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Quotes `arg` for display as part of a shell command line.
+pub fn quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || "'\"$`\\".contains(c)) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_plain_words() {
+        assert_eq!(
+            split("  kubectl get   pods -A ").unwrap(),
+            vec!["kubectl", "get", "pods", "-A"]
+        );
+        assert!(split("").unwrap().is_empty());
+        assert!(split("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_split_quotes() {
+        assert_eq!(
+            split(r#"grep 'a b' "c d" e"f"g"#).unwrap(),
+            vec!["grep", "a b", "c d", "efg"]
+        );
+        assert_eq!(split(r#"echo '' """#).unwrap(), vec!["echo", "", ""]);
+        assert_eq!(split(r#"echo 'it"s'"#).unwrap(), vec!["echo", "it\"s"]);
+    }
+
+    #[test]
+    fn test_split_escapes() {
+        assert_eq!(split(r"echo a\ b \'x").unwrap(), vec!["echo", "a b", "'x"]);
+        assert_eq!(
+            split(r#"echo "say \"hi\" \$HOME \n""#).unwrap(),
+            vec!["echo", r#"say "hi" $HOME \n"#]
+        );
+    }
+
+    #[test]
+    fn test_split_does_not_expand() {
+        assert_eq!(
+            split("echo $HOME; rm -rf * | cat").unwrap(),
+            vec!["echo", "$HOME;", "rm", "-rf", "*", "|", "cat"]
+        );
+    }
+
+    #[test]
+    fn test_split_unterminated() {
+        assert!(split("echo 'oops").is_err());
+        assert!(split("echo \"oops").is_err());
+        assert!(split("echo oops\\").is_err());
+    }
+
+    #[test]
+    fn test_quote_round_trips_through_split() {
+        for arg in ["A=1", "two words", "it's", "", "$HOME", r"back\slash"] {
+            assert_eq!(split(&quote(arg)).unwrap(), vec![arg.to_string()]);
+        }
+    }
+}
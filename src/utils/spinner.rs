@@ -1,8 +1,9 @@
+use crate::utils::colors;
 use anyhow::{Context, Result};
 use spinoff::{spinners, Color, Spinner, Streams};
 use std::io::Write;
 use std::process::{Command, ExitStatus};
-use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
+use termcolor::{ColorSpec, StandardStream, WriteColor};
 
 pub fn create_spinner(msg: &str) -> Option<Spinner> {
     if !should_show_spinner() {
@@ -31,11 +32,7 @@ pub fn stop_spinner(sp: Option<&mut Spinner>) {
 }
 
 pub fn print_success_message(message: &str) {
-    let mut stderr = StandardStream::stderr(if atty::is(atty::Stream::Stderr) {
-        ColorChoice::Auto
-    } else {
-        ColorChoice::Never
-    });
+    let mut stderr = StandardStream::stderr(colors::color_choice(atty::Stream::Stderr));
     let _ = stderr.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Green)));
     let _ = writeln!(stderr, "✓ {}", message);
     let _ = stderr.reset();
@@ -43,11 +40,7 @@ pub fn print_success_message(message: &str) {
 }
 
 pub fn print_success_message_replace_line(message: &str) {
-    let mut stderr = StandardStream::stderr(if atty::is(atty::Stream::Stderr) {
-        ColorChoice::Auto
-    } else {
-        ColorChoice::Never
-    });
+    let mut stderr = StandardStream::stderr(colors::color_choice(atty::Stream::Stderr));
     let _ = write!(stderr, "\r\x1b[K");
     let _ = stderr.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Green)));
     let _ = writeln!(stderr, "✓ {}", message);
@@ -56,17 +49,21 @@ pub fn print_success_message_replace_line(message: &str) {
 }
 
 pub fn print_failure_message(message: &str) {
-    let mut stderr = StandardStream::stderr(if atty::is(atty::Stream::Stderr) {
-        ColorChoice::Auto
-    } else {
-        ColorChoice::Never
-    });
+    let mut stderr = StandardStream::stderr(colors::color_choice(atty::Stream::Stderr));
     let _ = stderr.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Red)));
     let _ = writeln!(stderr, "✗ {}", message);
     let _ = stderr.reset();
     let _ = stderr.flush();
 }
 
+pub fn print_warning_message(message: &str) {
+    let mut stderr = StandardStream::stderr(colors::color_choice(atty::Stream::Stderr));
+    let _ = stderr.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Yellow)));
+    let _ = writeln!(stderr, "⚠ {}", message);
+    let _ = stderr.reset();
+    let _ = stderr.flush();
+}
+
 pub fn should_show_spinner() -> bool {
     if std::env::var("NO_SPINNER").is_ok() {
         return false;
@@ -75,7 +72,11 @@ pub fn should_show_spinner() -> bool {
 }
 
 fn colorize_spinner_message(msg: &str, color: Color) -> String {
-    if !atty::is(atty::Stream::Stderr) {
+    paint_spinner_message(msg, color, colors::use_color(atty::Stream::Stderr))
+}
+
+fn paint_spinner_message(msg: &str, color: Color, use_color: bool) -> String {
+    if !use_color {
         return msg.to_string();
     }
     let code = match color {
@@ -180,6 +181,18 @@ mod tests {
     use super::*;
     use std::process::Command;
 
+    #[test]
+    fn test_paint_spinner_message_without_color() {
+        assert_eq!(
+            paint_spinner_message("Pulling", Color::Green, false),
+            "Pulling"
+        );
+        assert_eq!(
+            paint_spinner_message("Pulling", Color::Green, true),
+            "\x1b[32mPulling\x1b[0m"
+        );
+    }
+
     #[test]
     fn test_should_show_spinner() {
         let _: bool = should_show_spinner();